@@ -0,0 +1,424 @@
+//! Command-line front end to the conversion service, for scripts and CI checks
+//! that need conversions without starting the server.
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+use clap::{Parser, Subcommand, ValueEnum};
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use serde::Serialize;
+use unit_conversion_api::config::AppConfig;
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models;
+use unit_conversion_api::models::decimal::DecimalValue;
+use unit_conversion_api::models::import::ImportFormat;
+use unit_conversion_api::models::tolerance::Tolerance;
+use unit_conversion_api::services::{ConversionRequest, ConversionResponse, ConversionService, ReplOutput, ReplSession};
+
+#[derive(Parser)]
+#[command(name = "unitconv", version, about = "Convert between units without starting the server")]
+struct Cli {
+    /// How to print results
+    #[arg(long, short, value_enum, default_value_t = Format::Text, global = true)]
+    format: Format,
+
+    /// GNU units or Pint database to import before running the command
+    #[arg(long, value_name = "FILE", global = true)]
+    import: Vec<String>,
+
+    /// Format of the imported databases, guessed when omitted
+    #[arg(long, value_name = "gnu|pint", global = true)]
+    import_format: Option<ImportFormat>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Tsv,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert a value from one unit to another
+    Convert {
+        #[arg(allow_negative_numbers = true)]
+        value: DecimalValue,
+        from: String,
+        to: String,
+        #[command(flatten)]
+        options: ConvertOptions,
+    },
+    /// Tell whether the first measurement is less than, equal to or greater than the second
+    Compare {
+        #[arg(allow_negative_numbers = true)]
+        value1: f64,
+        unit1: String,
+        #[arg(allow_negative_numbers = true)]
+        value2: f64,
+        unit2: String,
+        /// Category both units belong to, inferred from the units when omitted
+        #[arg(long, short)]
+        category: Option<String>,
+        /// Treat the measurements as equal within this much of the second unit
+        #[arg(long)]
+        abs_tolerance: Option<f64>,
+        /// Treat the measurements as equal within this fraction of the larger
+        #[arg(long)]
+        rel_tolerance: Option<f64>,
+        /// Treat the measurements as equal within this many representable doubles
+        #[arg(long)]
+        ulp_tolerance: Option<u64>,
+    },
+    /// List categories or the units of a category
+    List {
+        #[command(subcommand)]
+        what: ListCommand,
+    },
+    /// Convert the values in one column of standard input, line by line
+    Pipe {
+        from: String,
+        to: String,
+        /// Column holding the values, counting from 1
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        column: u64,
+        /// Character separating columns, any run of whitespace when omitted
+        #[arg(long, short)]
+        delimiter: Option<char>,
+        /// Pass the first line through unconverted
+        #[arg(long)]
+        header: bool,
+        #[command(flatten)]
+        options: ConvertOptions,
+    },
+    /// Start an interactive session with variables, such as `d = 12 mi`, and
+    /// expressions, such as `d / 45 min to km/h`
+    Repl,
+}
+
+#[derive(Subcommand)]
+enum ListCommand {
+    /// List every category
+    Categories,
+    /// List the unit symbols of a category
+    Units { category: String },
+}
+
+#[derive(clap::Args)]
+struct ConvertOptions {
+    /// Category of the units, which are parsed as unit expressions when omitted
+    #[arg(long, short)]
+    category: Option<String>,
+    /// Also give the unrounded result as a fraction
+    #[arg(long)]
+    exact: bool,
+    /// Also give the result as a decimal rounded to this many significant digits
+    #[arg(long)]
+    precision: Option<u64>,
+}
+
+impl ConvertOptions {
+    fn request(&self, value: DecimalValue, from: &str, to: &str) -> ConversionRequest {
+        ConversionRequest {
+            value,
+            from_category: self.category.clone(),
+            from_unit: from.to_string(),
+            to_unit: to.to_string(),
+            exact: self.exact,
+            precision: self.precision,
+            ..Default::default()
+        }
+    }
+}
+
+/// Relation printed for a comparison, as the HTTP API names it
+#[derive(Serialize)]
+struct Comparison {
+    result: i8,
+    relation: &'static str,
+    difference: f64,
+    ratio: Option<f64>,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if let Err(err) = setup(&cli) {
+        eprintln!("unitconv: {}", err);
+        return ExitCode::FAILURE;
+    }
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let result = run(&cli, &mut out).and_then(|ok| {
+        out.flush().map_err(|err| ApiError::InternalError(err.to_string()))?;
+        Ok(ok)
+    });
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("unitconv: {}", err);
+            ExitCode::FAILURE
+        },
+    }
+}
+
+/// Loads the same units the server would: the built-in ones, the definition
+/// files under config/, any imported databases and the configured exchange rates
+fn setup(cli: &Cli) -> Result<(), ApiError> {
+    let rates_file = AppConfig::from_env()
+        .map_err(|err| ApiError::BadRequest(err.to_string()))?
+        .rates_file;
+    let setup = models::init::setup(&cli.import, cli.import_format, rates_file.as_deref())?;
+    for (path, report) in &setup.imports {
+        for warning in &report.warnings {
+            eprintln!("unitconv: {}: {}", path, warning);
+        }
+    }
+    Ok(())
+}
+
+/// Runs the command, returning whether every conversion succeeded
+fn run(cli: &Cli, out: &mut impl Write) -> Result<bool, ApiError> {
+    match &cli.command {
+        Command::Convert { value, from, to, options } => {
+            let response = ConversionService::convert(options.request(value.clone(), from, to))?;
+            match cli.format {
+                Format::Text => write_line(out, &format!(
+                    "{} {} = {} {}", response.from_value, response.from_unit, result_value(&response), response.to_unit
+                ))?,
+                Format::Json => write_json(out, &response)?,
+                Format::Tsv => write_line(out, &conversion_row(&response).join("\t"))?,
+            }
+            Ok(true)
+        },
+        Command::Compare { value1, unit1, value2, unit2, category, abs_tolerance, rel_tolerance, ulp_tolerance } => {
+            let tolerance = Tolerance::new(*abs_tolerance, *rel_tolerance, *ulp_tolerance)?;
+            let comparison = ConversionService::compare_within(
+                *value1, unit1, *value2, unit2, category.as_deref(), &tolerance
+            )?;
+            let result = comparison.result;
+            let relation = match result {
+                -1 => "less_than",
+                0 => "equal",
+                _ => "greater_than",
+            };
+            match cli.format {
+                Format::Text => write_line(out, &format!(
+                    "{} {} is {} {} {}", value1, unit1, relation.replace('_', " "), value2, unit2
+                ))?,
+                Format::Json => write_json(out, &Comparison {
+                    result, relation, difference: comparison.difference, ratio: comparison.ratio
+                })?,
+                Format::Tsv => write_line(out, &format!("{}\t{}", result, relation))?,
+            }
+            Ok(true)
+        },
+        Command::List { what } => {
+            let names = match what {
+                ListCommand::Categories => ConversionService::list_categories()?,
+                ListCommand::Units { category } => ConversionService::list_units(category)?,
+            };
+            match cli.format {
+                Format::Json => write_json(out, &names)?,
+                Format::Text | Format::Tsv => {
+                    for name in &names {
+                        write_line(out, name)?;
+                    }
+                },
+            }
+            Ok(true)
+        },
+        Command::Pipe { from, to, column, delimiter, header, options } => {
+            let stdin = io::stdin();
+            pipe(stdin.lock(), out, cli.format, from, to, *column as usize - 1, *delimiter, *header, options)
+        },
+        Command::Repl => repl(),
+    }
+}
+
+/// Converts the value in `column` of each input line
+///
+/// Text output keeps each line as it was, separators included, with the value
+/// replaced by the result; TSV output joins the columns with tabs; JSON output
+/// writes one conversion per line. Lines that can't be converted are reported on stderr
+/// and passed through unchanged, so one bad row doesn't lose the rest.
+#[allow(clippy::too_many_arguments)]
+fn pipe(
+    input: impl BufRead,
+    out: &mut impl Write,
+    format: Format,
+    from: &str,
+    to: &str,
+    column: usize,
+    delimiter: Option<char>,
+    header: bool,
+    options: &ConvertOptions,
+) -> Result<bool, ApiError> {
+    let mut ok = true;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.map_err(|err| ApiError::InternalError(err.to_string()))?;
+        let spans = field_spans(&line, delimiter);
+        // The line with the field in `column` replaced by `value`, if one is given
+        let rewrite = |value: Option<&str>| match format {
+            Format::Text => match (value, spans.get(column)) {
+                (Some(value), Some(&(start, end))) => format!("{}{}{}", &line[..start], value, &line[end..]),
+                _ => line.clone(),
+            },
+            _ => spans.iter().enumerate()
+                .map(|(i, &(start, end))| value.filter(|_| i == column).unwrap_or(&line[start..end]))
+                .collect::<Vec<_>>()
+                .join("\t"),
+        };
+
+        if (header && index == 0) || spans.is_empty() {
+            if format != Format::Json {
+                write_line(out, &rewrite(None))?;
+            }
+            continue;
+        }
+
+        let converted = spans.get(column)
+            .ok_or_else(|| ApiError::BadRequest(format!("No column {}", column + 1)))
+            .and_then(|&(start, end)| line[start..end].parse::<DecimalValue>().map_err(ApiError::BadRequest))
+            .and_then(|value| ConversionService::convert(options.request(value, from, to)));
+
+        match converted {
+            Ok(response) if format == Format::Json => write_json(out, &response)?,
+            Ok(response) => write_line(out, &rewrite(Some(&result_value(&response))))?,
+            Err(err) => {
+                ok = false;
+                eprintln!("unitconv: line {}: {}", index + 1, err);
+                if format != Format::Json {
+                    write_line(out, &rewrite(None))?;
+                }
+            },
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Returns the byte range of each field of a line, split at `delimiter` or,
+/// without one, at runs of whitespace
+fn field_spans(line: &str, delimiter: Option<char>) -> Vec<(usize, usize)> {
+    match delimiter {
+        Some(delimiter) => {
+            let mut start = 0;
+            let mut spans = Vec::new();
+            for (i, c) in line.char_indices().filter(|&(_, c)| c == delimiter) {
+                spans.push((start, i));
+                start = i + c.len_utf8();
+            }
+            spans.push((start, line.len()));
+            spans
+        },
+        None => line.split_whitespace()
+            .map(|field| {
+                let start = field.as_ptr() as usize - line.as_ptr() as usize;
+                (start, start + field.len())
+            })
+            .collect(),
+    }
+}
+
+/// Completes variables, units and commands from the session being edited
+struct ReplHelper {
+    session: ReplSession,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(self.session.complete(line, pos))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+/// Runs the interactive session until `:quit` or end of input, keeping the
+/// history in ~/.unitconv_history between sessions
+fn repl() -> Result<bool, ApiError> {
+    let readline_error = |err: ReadlineError| ApiError::InternalError(err.to_string());
+    let mut editor: Editor<ReplHelper, DefaultHistory> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(ReplHelper { session: ReplSession::new() }));
+
+    let history = std::env::var_os("HOME").map(|home| std::path::Path::new(&home).join(".unitconv_history"));
+    if let Some(history) = &history {
+        let _ = editor.load_history(history);
+    }
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            // Ctrl-C abandons the line being typed, as in a shell
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(readline_error(err)),
+        };
+        let _ = editor.add_history_entry(line.as_str());
+
+        let session = &mut editor.helper_mut().expect("helper is set").session;
+        match session.eval(&line) {
+            Ok(ReplOutput::Text(text)) => println!("{}", text),
+            Ok(ReplOutput::Empty) => {},
+            Ok(ReplOutput::Quit) => break,
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    if let Some(history) = &history {
+        let _ = editor.save_history(history);
+    }
+    Ok(true)
+}
+
+/// The most precise form of the result that was asked for
+fn result_value(response: &ConversionResponse) -> String {
+    response.decimal_value.clone()
+        .or_else(|| response.exact_value.clone())
+        .unwrap_or_else(|| response.to_value.to_string())
+}
+
+/// TSV columns of a conversion: the value and unit converted from, the result and
+/// its unit, the category, then the exact and decimal results when they were asked for
+fn conversion_row(response: &ConversionResponse) -> Vec<String> {
+    let mut row = vec![
+        response.from_value.to_string(),
+        response.from_unit.clone(),
+        response.to_value.to_string(),
+        response.to_unit.clone(),
+        response.category.clone(),
+    ];
+    row.extend(response.exact_value.clone());
+    row.extend(response.decimal_value.clone());
+    row
+}
+
+fn write_line(out: &mut impl Write, line: &str) -> Result<(), ApiError> {
+    writeln!(out, "{}", line).map_err(|err| ApiError::InternalError(err.to_string()))
+}
+
+fn write_json(out: &mut impl Write, value: &impl Serialize) -> Result<(), ApiError> {
+    let json = serde_json::to_string(value).map_err(|err| ApiError::InternalError(err.to_string()))?;
+    write_line(out, &json)
+}
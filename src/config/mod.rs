@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use config::{Config, ConfigError, File};
 use std::env;
+use crate::models::ListingOrder;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerConfig {
@@ -12,13 +13,26 @@ pub struct ServerConfig {
 pub struct AppConfig {
     pub server: ServerConfig,
     pub log_level: String,
+    // Bearer token for the admin endpoints, which are disabled without one
+    #[serde(default)]
+    pub admin_token: Option<String>,
+    // CSV or JSON file to load exchange rates from, instead of keeping them in memory
+    #[serde(default)]
+    pub rates_file: Option<String>,
+    // Order units and categories are listed in when a request doesn't give one
+    #[serde(default)]
+    pub listing_order: ListingOrder,
+    // Directory holding Swagger UI's swagger-ui.css and swagger-ui-bundle.js, served
+    // with the docs page instead of loading them from unpkg
+    #[serde(default)]
+    pub swagger_ui_dir: Option<String>,
 }
 
 impl AppConfig {
     pub fn new() -> Result<Self, ConfigError> {
         let run_mode = env::var("RUN_MODE").unwrap_or_else(|_| "development".into());
         
-        let mut s = Config::builder()
+        let s = Config::builder()
             // Start with default config
             .add_source(File::with_name("config/default").required(false))
             // Add environment specific config
@@ -33,13 +47,25 @@ impl AppConfig {
         s.try_deserialize()
     }
 
-    pub fn from_env() -> Self {
-        Self {
+    /// Reads the configuration from environment variables, failing on a setting
+    /// that doesn't parse rather than quietly using the default
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let listing_order = match env::var("LISTING_ORDER") {
+            Ok(order) if !order.is_empty() => order.parse()
+                .map_err(|err| ConfigError::Message(format!("LISTING_ORDER: {}", err)))?,
+            _ => ListingOrder::default(),
+        };
+        
+        Ok(Self {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
             },
             log_level: env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string()),
-        }
+            admin_token: env::var("ADMIN_TOKEN").ok().filter(|token| !token.is_empty()),
+            rates_file: env::var("RATES_FILE").ok().filter(|path| !path.is_empty()),
+            listing_order,
+            swagger_ui_dir: env::var("SWAGGER_UI_DIR").ok().filter(|path| !path.is_empty()),
+        })
     }
 } 
\ No newline at end of file
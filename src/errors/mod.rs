@@ -14,6 +14,46 @@ pub enum ApiError {
     
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+    
+    #[error("Not found: {message}")]
+    UnitNotFound {
+        message: String,
+        suggestions: Vec<String>,
+    },
+    
+    #[error("Ambiguous: {message}")]
+    Ambiguous {
+        message: String,
+        candidates: Vec<String>,
+    },
+    
+    #[error("Parse error: {message}")]
+    ParseError {
+        message: String,
+        position: usize,
+        token: String,
+    },
+}
+
+impl ApiError {
+    /// Builds the error for a unit that isn't registered, naming the closest
+    /// matches in the message as well
+    pub fn unit_not_found(message: impl Into<String>, suggestions: Vec<String>) -> Self {
+        let mut message = message.into();
+        if !suggestions.is_empty() {
+            message.push_str(&format!("; did you mean {}?", quoted_list(&suggestions, "or")));
+        }
+        ApiError::UnitNotFound { message, suggestions }
+    }
+}
+
+/// Quotes and joins items for a message, as in "'a', 'b' or 'c'"
+pub(crate) fn quoted_list<S: AsRef<str>>(items: &[S], conjunction: &str) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| format!("'{}'", item.as_ref())).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} {} {}", rest.join(", "), conjunction, last),
+        _ => quoted.concat(),
+    }
 }
 
 impl ResponseError for ApiError {
@@ -42,6 +82,28 @@ impl ResponseError for ApiError {
                     "error": "unauthorized",
                     "message": message
                 }))
+            },
+            ApiError::UnitNotFound { message, suggestions } => {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "unit_not_found",
+                    "message": message,
+                    "suggestions": suggestions
+                }))
+            },
+            ApiError::Ambiguous { message, candidates } => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "ambiguous_unit",
+                    "message": message,
+                    "candidates": candidates
+                }))
+            },
+            ApiError::ParseError { message, position, token } => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "parse_error",
+                    "message": message,
+                    "position": position,
+                    "token": token
+                }))
             }
         }
     }
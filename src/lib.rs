@@ -2,4 +2,5 @@ pub mod config;
 pub mod errors;
 pub mod routes;
 pub mod models;
-pub mod services; 
\ No newline at end of file
+pub mod services;
+pub mod parser; 
\ No newline at end of file
@@ -1,32 +1,78 @@
-mod config;
-mod errors;
-mod routes;
-mod models;
-mod services;
-
-use actix_web::{App, HttpServer, middleware::Logger};
-use log::info;
+use unit_conversion_api::{config, models, routes};
+use unit_conversion_api::models::definitions::DEFINITIONS_DIR;
+use unit_conversion_api::models::import::ImportFormat;
+use actix_web::{web, App, HttpServer, middleware::Logger};
+use clap::Parser;
+use log::{info, warn, error};
 use dotenv::dotenv;
 
+#[derive(Parser)]
+#[command(version, about = "Serve the unit conversion API")]
+struct Args {
+    /// GNU units or Pint database to import at startup, repeated for several
+    #[arg(long, value_name = "FILE")]
+    import: Vec<String>,
+
+    /// Format of the imported databases, guessed when omitted
+    #[arg(long, value_name = "gnu|pint")]
+    import_format: Option<ImportFormat>,
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    
     // Load environment variables from .env file if it exists
     dotenv().ok();
     
     // Initialize logger
     env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
     
-    // Initialize the unit registry with available units
-    models::init::init();
+    // Load configuration, refusing to start with a setting that doesn't parse
+    let config = match config::AppConfig::from_env() {
+        Ok(config) => config,
+        Err(err) => {
+            error!("{}", err);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string()));
+        },
+    };
     
-    // Load configuration
-    let config = config::AppConfig::from_env();
+    // Register the built-in units, those of the definition files under config/,
+    // the databases given with --import and the currencies of the rates file,
+    // refusing to start with any that don't load
+    let setup = match models::init::setup(&args.import, args.import_format, config.rates_file.as_deref()) {
+        Ok(setup) => setup,
+        Err(err) => {
+            error!("{}", err);
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()));
+        },
+    };
+    if setup.defined > 0 {
+        info!("Loaded {} units from {}", setup.defined, DEFINITIONS_DIR);
+    }
+    for (path, report) in &setup.imports {
+        for warning in &report.warnings {
+            warn!("{}: {}", path, warning);
+        }
+        info!(
+            "Imported {} units and {} aliases from {}, skipping {} already defined and {} with warnings",
+            report.units, report.aliases, path, report.skipped, report.warnings.len()
+        );
+    }
+    if let Some(path) = &config.rates_file {
+        info!("Loaded exchange rates from {}", path);
+    }
     
     info!("Starting server at http://{}:{}", config.server.host, config.server.port);
     
+    // Share configuration, such as the admin token, with handlers
+    let app_config = web::Data::new(config.clone());
+    
     // Configure and start HTTP server
     HttpServer::new(move || {
         App::new()
+            .app_data(app_config.clone())
+            
             // Enable logger middleware
             .wrap(Logger::default())
             
@@ -0,0 +1,159 @@
+/// Irregular plurals of words that appear in unit names
+const IRREGULAR_PLURALS: [(&str, &str); 6] = [
+    ("foot", "feet"),
+    ("century", "centuries"),
+    ("inch", "inches"),
+    ("krona", "kronor"),
+    ("krone", "kroner"),
+    ("real", "reais"),
+];
+
+/// Words in unit names that are never pluralized
+const UNCOUNTABLE: [&str; 9] = [
+    "celsius", "fahrenheit", "kelvin", "rankine", "reaumur", "mach",
+    "yen", "yuan", "won",
+];
+
+/// Returns the plural of a single word
+fn pluralize_word(word: &str) -> String {
+    if let Some(&(_, plural)) = IRREGULAR_PLURALS.iter().find(|&&(singular, _)| singular == word) {
+        return plural.to_string();
+    }
+
+    if UNCOUNTABLE.contains(&word) || word.ends_with("power") || word.ends_with('s') {
+        word.to_string()
+    } else if word.ends_with("ch") || word.ends_with("sh") || word.ends_with('x') {
+        format!("{}es", word)
+    } else if word.ends_with('y') && !word.ends_with("ay") && !word.ends_with("ey") {
+        format!("{}ies", &word[..word.len() - 1])
+    } else {
+        format!("{}s", word)
+    }
+}
+
+/// Returns the plural form of a unit name
+///
+/// Compound names pluralize their head noun: "mile per hour" becomes "miles per hour",
+/// "pound-force" becomes "pounds-force" and "square foot" becomes "square feet".
+pub fn pluralize(name: &str) -> String {
+    for separator in [" per ", " of ", "-"] {
+        if let Some((head, tail)) = name.split_once(separator) {
+            return format!("{}{}{}", pluralize(head), separator, tail);
+        }
+    }
+
+    match name.rsplit_once(' ') {
+        Some((head, last)) => format!("{} {}", head, pluralize_word(last)),
+        None => pluralize_word(name),
+    }
+}
+
+/// Returns the singular form of a name that is already plural, such as
+/// "pounds per square inch" or "millimeters of mercury"
+fn singularize(name: &str) -> Option<String> {
+    let (head, separator, tail) = [" per ", " of "].iter()
+        .find_map(|sep| name.split_once(sep).map(|(h, t)| (h, *sep, t)))?;
+
+    let singular = if let Some(&(word, _)) = IRREGULAR_PLURALS.iter().find(|&&(_, plural)| head.ends_with(plural)) {
+        format!("{}{}", &head[..head.len() - pluralize_word(word).len()], word)
+    } else {
+        head.strip_suffix('s')?.to_string()
+    };
+
+    Some(format!("{}{}{}", singular, separator, tail))
+}
+
+/// Returns British spellings of a name, such as "metre" for "meter"
+fn british_spellings(name: &str) -> Option<String> {
+    if name.contains("meter") || name.contains("liter") {
+        Some(name.replace("meter", "metre").replace("liter", "litre"))
+    } else {
+        None
+    }
+}
+
+/// Returns ASCII stand-ins for a symbol, such as "degC" for "°C", "m2" for "m²",
+/// "um" for "μm" or "delta degC" for "Δ°C", along with "sq"/"cu" forms for areas
+/// and volumes
+fn ascii_symbols(symbol: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    if let Some(rest) = symbol.strip_prefix('Δ') {
+        // The increment sign (U+2206) looks identical to the Greek capital delta
+        result.push(format!("∆{}", rest));
+        for stand_in in std::iter::once(rest.to_string()).chain(ascii_symbols(rest)) {
+            result.push(format!("delta {}", stand_in));
+        }
+    }
+
+    if let Some(rest) = symbol.strip_prefix('°') {
+        let rest_ascii = rest.replace('é', "e");
+        result.push(format!("deg{}", rest));
+        result.push(format!("deg {}", rest));
+        if rest_ascii != rest {
+            result.push(format!("deg{}", rest_ascii));
+            result.push(format!("deg {}", rest_ascii));
+        }
+    }
+
+    for (superscript, digit, prefix) in [('²', '2', "sq"), ('³', '3', "cu")] {
+        if let Some(base) = symbol.strip_suffix(superscript) {
+            result.push(format!("{}{}", base, digit));
+            result.push(format!("{}^{}", base, digit));
+            result.push(format!("{} {}", prefix, base));
+        }
+    }
+
+    if symbol.contains('μ') {
+        result.push(symbol.replace('μ', "u"));
+        // The micro sign (U+00B5) looks identical to the Greek mu used in symbols
+        result.push(symbol.replace('μ', "µ"));
+    }
+
+    if symbol.contains('⋅') {
+        for replacement in ["*", "·", "-", " "] {
+            result.push(symbol.replace('⋅', replacement));
+        }
+    }
+
+    result
+}
+
+/// Generates the aliases a unit can be looked up by, besides its symbol:
+/// its full name, plural, British spelling and ASCII stand-ins for its symbol
+pub fn generate_aliases(name: &str, symbol: &str) -> Vec<String> {
+    let mut names = vec![name.to_string()];
+    let plural = pluralize(name);
+    if plural != name {
+        names.push(plural);
+    }
+    if let Some(singular) = singularize(name) {
+        names.push(singular);
+    }
+    if name.contains('-') {
+        names.push(name.replace('-', " "));
+    }
+
+    let mut aliases = names.clone();
+    aliases.extend(names.iter().filter_map(|n| british_spellings(n)));
+
+    // Absolute temperatures are usually named as degrees, and their intervals
+    // as degrees of difference
+    if symbol.starts_with('°') {
+        aliases.push(format!("degree {}", name));
+        aliases.push(format!("degrees {}", name));
+    }
+    if let (true, Some(scale)) = (symbol.starts_with("Δ°"), name.strip_prefix("delta ")) {
+        aliases.push(format!("delta degrees {}", scale));
+    }
+
+    aliases.extend(ascii_symbols(symbol));
+
+    let mut unique: Vec<String> = Vec::new();
+    for alias in aliases {
+        if alias != symbol && !unique.contains(&alias) {
+            unique.push(alias);
+        }
+    }
+    unique
+}
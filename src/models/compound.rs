@@ -0,0 +1,565 @@
+use crate::errors::ApiError;
+use crate::models::dimension::Dimension;
+use crate::models::exact::{self, LinearConversion};
+use crate::models::registry::UnitRegistry;
+use crate::models::unit::UnitCategory;
+use crate::models::units::temperature::TemperatureCategory;
+use bigdecimal::BigDecimal;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+
+/// Characters that combine units within an expression
+const OPERATORS: [char; 7] = ['·', '⋅', '*', '/', '(', ')', '^'];
+
+/// Largest power a unit can be raised to, the most a dimension's exponents can hold
+const MAX_EXPONENT: i32 = i8::MAX as i32;
+
+/// A registered unit raised to an integer power within a compound expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitFactor {
+    pub symbol: &'static str,
+    pub category: &'static str,
+    pub exponent: i32,
+}
+
+/// A unit expression such as "kg·m/s²" or "W/(m²·K)", resolved against the registry
+///
+/// Every category's base unit is the coherent SI unit for its dimension, so a
+/// compound unit reduces to a scale factor relative to SI plus a dimension vector.
+/// Two compound units can be converted between whenever their dimensions match.
+/// The scale is kept as an exact rational when every unit involved has an exact definition.
+#[derive(Debug, Clone)]
+pub struct CompoundUnit {
+    expression: String,
+    factors: Vec<UnitFactor>,
+    scale: f64,
+    offset: f64,
+    exact: Option<LinearConversion>,
+    dimension: Dimension,
+}
+
+impl CompoundUnit {
+    /// Parses a unit expression, combining registered units with `·`, `*`, `/`,
+    /// parentheses and exponents (`^2`, `^-1`, `²`, `⁻¹` or a trailing digit as in `m3`)
+    pub fn parse(expression: &str, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return Err(ApiError::BadRequest("Unit expression is empty".to_string()));
+        }
+
+        let tokens = tokenize(expression, registry)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0, expression };
+        let term = parser.parse_expression()?;
+
+        if parser.pos < tokens.len() {
+            let token = &tokens[parser.pos];
+            return Err(ApiError::BadRequest(format!(
+                "Unexpected '{}' at position {} in unit expression '{}'",
+                token.text, token.offset, expression
+            )));
+        }
+
+        Self::from_factors(expression, term.scale, term.factors, registry)
+    }
+
+    /// Builds a compound unit from resolved factors and a numeric scale
+    fn from_factors(
+        expression: &str,
+        scale: BigRational,
+        factors: Vec<UnitFactor>,
+        registry: &UnitRegistry,
+    ) -> Result<Self, ApiError> {
+        // A lone unit with an offset (such as °C) may still be used on its own
+        let single = factors.len() == 1 && factors[0].exponent == 1 && scale.is_one();
+
+        let mut total_scale = exact::rational_to_f64(&scale);
+        let mut offset = 0.0;
+        let mut exact = Some(LinearConversion::scale(scale));
+        let mut dimension = Dimension::DIMENSIONLESS;
+
+        for factor in &factors {
+            let unit = registry.get_unit(factor.category, factor.symbol)
+                .ok_or_else(|| ApiError::NotFound(format!("Unit '{}' not found", factor.symbol)))?;
+
+            let unit_dimension = unit.dimension().ok_or_else(|| ApiError::BadRequest(format!(
+                "Unit '{}' has no physical dimension and can't be used in a unit expression",
+                factor.symbol
+            )))?;
+
+            let zero = unit.to_base(0.0);
+            let unit_scale = unit.to_base(1.0) - zero;
+
+            // Within a product or quotient, as in W/(m²·°F), a unit with an offset
+            // stands for its interval, so only its scale counts
+            if single {
+                offset = zero;
+            }
+
+            exact = match (exact, unit.exact()) {
+                // A lone unit keeps its offset, so its conversion is taken whole
+                (Some(_), Some(unit_exact)) if single && unit_exact.has_offset() => Some(unit_exact.clone()),
+                (Some(product), Some(unit_exact)) => {
+                    let scale = exact::checked_pow(unit_exact.factor(), factor.exponent)
+                        .map(|power| product.factor() * power)
+                        .filter(|scale| exact::bits(scale) <= exact::MAX_FACTOR_BITS)
+                        .ok_or_else(|| ApiError::BadRequest(format!(
+                            "Unit expression '{}' has a scale factor too large to represent", expression
+                        )))?;
+                    Some(LinearConversion::scale(scale))
+                },
+                _ => None,
+            };
+
+            total_scale *= unit_scale.powi(factor.exponent);
+            dimension = unit_dimension.checked_powi(factor.exponent)
+                .and_then(|d| dimension.checked_mul(d))
+                .ok_or_else(|| ApiError::BadRequest(format!(
+                    "Unit expression '{}' has a dimension with exponents too large to represent",
+                    expression
+                )))?;
+        }
+
+        Ok(Self {
+            expression: expression.to_string(),
+            factors,
+            scale: total_scale,
+            offset,
+            exact,
+            dimension,
+        })
+    }
+
+    /// Returns the expression this unit was parsed from
+    pub fn expression(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the registered units making up this expression
+    pub fn factors(&self) -> &[UnitFactor] {
+        &self.factors
+    }
+
+    /// Returns the physical dimension of the expression
+    pub fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Returns true if the unit has an offset from zero, as °C and °F do
+    pub fn has_offset(&self) -> bool {
+        self.offset != 0.0
+    }
+
+    /// Returns true if the unit measures temperatures rather than amounts: a lone
+    /// unit with an offset, such as °C, or from the temperature category, such as K
+    pub fn is_temperature(&self) -> bool {
+        self.has_offset() || matches!(
+            self.factors.as_slice(),
+            [factor] if factor.exponent == 1 && factor.category == TemperatureCategory.name()
+        )
+    }
+
+    /// Returns the exact conversion to coherent SI units, if every unit in the
+    /// expression has an exact definition
+    pub fn exact(&self) -> Option<&LinearConversion> {
+        self.exact.as_ref()
+    }
+
+    /// Converts a value in this unit to coherent SI units
+    pub fn to_base(&self, value: f64) -> f64 {
+        match &self.exact {
+            Some(exact) => exact.to_base_f64(value),
+            None => value * self.scale + self.offset,
+        }
+    }
+
+    /// Converts a value in coherent SI units to this unit
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base(&self, value: f64) -> f64 {
+        match &self.exact {
+            Some(exact) => exact.from_base_f64(value),
+            None => (value - self.offset) / self.scale,
+        }
+    }
+
+    /// Converts a value from this unit to another unit of the same dimension
+    pub fn convert(&self, value: f64, target: &CompoundUnit) -> Result<f64, ApiError> {
+        self.check_dimension(target)?;
+
+        if let (Some(from), Some(to)) = (&self.exact, &target.exact) {
+            if let Some(result) = exact::convert_exact(value, from, to) {
+                return Ok(exact::rational_to_f64(&result));
+            }
+        }
+
+        Ok(target.from_base(self.to_base(value)))
+    }
+
+    /// Converts an exact value to another unit of the same dimension without
+    /// rounding, failing if either unit lacks an exact definition
+    pub fn convert_exact(&self, value: &BigRational, target: &CompoundUnit) -> Result<BigRational, ApiError> {
+        let (from, to) = self.exact_pair(target)?;
+        Ok(from.to(to).to_base(value))
+    }
+
+    /// Converts an arbitrary-precision decimal to another unit of the same
+    /// dimension, failing if either unit lacks an exact definition
+    pub fn convert_decimal(&self, value: &BigDecimal, target: &CompoundUnit) -> Result<BigDecimal, ApiError> {
+        let (from, to) = self.exact_pair(target)?;
+        Ok(to.from_base_decimal(&from.to_base_decimal(value)))
+    }
+
+    fn exact_pair<'a>(&'a self, target: &'a CompoundUnit) -> Result<(&'a LinearConversion, &'a LinearConversion), ApiError> {
+        self.check_dimension(target)?;
+
+        match (&self.exact, &target.exact) {
+            (Some(from), Some(to)) => Ok((from, to)),
+            (None, _) => Err(self.not_exact()),
+            (_, None) => Err(target.not_exact()),
+        }
+    }
+
+    fn check_dimension(&self, target: &CompoundUnit) -> Result<(), ApiError> {
+        if self.dimension != target.dimension {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot convert '{}' ({}) to '{}' ({}): dimensions differ",
+                self.expression, self.dimension, target.expression, target.dimension
+            )));
+        }
+        Ok(())
+    }
+
+    fn not_exact(&self) -> ApiError {
+        ApiError::BadRequest(format!("Unit '{}' has no exact definition", self.expression))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Unit(&'static str, &'static str),
+    Number(f64),
+    Mul,
+    Div,
+    LParen,
+    RParen,
+    Power(i32),
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    offset: usize,
+}
+
+fn superscript_digit(c: char) -> Option<char> {
+    match c {
+        '⁰' => Some('0'),
+        '¹' => Some('1'),
+        '²' => Some('2'),
+        '³' => Some('3'),
+        '⁴' => Some('4'),
+        '⁵' => Some('5'),
+        '⁶' => Some('6'),
+        '⁷' => Some('7'),
+        '⁸' => Some('8'),
+        '⁹' => Some('9'),
+        _ => None,
+    }
+}
+
+fn is_exponent_start(c: char) -> bool {
+    c == '^' || c == '⁻' || c.is_ascii_digit() || superscript_digit(c).is_some()
+}
+
+/// Resolves a symbol or alias to a single unit, failing if it's ambiguous
+fn resolve_symbol(symbol: &str, registry: &UnitRegistry) -> Result<Option<(&'static str, &'static str)>, ApiError> {
+    Ok(registry.lookup_unit(symbol, None)?.map(|unit| (unit.category_name(), unit.symbol())))
+}
+
+/// Splits an expression into tokens, matching the longest registered symbol or alias
+/// at each position so that ones containing spaces (such as "fl oz") still resolve
+fn tokenize(expression: &str, registry: &UnitRegistry) -> Result<Vec<Token>, ApiError> {
+    // A registered symbol matching the whole expression wins outright ("km/h", "ft⋅lb")
+    if let Some((category, symbol)) = resolve_symbol(expression, registry)? {
+        return Ok(vec![Token {
+            kind: TokenKind::Unit(category, symbol),
+            text: expression.to_string(),
+            offset: 0,
+        }]);
+    }
+
+    let symbols = registry.terms_by_length();
+
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut pos = 0;
+
+    while pos < expression.len() {
+        let rest = &expression[pos..];
+        let c = rest.chars().next().unwrap_or_default();
+
+        if c.is_whitespace() {
+            pos += c.len_utf8();
+            continue;
+        }
+
+        // Adjacent atoms separated only by whitespace are multiplied
+        let after_atom = matches!(
+            tokens.last().map(|t| &t.kind),
+            Some(TokenKind::Unit(..)) | Some(TokenKind::Number(_)) | Some(TokenKind::RParen) | Some(TokenKind::Power(_))
+        );
+        let previous_char = expression[..pos].chars().next_back();
+        let starts_atom = !OPERATORS.contains(&c) || c == '(';
+        let after_whitespace = previous_char.is_some_and(char::is_whitespace);
+        if after_atom && starts_atom && after_whitespace {
+            tokens.push(Token { kind: TokenKind::Mul, text: " ".to_string(), offset: pos });
+        }
+
+        // An exponent attaches directly to the preceding atom ("m2", "s^-1", "m²")
+        let after_power = matches!(tokens.last().map(|t| &t.kind), Some(TokenKind::Power(_)));
+        let is_exponent = after_atom && !after_power && !after_whitespace
+            && (is_exponent_start(c) || rest.starts_with("**"));
+
+        let (kind, len) = match c {
+            '·' | '⋅' | '*' if !is_exponent => (TokenKind::Mul, c.len_utf8()),
+            '/' => (TokenKind::Div, 1),
+            '(' => (TokenKind::LParen, 1),
+            ')' => (TokenKind::RParen, 1),
+            _ if is_exponent => read_exponent(rest, pos, expression)?,
+            _ if c.is_ascii_digit() || c == '.' => {
+                let len = rest.find(|ch: char| !(ch.is_ascii_digit() || ch == '.')).unwrap_or(rest.len());
+                let number = rest[..len].parse::<f64>().map_err(|_| ApiError::BadRequest(format!(
+                    "Invalid number '{}' at position {} in unit expression '{}'",
+                    &rest[..len], pos, expression
+                )))?;
+                (TokenKind::Number(number), len)
+            },
+            _ => read_unit(rest, pos, expression, symbols, tokens.last(), registry)?,
+        };
+
+        tokens.push(Token { kind, text: rest[..len].to_string(), offset: pos });
+        pos += len;
+    }
+
+    Ok(tokens)
+}
+
+/// Reads an exponent in one of the forms `^2`, `^-1`, `**2`, `²`, `⁻¹` or `2`
+fn read_exponent(rest: &str, pos: usize, expression: &str) -> Result<(TokenKind, usize), ApiError> {
+    let invalid = || ApiError::BadRequest(format!(
+        "Invalid exponent at position {} in unit expression '{}'", pos, expression
+    ));
+
+    let mut digits = String::new();
+    let mut len = 0;
+    let mut chars = rest.char_indices().peekable();
+
+    if rest.starts_with("**") {
+        chars.next();
+        chars.next();
+        len = 2;
+    } else if rest.starts_with('^') {
+        chars.next();
+        len = 1;
+    }
+
+    if let Some(&(_, c)) = chars.peek() {
+        if c == '-' || c == '⁻' {
+            digits.push('-');
+            len += c.len_utf8();
+            chars.next();
+        }
+    }
+
+    for (_, c) in chars {
+        let digit = if c.is_ascii_digit() { Some(c) } else { superscript_digit(c) };
+        match digit {
+            Some(d) => {
+                digits.push(d);
+                len += c.len_utf8();
+            },
+            None => break,
+        }
+    }
+
+    let exponent = digits.parse::<i32>().ok().and_then(bounded).ok_or_else(invalid)?;
+    Ok((TokenKind::Power(exponent), len))
+}
+
+/// Reads a unit, preferring the longest registered symbol or alias at this position
+fn read_unit(
+    rest: &str,
+    pos: usize,
+    expression: &str,
+    symbols: &[String],
+    previous: Option<&Token>,
+    registry: &UnitRegistry,
+) -> Result<(TokenKind, usize), ApiError> {
+    let after_division = matches!(previous.map(|t| &t.kind), Some(TokenKind::Div));
+
+    for term in symbols {
+        // Match case-insensitively, leaving precedence between cases to the registry
+        let symbol = match rest.get(..term.len()) {
+            Some(head) if head == term || head.to_lowercase() == term.to_lowercase() => head,
+            _ => continue,
+        };
+
+        let next = rest[symbol.len()..].chars().next();
+        let at_boundary = match next {
+            None => true,
+            Some(c) => c.is_whitespace() || OPERATORS.contains(&c) || is_exponent_start(c),
+        };
+        if !at_boundary {
+            continue;
+        }
+
+        // Symbols with operators inside ("km/h") can't take an exponent or follow a
+        // division, since the parts would then bind differently than written
+        if symbol.contains(OPERATORS) && (after_division || next.is_some_and(is_exponent_start)) {
+            continue;
+        }
+
+        if let Some((category, resolved)) = resolve_symbol(symbol, registry)? {
+            return Ok((TokenKind::Unit(category, resolved), symbol.len()));
+        }
+    }
+
+    let len = rest.find(|c: char| c.is_whitespace() || OPERATORS.contains(&c) || is_exponent_start(c))
+        .unwrap_or(rest.len())
+        .max(rest.chars().next().map_or(1, char::len_utf8));
+
+    Err(ApiError::unit_not_found(format!(
+        "Unknown unit '{}' at position {} in unit expression '{}'",
+        &rest[..len], pos, expression
+    ), registry.suggest_units(&rest[..len], None)))
+}
+
+/// Intermediate result of parsing part of an expression
+struct Term {
+    scale: BigRational,
+    factors: Vec<UnitFactor>,
+}
+
+impl Term {
+    /// Multiplies or, with a `sign` of -1, divides by another term, failing
+    /// with the reason if a unit's exponent would pass `MAX_EXPONENT` or the
+    /// scale `exact::MAX_FACTOR_BITS`
+    fn multiply(&mut self, other: Term, sign: i32) -> Result<(), &'static str> {
+        self.scale *= other.scale.pow(sign);
+        if exact::bits(&self.scale) > exact::MAX_FACTOR_BITS {
+            return Err(SCALE_TOO_LARGE);
+        }
+        for factor in other.factors {
+            let exponent = factor.exponent * sign;
+            match self.factors.iter_mut().find(|f| f.symbol == factor.symbol && f.category == factor.category) {
+                Some(existing) => existing.exponent = bounded(existing.exponent + exponent).ok_or(EXPONENT_TOO_LARGE)?,
+                None => self.factors.push(UnitFactor { exponent, ..factor }),
+            }
+        }
+        self.factors.retain(|f| f.exponent != 0);
+        Ok(())
+    }
+
+    /// Raises the term to a power, failing like `multiply`
+    fn pow(mut self, exponent: i32) -> Result<Term, &'static str> {
+        for factor in self.factors.iter_mut() {
+            factor.exponent = factor.exponent.checked_mul(exponent)
+                .and_then(bounded)
+                .ok_or(EXPONENT_TOO_LARGE)?;
+        }
+        self.scale = exact::checked_pow(&self.scale, exponent).ok_or(SCALE_TOO_LARGE)?;
+        Ok(self)
+    }
+}
+
+// Reasons a term can't be built, reported by `too_large`
+const EXPONENT_TOO_LARGE: &str = "Exponent outside ±127";
+const SCALE_TOO_LARGE: &str = "Scale factor too large";
+
+/// Checks an exponent is within the range dimensions can represent
+fn bounded(exponent: i32) -> Option<i32> {
+    (exponent.abs() <= MAX_EXPONENT).then_some(exponent)
+}
+
+/// Builds the error for an operator whose result would be too large
+fn too_large(reason: &str, token: &Token, expression: &str) -> ApiError {
+    ApiError::ParseError {
+        message: format!("{} at position {} in unit expression '{}'", reason, token.offset, expression),
+        position: token.offset,
+        token: token.text.clone(),
+    }
+}
+
+/// Recursive-descent parser over the token stream; operators associate left to right
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    expression: &'a str,
+}
+
+impl Parser<'_> {
+    fn parse_expression(&mut self) -> Result<Term, ApiError> {
+        let mut result = self.parse_power()?;
+
+        while let Some(token) = self.tokens.get(self.pos) {
+            let sign = match token.kind {
+                TokenKind::Mul => 1,
+                TokenKind::Div => -1,
+                _ => break,
+            };
+            self.pos += 1;
+            let rhs = self.parse_power()?;
+            result.multiply(rhs, sign).map_err(|reason| too_large(reason, token, self.expression))?;
+        }
+
+        Ok(result)
+    }
+
+    fn parse_power(&mut self) -> Result<Term, ApiError> {
+        let mut atom = self.parse_atom()?;
+
+        while let Some(token @ Token { kind: TokenKind::Power(exponent), .. }) = self.tokens.get(self.pos) {
+            atom = atom.pow(*exponent).map_err(|reason| too_large(reason, token, self.expression))?;
+            self.pos += 1;
+        }
+
+        Ok(atom)
+    }
+
+    fn parse_atom(&mut self) -> Result<Term, ApiError> {
+        let token = self.tokens.get(self.pos).ok_or_else(|| ApiError::BadRequest(format!(
+            "Unexpected end of unit expression '{}'", self.expression
+        )))?;
+        self.pos += 1;
+
+        match &token.kind {
+            TokenKind::Unit(category, symbol) => Ok(Term {
+                scale: BigRational::one(),
+                factors: vec![UnitFactor { symbol, category, exponent: 1 }],
+            }),
+            TokenKind::Number(value) => match exact::rational_from_f64(*value) {
+                Some(scale) if !scale.is_zero() => Ok(Term { scale, factors: Vec::new() }),
+                _ => Err(ApiError::BadRequest(format!(
+                    "Invalid scale factor '{}' at position {} in unit expression '{}'",
+                    token.text, token.offset, self.expression
+                ))),
+            },
+            TokenKind::LParen => {
+                let inner = self.parse_expression()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token { kind: TokenKind::RParen, .. }) => {
+                        self.pos += 1;
+                        Ok(inner)
+                    },
+                    _ => Err(ApiError::BadRequest(format!(
+                        "Missing ')' for '(' at position {} in unit expression '{}'",
+                        token.offset, self.expression
+                    ))),
+                }
+            },
+            _ => Err(ApiError::BadRequest(format!(
+                "Unexpected '{}' at position {} in unit expression '{}'",
+                token.text, token.offset, self.expression
+            ))),
+        }
+    }
+}
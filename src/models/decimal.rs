@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::cmp::Ordering;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use utoipa::openapi::{ObjectBuilder, OneOfBuilder, RefOr, Schema, SchemaType};
+use utoipa::ToSchema;
+use crate::models::exact;
+
+/// Most significant digits a decimal result can be rounded to
+pub const MAX_PRECISION: u64 = 100;
+
+/// Largest decimal exponent accepted in a value, comfortably beyond the f64 range
+const MAX_EXPONENT: i64 = 1000;
+
+/// A numeric value written either as a JSON number or as a decimal string such as "0.1"
+///
+/// The value keeps every digit it was written with, so "0.1" is exactly one tenth
+/// and "1234567.891234567891234" doesn't lose digits to floating point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DecimalValue(BigDecimal);
+
+impl DecimalValue {
+    /// Returns the value as an arbitrary-precision decimal
+    pub fn as_decimal(&self) -> &BigDecimal {
+        &self.0
+    }
+
+    /// Returns the value as an exact rational
+    pub fn to_rational(&self) -> BigRational {
+        decimal_to_rational(&self.0)
+    }
+
+    /// Returns the nearest float to the value
+    pub fn to_f64(&self) -> f64 {
+        exact::rational_to_f64(&self.to_rational())
+    }
+}
+
+impl FromStr for DecimalValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decimal = BigDecimal::from_str(s.trim())
+            .map_err(|_| format!("Invalid decimal value '{}'", s))?;
+
+        let (_, scale) = decimal.as_bigint_and_exponent();
+        if scale.abs() > MAX_EXPONENT {
+            return Err(format!("Decimal value '{}' is out of range", s));
+        }
+
+        Ok(Self(decimal))
+    }
+}
+
+/// Converts a float by its shortest decimal form, so 0.1 becomes exactly 0.1
+///
+/// Panics if the value is NaN or infinite, which no decimal can represent.
+impl From<f64> for DecimalValue {
+    fn from(value: f64) -> Self {
+        assert!(value.is_finite(), "Cannot represent {} as a decimal", value);
+        format!("{:e}", value).parse().expect("a finite float is a valid decimal")
+    }
+}
+
+impl Display for DecimalValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.0.to_plain_string())
+    }
+}
+
+impl Serialize for DecimalValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for DecimalValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(f64),
+            Text(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(value) => Ok(Self::from(value)),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Documents that a value may be written as a JSON number or a decimal string
+impl<'s> ToSchema<'s> for DecimalValue {
+    fn schema() -> (&'s str, RefOr<Schema>) {
+        let schema = OneOfBuilder::new()
+            .item(ObjectBuilder::new().schema_type(SchemaType::Number))
+            .item(ObjectBuilder::new().schema_type(SchemaType::String).pattern(Some(r"^[-+]?(\d+\.?\d*|\.\d+)([eE][-+]?\d+)?$")))
+            .description(Some("A number, or a decimal string such as \"0.1\" that keeps every digit"))
+            .example(Some(serde_json::json!("0.1")));
+        ("DecimalValue", schema.into())
+    }
+}
+
+/// Converts a decimal to the rational number it denotes
+pub fn decimal_to_rational(value: &BigDecimal) -> BigRational {
+    let (digits, scale) = value.as_bigint_and_exponent();
+    let power = BigInt::from(10).pow(scale.unsigned_abs() as u32);
+    if scale >= 0 {
+        BigRational::new(digits, power)
+    } else {
+        BigRational::from_integer(digits * power)
+    }
+}
+
+/// Converts a rational number to a decimal, exactly when its decimal expansion
+/// terminates within `MAX_PRECISION` digits and rounded to that many otherwise
+pub fn rational_to_decimal(value: &BigRational) -> BigDecimal {
+    round_rational(value, MAX_PRECISION).normalized()
+}
+
+/// Rounds a rational number to the given number of significant digits,
+/// rounding ties to even
+pub fn round_rational(value: &BigRational, precision: u64) -> BigDecimal {
+    if value.is_zero() {
+        return BigDecimal::zero();
+    }
+
+    // Digits in the integer part of a value, where zero has none
+    let integer_digits = |n: &BigRational| {
+        let integer = n.to_integer();
+        if integer.is_zero() { 0 } else { integer.to_string().len() as i64 }
+    };
+    let precision = precision as i64;
+    let magnitude = value.abs();
+
+    // Find the scale at which the integer part has exactly `precision` digits,
+    // starting from an estimate based on the sizes of numerator and denominator
+    let mut scale = precision
+        - (value.numer().abs().to_string().len() as i64 - value.denom().to_string().len() as i64);
+    let scaled = loop {
+        let scaled = &magnitude * pow10(scale);
+        match integer_digits(&scaled).cmp(&precision) {
+            Ordering::Greater => scale -= 1,
+            Ordering::Less => scale += 1,
+            Ordering::Equal => break scaled,
+        }
+    };
+
+    let floor = scaled.to_integer();
+    let remainder = &scaled - BigRational::from_integer(floor.clone());
+    let half = BigRational::new(BigInt::from(1), BigInt::from(2));
+    let rounded = if remainder > half || (remainder == half && floor.bit(0)) {
+        floor + 1
+    } else {
+        floor
+    };
+
+    let rounded = if value.is_negative() { -rounded } else { rounded };
+    BigDecimal::new(rounded, scale)
+}
+
+/// Returns 10 raised to an integer power as a rational
+fn pow10(exponent: i64) -> BigRational {
+    let power = BigInt::from(10).pow(exponent.unsigned_abs() as u32);
+    if exponent >= 0 {
+        BigRational::from_integer(power)
+    } else {
+        BigRational::new(BigInt::from(1), power)
+    }
+}
+
+/// Formats a decimal rounded to the given number of significant digits,
+/// without trailing zeros or exponent notation
+pub fn format_decimal(value: &BigDecimal, precision: u64) -> String {
+    round_rational(&decimal_to_rational(value), precision)
+        .normalized()
+        .to_plain_string()
+}
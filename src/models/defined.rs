@@ -0,0 +1,227 @@
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+use bigdecimal::BigDecimal;
+use crate::errors::ApiError;
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::registry::{AnyUnit, UnitRegistry};
+use crate::models::system::UnitSystem;
+use crate::models::unit::UnitCategory;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Longest name or symbol a defined unit or category can have
+const MAX_NAME_LENGTH: usize = 64;
+
+/// Characters unit expressions use as operators, which a symbol can't contain
+const SYMBOL_OPERATORS: &str = "*/^()·";
+
+/// A unit defined by data rather than by a type of its own, such as a unit
+/// generated by attaching a prefix to a registered unit, one defined over HTTP
+/// or one loaded from a definition file
+#[derive(Debug, Clone)]
+pub struct DefinedUnit {
+    name: &'static str,
+    symbol: &'static str,
+    category: &'static str,
+    dimension: Option<Dimension>,
+    conversion: LinearConversion,
+    systems: &'static [UnitSystem],
+    description: Option<&'static str>,
+}
+
+impl DefinedUnit {
+    /// Creates a unit in the given category, converting to its base unit by `conversion`
+    pub fn new(
+        name: &'static str,
+        symbol: &'static str,
+        category: &'static str,
+        dimension: Option<Dimension>,
+        conversion: LinearConversion,
+    ) -> Self {
+        Self { name, symbol, category, dimension, conversion, systems: &[], description: None }
+    }
+
+    /// Attaches a human-readable description to the unit
+    pub fn with_description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Places the unit in the given systems of measurement
+    pub fn with_systems(mut self, systems: &'static [UnitSystem]) -> Self {
+        self.systems = systems;
+        self
+    }
+
+    /// Returns the unit's description, if it was given one
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+}
+
+impl AnyUnit for DefinedUnit {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn symbol(&self) -> &'static str {
+        self.symbol
+    }
+
+    fn category_name(&self) -> &'static str {
+        self.category
+    }
+
+    fn dimension(&self) -> Option<Dimension> {
+        self.dimension
+    }
+
+    fn to_base(&self, value: f64) -> f64 {
+        self.conversion.to_base_f64(value)
+    }
+
+    fn from_base(&self, value: f64) -> f64 {
+        self.conversion.from_base_f64(value)
+    }
+
+    fn exact(&self) -> Option<&LinearConversion> {
+        Some(&self.conversion)
+    }
+
+    fn to_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        Some(self.conversion.to_base_decimal(value))
+    }
+
+    fn from_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        Some(self.conversion.from_base_decimal(value))
+    }
+
+    fn prefixes(&self) -> Prefixes {
+        Prefixes::None
+    }
+
+    fn systems(&self) -> &'static [UnitSystem] {
+        self.systems
+    }
+
+    fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+}
+
+/// A category defined by data rather than by a type of its own, such as one
+/// defined over HTTP or loaded from a definition file
+#[derive(Debug, Clone, Copy)]
+pub struct DefinedCategory {
+    name: &'static str,
+    description: &'static str,
+    base_unit: &'static str,
+}
+
+impl DefinedCategory {
+    /// Creates a category with no physical dimension, whose units are defined
+    /// relative to the unit with symbol `base_unit`
+    pub fn new(name: &'static str, description: &'static str, base_unit: &'static str) -> Self {
+        Self { name, description, base_unit }
+    }
+}
+
+impl Display for DefinedCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl UnitCategory for DefinedCategory {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn base_unit(&self) -> Option<&'static str> {
+        Some(self.base_unit)
+    }
+}
+
+/// Checks a name is non-empty and not too long, returning it trimmed
+pub(crate) fn check_name<'a>(what: &str, name: &'a str) -> Result<&'a str, ApiError> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(ApiError::BadRequest(format!(
+            "{} must be between 1 and {} characters", what, MAX_NAME_LENGTH
+        )));
+    }
+    if name.chars().any(char::is_control) {
+        return Err(ApiError::BadRequest(format!("{} '{}' contains control characters", what, name)));
+    }
+    Ok(name)
+}
+
+/// Checks a symbol can be told apart from a number in queries and expressions,
+/// and doesn't contain whitespace or the operators of unit expressions
+pub(crate) fn check_symbol(symbol: &str) -> Result<&str, ApiError> {
+    let symbol = check_name("Unit symbol", symbol)?;
+    if symbol.starts_with(|c: char| c.is_ascii_digit() || "+-.,".contains(c)) {
+        return Err(ApiError::BadRequest(format!(
+            "Unit symbol '{}' must not start with a digit or sign", symbol
+        )));
+    }
+    if symbol.contains(|c: char| c.is_whitespace() || SYMBOL_OPERATORS.contains(c)) {
+        return Err(ApiError::BadRequest(format!(
+            "Unit symbol '{}' must not contain whitespace or any of {}", symbol, SYMBOL_OPERATORS
+        )));
+    }
+    Ok(symbol)
+}
+
+/// Checks no registered unit, in any category, already goes by a new unit's
+/// symbol or the names it would be looked up by
+pub(crate) fn check_unused(registry: &UnitRegistry, name: &str, symbol: &str) -> Result<(), ApiError> {
+    match registry.find_clash(name, symbol) {
+        Some(unit) => Err(ApiError::BadRequest(format!(
+            "Unit '{}' ({}) would be confused with '{}' ({}, {})",
+            symbol, name, unit.symbol(), unit.name(), unit.category_name()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Checks a category name is lowercase letters, digits and underscores,
+/// so it can appear in URLs as it is
+pub(crate) fn check_category_name(name: &str) -> Result<&str, ApiError> {
+    let name = check_name("Category name", name)?;
+    if !name.starts_with(|c: char| c.is_ascii_lowercase())
+        || !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Category name '{}' must be lowercase letters, digits and underscores, starting with a letter",
+            name
+        )));
+    }
+    Ok(name)
+}
+
+// Names and symbols live as long as the registry, like those of built-in units,
+// and are allocated once however often they're defined, removed and defined again
+pub(crate) fn intern(s: &str) -> &'static str {
+    static INTERNED: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    
+    let mut interned = INTERNED.get_or_init(Default::default).lock().unwrap_or_else(|e| e.into_inner());
+    match interned.get(s) {
+        Some(&interned) => interned,
+        None => {
+            let leaked: &'static str = Box::leak(s.to_string().into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        },
+    }
+}
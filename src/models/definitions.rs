@@ -0,0 +1,508 @@
+use std::collections::HashMap;
+use std::path::Path;
+use serde::Deserialize;
+use num_traits::Zero;
+use crate::errors::ApiError;
+use crate::models::alias::generate_aliases;
+use crate::models::defined::{check_name, check_symbol, check_category_name, intern};
+use crate::models::registry::UnitRegistry;
+use crate::models::{DecimalValue, DefinedCategory, DefinedUnit, Dimension, LinearConversion};
+
+/// Directory, alongside the configuration files, that definition files are loaded from at startup
+pub const DEFINITIONS_DIR: &str = "config/units";
+
+/// The contents of one definition file
+///
+/// In TOML, a category with its base unit and a unit defined in terms of it read
+///
+/// ```toml
+/// [[categories]]
+/// name = "packaging"
+/// description = "Shipping containers"
+/// base_unit = { name = "case", symbol = "cse" }
+///
+/// [[units]]
+/// name = "pallet"
+/// symbol = "plt"
+/// factor = 48
+/// unit = "cse"
+/// ```
+///
+/// JSON files hold the same structure.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Definitions {
+    #[serde(default)]
+    pub categories: Vec<CategorySpec>,
+    #[serde(default)]
+    pub units: Vec<UnitSpec>,
+}
+
+/// A category along with the unit every other unit of it is defined in terms of
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CategorySpec {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub base_unit: BaseUnitSpec,
+}
+
+/// The base unit of a category defined in a definition file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaseUnitSpec {
+    pub name: String,
+    pub symbol: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub allow_ambiguous: bool,
+}
+
+/// A unit defined in terms of another, which may come from the registry or
+/// from any of the files loaded alongside
+///
+/// One of the unit equals `factor` times `unit`, plus `offset`. A unit that's
+/// already registered is only redefined when `replace` is set, and a symbol or
+/// alias that stands for a unit of another category is only shared when
+/// `allow_ambiguous` is.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UnitSpec {
+    pub name: String,
+    pub symbol: String,
+    pub factor: DecimalValue,
+    #[serde(default)]
+    pub offset: Option<DecimalValue>,
+    pub unit: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub replace: bool,
+    #[serde(default)]
+    pub allow_ambiguous: bool,
+}
+
+impl Definitions {
+    /// Parses definitions written in TOML
+    pub fn from_toml(text: &str) -> Result<Self, ApiError> {
+        toml::from_str(text).map_err(|err| ApiError::BadRequest(format!("Invalid definition file: {}", err)))
+    }
+
+    /// Parses definitions written in JSON
+    pub fn from_json(text: &str) -> Result<Self, ApiError> {
+        serde_json::from_str(text).map_err(|err| ApiError::BadRequest(format!("Invalid definition file: {}", err)))
+    }
+
+    /// Reads definitions from a `.toml` or `.json` file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ApiError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| ApiError::InternalError(format!(
+            "Failed to read unit definitions from '{}': {}", path.display(), err
+        )))?;
+
+        let parsed = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Self::from_toml(&text),
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&text),
+            _ => return Err(ApiError::BadRequest(format!(
+                "Definition file '{}' must be a .toml or .json file", path.display()
+            ))),
+        };
+        parsed.map_err(|err| located(&path.display().to_string(), err))
+    }
+}
+
+/// Definitions from any number of files, checked and registered together so
+/// that units can refer to units from other files in any order
+#[derive(Debug, Default)]
+pub struct DefinitionSet {
+    files: Vec<(String, Definitions)>,
+}
+
+/// A unit from a definition set, before its conversion is worked out
+struct Entry<'a> {
+    source: &'a str,
+    name: &'a str,
+    symbol: &'a str,
+    category: Option<&'a str>,
+    aliases: &'a [String],
+    description: Option<&'a str>,
+    allow_ambiguous: bool,
+    // None for the base unit of a category in the set
+    spec: Option<&'a UnitSpec>,
+}
+
+impl Entry<'_> {
+    /// Returns the terms the unit will be looked up by: its symbol, the aliases
+    /// generated from its name and those it lists
+    fn terms(&self) -> Vec<String> {
+        std::iter::once(self.symbol.to_string())
+            .chain(generate_aliases(self.name, self.symbol))
+            .chain(self.aliases.iter().map(|alias| alias.trim().to_string()))
+            .collect()
+    }
+}
+
+/// A unit's category, dimension and exact conversion to its category's base unit
+#[derive(Clone)]
+struct Resolved<'a> {
+    category: &'a str,
+    dimension: Option<Dimension>,
+    conversion: LinearConversion,
+}
+
+enum State<'a> {
+    Unvisited,
+    Resolving,
+    Resolved(Resolved<'a>),
+}
+
+impl DefinitionSet {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds definitions, naming where they came from for error messages
+    pub fn add(&mut self, source: impl Into<String>, definitions: Definitions) {
+        self.files.push((source.into(), definitions));
+    }
+
+    /// Reads every `.toml` and `.json` file in a directory, in file name order
+    ///
+    /// A missing directory gives an empty set, so deployments without
+    /// definition files start as before.
+    pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Self, ApiError> {
+        let dir = dir.as_ref();
+        let mut set = Self::new();
+        if !dir.is_dir() {
+            return Ok(set);
+        }
+
+        let entries = std::fs::read_dir(dir).map_err(|err| ApiError::InternalError(format!(
+            "Failed to read unit definitions from '{}': {}", dir.display(), err
+        )))?;
+        let mut paths: Vec<_> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("toml") || ext.eq_ignore_ascii_case("json")))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let definitions = Definitions::load(&path)?;
+            set.add(path.display().to_string(), definitions);
+        }
+        Ok(set)
+    }
+
+    /// Checks every definition and registers the categories and units
+    ///
+    /// Nothing is registered unless the whole set is valid. Returns the number
+    /// of units registered, counting the base units of new categories.
+    pub fn register(&self, registry: &mut UnitRegistry) -> Result<usize, ApiError> {
+        let categories = self.check_categories(registry)?;
+        let entries = self.entries(&categories)?;
+
+        let mut states: Vec<State> = entries.iter().map(|_| State::Unvisited).collect();
+        let mut path = Vec::new();
+        for index in 0..entries.len() {
+            resolve(index, &entries, registry, &mut states, &mut path)?;
+        }
+        let resolved: Vec<Resolved> = states.into_iter()
+            .map(|state| match state {
+                State::Resolved(resolved) => resolved,
+                _ => unreachable!("every entry was resolved"),
+            })
+            .collect();
+
+        check_duplicates(&entries, &resolved, registry)?;
+        check_ambiguity(&entries, &resolved, registry)?;
+
+        for &(name, spec) in &categories {
+            let description = spec.description.as_deref().map(str::trim).unwrap_or_default();
+            let base_unit = spec.base_unit.symbol.trim();
+            registry.register_category(DefinedCategory::new(intern(name), intern(description), intern(base_unit)))?;
+        }
+
+        for (entry, resolved) in entries.iter().zip(resolved) {
+            let category = registry.get_category(resolved.category)
+                .map(|category| category.name())
+                .expect("categories are registered before their units");
+            let mut unit = DefinedUnit::new(
+                intern(entry.name),
+                intern(entry.symbol),
+                category,
+                resolved.dimension,
+                resolved.conversion,
+            );
+            if let Some(description) = entry.description {
+                unit = unit.with_description(intern(description));
+            }
+
+            if entry.spec.is_some_and(|spec| spec.replace) {
+                registry.replace_unit(unit)?;
+            } else {
+                registry.register_defined_unit(unit)?;
+            }
+            for alias in entry.aliases {
+                registry.register_alias(category, entry.symbol, alias.trim())?;
+            }
+        }
+
+        Ok(entries.len())
+    }
+
+    /// Checks the categories are valid and new, returning their names
+    fn check_categories(&self, registry: &UnitRegistry) -> Result<Vec<(&str, &CategorySpec)>, ApiError> {
+        let mut sources: HashMap<&str, &str> = HashMap::new();
+        let mut categories = Vec::new();
+
+        for (source, definitions) in &self.files {
+            for spec in &definitions.categories {
+                let name = check_category_name(&spec.name).map_err(|err| located(source, err))?;
+                if let Some(other) = sources.insert(name, source) {
+                    return Err(located(source, ApiError::BadRequest(format!(
+                        "Category '{}' is also defined in '{}'", name, other
+                    ))));
+                }
+                if registry.get_category(name).is_some() {
+                    return Err(located(source, ApiError::BadRequest(format!(
+                        "Category '{}' already registered", name
+                    ))));
+                }
+                categories.push((name, spec));
+            }
+        }
+        Ok(categories)
+    }
+
+    /// Lists every unit in the set, base units first, checking names and symbols
+    fn entries<'a>(&'a self, categories: &[(&'a str, &'a CategorySpec)]) -> Result<Vec<Entry<'a>>, ApiError> {
+        let sources: HashMap<&str, &str> = self.files.iter()
+            .flat_map(|(source, defs)| defs.categories.iter().map(move |spec| (spec.name.trim(), source.as_str())))
+            .collect();
+        let mut entries = Vec::new();
+
+        for &(name, spec) in categories {
+            let source = sources[name];
+            let base = &spec.base_unit;
+            entries.push(Entry {
+                source,
+                name: check_name("Unit name", &base.name).map_err(|err| located(source, err))?,
+                symbol: check_symbol(&base.symbol).map_err(|err| located(source, err))?,
+                category: Some(name),
+                aliases: &base.aliases,
+                description: base.description.as_deref().map(str::trim),
+                allow_ambiguous: base.allow_ambiguous,
+                spec: None,
+            });
+        }
+
+        for (source, definitions) in &self.files {
+            for spec in &definitions.units {
+                entries.push(Entry {
+                    source,
+                    name: check_name("Unit name", &spec.name).map_err(|err| located(source, err))?,
+                    symbol: check_symbol(&spec.symbol).map_err(|err| located(source, err))?,
+                    category: spec.category.as_deref().map(str::trim),
+                    aliases: &spec.aliases,
+                    description: spec.description.as_deref().map(str::trim),
+                    allow_ambiguous: spec.allow_ambiguous,
+                    spec: Some(spec),
+                });
+            }
+        }
+
+        for entry in &entries {
+            for alias in entry.aliases {
+                check_name("Alias", alias).map_err(|err| located(entry.source, err))?;
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// Works out a unit's conversion, first working out the unit it's defined
+/// in terms of if that's also in the set
+fn resolve<'a>(
+    index: usize,
+    entries: &[Entry<'a>],
+    registry: &UnitRegistry,
+    states: &mut Vec<State<'a>>,
+    path: &mut Vec<usize>,
+) -> Result<Resolved<'a>, ApiError> {
+    match &states[index] {
+        State::Resolved(resolved) => return Ok(resolved.clone()),
+        State::Resolving => {
+            let start = path.iter().position(|&i| i == index).unwrap_or_default();
+            let cycle: Vec<&str> = path[start..].iter()
+                .chain(std::iter::once(&index))
+                .map(|&i| entries[i].symbol)
+                .collect();
+            return Err(located(entries[index].source, ApiError::BadRequest(format!(
+                "Cyclic definition: {}", cycle.join(" -> ")
+            ))));
+        },
+        State::Unvisited => {},
+    }
+
+    let entry = &entries[index];
+    let spec = match entry.spec {
+        Some(spec) => spec,
+        None => {
+            let resolved = Resolved {
+                category: entry.category.expect("base units belong to their category"),
+                dimension: None,
+                conversion: LinearConversion::parse("1", "0"),
+            };
+            states[index] = State::Resolved(resolved.clone());
+            return Ok(resolved);
+        },
+    };
+
+    states[index] = State::Resolving;
+    path.push(index);
+
+    // Units in the set take precedence over registered ones, so a unit can be
+    // defined in terms of one the set corrects
+    let reference = spec.unit.trim();
+    let local: Vec<usize> = (0..entries.len())
+        .filter(|&i| entries[i].symbol == reference || entries[i].name == reference)
+        .filter(|&i| match (entry.category, entries[i].category) {
+            (Some(wanted), Some(category)) => wanted == category,
+            _ => true,
+        })
+        .collect();
+
+    let target = match local.as_slice() {
+        [] => {
+            let unit = registry.resolve_unit(reference, entry.category).map_err(|err| match err {
+                ApiError::UnitNotFound { suggestions, .. } => located(entry.source, ApiError::unit_not_found(format!(
+                    "Unit '{}' refers to unknown unit '{}'", entry.symbol, reference
+                ), suggestions)),
+                err => located(entry.source, err),
+            })?;
+            let conversion = unit.exact().ok_or_else(|| located(entry.source, ApiError::BadRequest(format!(
+                "Unit '{}' has no exact definition, so units can't be defined in terms of it",
+                unit.symbol()
+            ))))?;
+            Resolved {
+                category: unit.category_name(),
+                dimension: unit.dimension(),
+                conversion: conversion.clone(),
+            }
+        },
+        &[i] => resolve(i, entries, registry, states, path)?,
+        _ => return Err(located(entry.source, ApiError::BadRequest(format!(
+            "Unit '{}' refers to '{}', which more than one unit in the definitions matches; \
+             give the unit a category",
+            entry.symbol, reference
+        )))),
+    };
+
+    if let Some(category) = entry.category {
+        if category != target.category {
+            return Err(located(entry.source, ApiError::BadRequest(format!(
+                "Unit '{}' is declared in category '{}', but '{}' is in category '{}'",
+                entry.symbol, category, reference, target.category
+            ))));
+        }
+    }
+
+    let factor = spec.factor.to_rational();
+    if factor.is_zero() {
+        return Err(located(entry.source, ApiError::BadRequest(format!(
+            "Unit '{}' has a factor of zero", entry.symbol
+        ))));
+    }
+    let offset = spec.offset.as_ref().map(|o| o.to_rational()).unwrap_or_default();
+
+    let resolved = Resolved {
+        category: target.category,
+        dimension: target.dimension,
+        conversion: LinearConversion::new(factor, offset).then(&target.conversion),
+    };
+
+    path.pop();
+    states[index] = State::Resolved(resolved.clone());
+    Ok(resolved)
+}
+
+/// Checks no unit is defined twice, and that units are only redefined on purpose
+fn check_duplicates(entries: &[Entry], resolved: &[Resolved], registry: &UnitRegistry) -> Result<(), ApiError> {
+    let mut seen: HashMap<(&str, &str), &str> = HashMap::new();
+
+    for (entry, resolved) in entries.iter().zip(resolved) {
+        if let Some(other) = seen.insert((resolved.category, entry.symbol), entry.source) {
+            return Err(located(entry.source, ApiError::BadRequest(format!(
+                "Unit '{}' in category '{}' is also defined in '{}'",
+                entry.symbol, resolved.category, other
+            ))));
+        }
+
+        let registered = registry.has_unit(resolved.category, entry.symbol);
+        let replace = entry.spec.is_some_and(|spec| spec.replace);
+        if registered && !replace {
+            return Err(located(entry.source, ApiError::BadRequest(format!(
+                "Unit '{}' already registered in category '{}'; set replace = true to redefine it",
+                entry.symbol, resolved.category
+            ))));
+        }
+        if replace && !registered {
+            return Err(located(entry.source, ApiError::BadRequest(format!(
+                "Unit '{}' replaces a unit, but category '{}' has no unit '{}'",
+                entry.symbol, resolved.category, entry.symbol
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/// Checks no unit's symbol or aliases also stand for a unit of another
+/// category, registered or in the set, ignoring case, unless the unit allows it
+fn check_ambiguity(entries: &[Entry], resolved: &[Resolved], registry: &UnitRegistry) -> Result<(), ApiError> {
+    let mut seen: HashMap<String, (&str, &str)> = HashMap::new();
+
+    for (entry, resolved) in entries.iter().zip(resolved) {
+        for term in entry.terms() {
+            let folded = term.to_lowercase();
+            let local = seen.get(&folded)
+                .filter(|&&(category, _)| category != resolved.category)
+                .copied();
+            let registered = registry.find_units(&term).into_iter()
+                .find(|unit| unit.category_name() != resolved.category)
+                .map(|unit| (unit.category_name(), unit.symbol()));
+
+            if let (false, Some((category, symbol))) = (entry.allow_ambiguous, local.or(registered)) {
+                return Err(located(entry.source, ApiError::BadRequest(format!(
+                    "Unit '{}' would be looked up as '{}', which is also '{}' in category '{}'; \
+                     set allow_ambiguous = true to share it",
+                    entry.symbol, term, symbol, category
+                ))));
+            }
+            seen.entry(folded).or_insert((resolved.category, entry.symbol));
+        }
+    }
+    Ok(())
+}
+
+/// Names the file an error came from
+fn located(source: &str, err: ApiError) -> ApiError {
+    match err {
+        ApiError::BadRequest(message) | ApiError::NotFound(message)
+        | ApiError::UnitNotFound { message, .. } => {
+            ApiError::BadRequest(format!("{}: {}", source, message))
+        },
+        ApiError::Ambiguous { message, .. } => ApiError::BadRequest(format!("{}: {}", source, message)),
+        err => err,
+    }
+}
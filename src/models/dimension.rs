@@ -0,0 +1,110 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Symbols of the base quantities, in the order they are stored in a `Dimension`
+const BASE_QUANTITIES: [&str; 8] = ["L", "M", "T", "I", "Θ", "N", "J", "bit"];
+
+/// Exponents of a physical dimension over the seven SI base quantities:
+/// length, mass, time, electric current, temperature, amount of substance
+/// and luminous intensity, followed by information, which the SI doesn't
+/// have but which keeps data sizes apart from plain numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension([i8; 8]);
+
+impl Dimension {
+    pub const DIMENSIONLESS: Dimension = Dimension([0, 0, 0, 0, 0, 0, 0, 0]);
+    pub const LENGTH: Dimension = Dimension([1, 0, 0, 0, 0, 0, 0, 0]);
+    pub const MASS: Dimension = Dimension([0, 1, 0, 0, 0, 0, 0, 0]);
+    pub const TIME: Dimension = Dimension([0, 0, 1, 0, 0, 0, 0, 0]);
+    pub const CURRENT: Dimension = Dimension([0, 0, 0, 1, 0, 0, 0, 0]);
+    pub const TEMPERATURE: Dimension = Dimension([0, 0, 0, 0, 1, 0, 0, 0]);
+    pub const AMOUNT: Dimension = Dimension([0, 0, 0, 0, 0, 1, 0, 0]);
+    pub const LUMINOSITY: Dimension = Dimension([0, 0, 0, 0, 0, 0, 1, 0]);
+    pub const INFORMATION: Dimension = Dimension([0, 0, 0, 0, 0, 0, 0, 1]);
+
+    /// Creates a dimension from its exponents over the SI base quantities
+    pub const fn new(exponents: [i8; 7]) -> Self {
+        let [l, m, t, i, theta, n, j] = exponents;
+        Self([l, m, t, i, theta, n, j, 0])
+    }
+
+    /// Returns the exponents over the base quantities, information last
+    pub fn exponents(&self) -> [i8; 8] {
+        self.0
+    }
+
+    /// Raises the dimension to an integer power, or gives `None` if an
+    /// exponent would leave the range a dimension can hold
+    pub fn checked_powi(self, exponent: i32) -> Option<Self> {
+        let mut result = self.0;
+        for e in result.iter_mut() {
+            *e = i8::try_from((*e as i32).checked_mul(exponent)?).ok()?;
+        }
+        Some(Self(result))
+    }
+
+    /// Multiplies quantities' dimensions by adding their exponents, or gives
+    /// `None` if an exponent would leave the range a dimension can hold
+    pub fn checked_mul(self, other: Dimension) -> Option<Self> {
+        let mut result = self.0;
+        for (e, o) in result.iter_mut().zip(other.0.iter()) {
+            *e = e.checked_add(*o)?;
+        }
+        Some(Self(result))
+    }
+
+    /// Divides quantities' dimensions by subtracting their exponents, or gives
+    /// `None` if an exponent would leave the range a dimension can hold
+    pub fn checked_div(self, other: Dimension) -> Option<Self> {
+        let mut result = self.0;
+        for (e, o) in result.iter_mut().zip(other.0.iter()) {
+            *e = e.checked_sub(*o)?;
+        }
+        Some(Self(result))
+    }
+
+    /// Returns true if all exponents are zero
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::DIMENSIONLESS
+    }
+}
+
+/// Formats an integer exponent with Unicode superscript digits
+pub(crate) fn superscript(exponent: i32) -> String {
+    exponent.to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+// Formats the dimension as a product of base quantities, e.g. "M·L·T⁻²"
+impl Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.is_dimensionless() {
+            return write!(f, "1");
+        }
+
+        // List mass and length first, as is conventional
+        let order = [1, 0, 2, 3, 4, 5, 6, 7];
+        let parts: Vec<String> = order.iter()
+            .filter(|&&i| self.0[i] != 0)
+            .map(|&i| match self.0[i] {
+                1 => BASE_QUANTITIES[i].to_string(),
+                e => format!("{}{}", BASE_QUANTITIES[i], superscript(e as i32)),
+            })
+            .collect();
+
+        write!(f, "{}", parts.join("·"))
+    }
+}
@@ -0,0 +1,211 @@
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+use crate::models::decimal::{decimal_to_rational, rational_to_decimal};
+
+/// Largest power of ten accepted when parsing decimals, comfortably beyond the f64 range
+const MAX_EXPONENT: u32 = 1000;
+
+/// Most bits, numerator and denominator together, a factor built up from
+/// powers and products may take, far beyond any real unit but small enough
+/// that expressions such as `(1000^127)^127` can't tie up the CPU
+pub const MAX_FACTOR_BITS: u64 = 1 << 16;
+
+/// An exact linear conversion to the base unit of a category,
+/// where `base = value × factor + offset`
+///
+/// Units are defined by rational factors such as 3048/10000 for the foot, so
+/// conversions can be composed exactly and only rounded to `f64` at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearConversion {
+    factor: BigRational,
+    offset: BigRational,
+}
+
+impl LinearConversion {
+    /// Creates a conversion from a factor and an offset
+    pub fn new(factor: BigRational, offset: BigRational) -> Self {
+        Self { factor, offset }
+    }
+
+    /// Creates a conversion that only scales, with no offset
+    pub fn scale(factor: BigRational) -> Self {
+        Self::new(factor, BigRational::zero())
+    }
+
+    /// Parses a unit definition from a factor and offset written as decimals or
+    /// fractions, such as "0.3048", "5/9" or "1.602176634e-19"
+    ///
+    /// Panics if either is not a valid number, since definitions are written in source.
+    pub fn parse(factor: &str, offset: &str) -> Self {
+        let parse = |s: &str| parse_rational(s)
+            .unwrap_or_else(|| panic!("Invalid rational number '{}' in unit definition", s));
+        Self::new(parse(factor), parse(offset))
+    }
+
+    /// Returns the factor relative to the base unit
+    pub fn factor(&self) -> &BigRational {
+        &self.factor
+    }
+
+    /// Returns the offset from the zero of the base unit
+    pub fn offset(&self) -> &BigRational {
+        &self.offset
+    }
+
+    /// Returns true if the conversion has an offset, as °C and °F do
+    pub fn has_offset(&self) -> bool {
+        !self.offset.is_zero()
+    }
+
+    /// Converts an exact value in this unit to the base unit
+    pub fn to_base(&self, value: &BigRational) -> BigRational {
+        value * &self.factor + &self.offset
+    }
+
+    /// Converts an exact value in the base unit to this unit
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base(&self, value: &BigRational) -> BigRational {
+        (value - &self.offset) / &self.factor
+    }
+
+    /// Composes this conversion with the inverse of `target`, giving a single
+    /// conversion from this unit straight to the target unit
+    pub fn to(&self, target: &LinearConversion) -> LinearConversion {
+        Self::new(
+            &self.factor / &target.factor,
+            (&self.offset - &target.offset) / &target.factor,
+        )
+    }
+
+    /// Composes this conversion with `next`, giving a single conversion from this
+    /// unit to the base unit of `next`, for units defined in terms of other units
+    pub fn then(&self, next: &LinearConversion) -> LinearConversion {
+        Self::new(
+            &self.factor * &next.factor,
+            &self.offset * &next.factor + &next.offset,
+        )
+    }
+
+    /// Converts a floating-point value to the base unit, rounding only once
+    pub fn to_base_f64(&self, value: f64) -> f64 {
+        match rational_from_f64(value) {
+            Some(exact) => rational_to_f64(&self.to_base(&exact)),
+            None => value * rational_to_f64(&self.factor) + rational_to_f64(&self.offset),
+        }
+    }
+
+    /// Converts a floating-point value from the base unit, rounding only once
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base_f64(&self, value: f64) -> f64 {
+        match rational_from_f64(value) {
+            Some(exact) => rational_to_f64(&self.from_base(&exact)),
+            None => (value - rational_to_f64(&self.offset)) / rational_to_f64(&self.factor),
+        }
+    }
+
+    /// Converts an arbitrary-precision decimal to the base unit
+    pub fn to_base_decimal(&self, value: &BigDecimal) -> BigDecimal {
+        rational_to_decimal(&self.to_base(&decimal_to_rational(value)))
+    }
+
+    /// Converts an arbitrary-precision decimal from the base unit
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_base_decimal(&self, value: &BigDecimal) -> BigDecimal {
+        rational_to_decimal(&self.from_base(&decimal_to_rational(value)))
+    }
+}
+
+/// Parses a decimal such as "0.3048" or "1.6e-19", or a fraction of two
+/// decimals such as "5/9" or "4.4482216152605/0.00064516", into an exact rational
+pub fn parse_rational(s: &str) -> Option<BigRational> {
+    let s = s.trim();
+    if let Some((numer, denom)) = s.split_once('/') {
+        let denom = parse_decimal(denom)?;
+        if denom.is_zero() {
+            return None;
+        }
+        return Some(parse_decimal(numer)? / denom);
+    }
+    parse_decimal(s)
+}
+
+/// Parses a decimal number with an optional exponent into an exact rational
+fn parse_decimal(s: &str) -> Option<BigRational> {
+    let s = s.trim().replace('_', "");
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(pos) => (&s[..pos], s[pos + 1..].parse::<i32>().ok()?),
+        None => (s.as_str(), 0),
+    };
+
+    let (negative, mantissa) = match mantissa.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, mantissa.strip_prefix('+').unwrap_or(mantissa)),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits: BigInt = format!("{}{}", whole, fraction).parse().ok()?;
+    let digits = if negative { -digits } else { digits };
+    let exponent = exponent - fraction.len() as i32;
+    // Bound the exponent so a malformed input can't build an enormous integer
+    if exponent.unsigned_abs() > MAX_EXPONENT {
+        return None;
+    }
+
+    let power = BigInt::from(10).pow(exponent.unsigned_abs());
+    Some(if exponent >= 0 {
+        BigRational::from_integer(digits * power)
+    } else {
+        BigRational::new(digits, power)
+    })
+}
+
+/// Converts a float to the rational number its shortest decimal form denotes,
+/// so that 0.1 is read as exactly 1/10; returns `None` for NaN and infinities
+pub fn rational_from_f64(value: f64) -> Option<BigRational> {
+    if !value.is_finite() {
+        return None;
+    }
+    parse_decimal(&format!("{:e}", value))
+}
+
+/// Returns the number of bits in a rational's numerator and denominator
+pub fn bits(value: &BigRational) -> u64 {
+    value.numer().bits() + value.denom().bits()
+}
+
+/// Raises a rational number to an integer power, or gives `None` if the
+/// result would take more than `MAX_FACTOR_BITS`
+pub fn checked_pow(value: &BigRational, exponent: i32) -> Option<BigRational> {
+    let bits = bits(value).checked_mul(exponent.unsigned_abs().into())?;
+    (bits <= MAX_FACTOR_BITS).then(|| value.pow(exponent))
+}
+
+/// Rounds a rational number to the nearest float
+pub fn rational_to_f64(value: &BigRational) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Formats a rational number as "numerator/denominator", or as an integer
+/// when the denominator is one
+pub fn format_rational(value: &BigRational) -> String {
+    if value.denom().is_one() {
+        value.numer().to_string()
+    } else {
+        format!("{}/{}", value.numer(), value.denom())
+    }
+}
+
+/// Converts a value between two exactly defined units, composing the
+/// conversions before evaluating; returns `None` if the value isn't finite
+pub fn convert_exact(value: f64, from: &LinearConversion, to: &LinearConversion) -> Option<BigRational> {
+    let value = rational_from_f64(value)?;
+    Some(from.to(to).to_base(&value))
+}
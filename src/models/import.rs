@@ -0,0 +1,1023 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::str::FromStr;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+use serde::Serialize;
+use crate::errors::ApiError;
+use crate::models::defined::{check_name, check_symbol, intern};
+use crate::models::dimension::Dimension;
+use crate::models::exact::{self, LinearConversion};
+use crate::models::registry::UnitRegistry;
+use crate::models::DefinedUnit;
+use crate::models::unit::UnitCategory;
+use crate::models::units::temperature::TemperatureIntervalCategory;
+
+/// Largest exponent a unit can be raised to within a definition
+const MAX_EXPONENT: i32 = 64;
+
+/// How many times an alias is followed to the unit it names
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// Formats of the unit databases the importer reads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// GNU `units` definitions, as in `definitions.units`: a name, then its definition
+    Gnu,
+    /// Pint definitions, as in `default_en.txt`: `name = definition = symbol = aliases`
+    Pint,
+}
+
+impl ImportFormat {
+    /// Guesses the format of a database: Pint separates a unit's name from its
+    /// definition with `=`, where GNU units uses whitespace
+    pub fn detect(text: &str) -> Self {
+        let (pint, gnu) = text.lines()
+            .map(|line| line.split('#').next().unwrap_or_default().trim())
+            .filter(|line| !line.is_empty() && !line.starts_with(['!', '@', '[']))
+            .take(200)
+            .fold((0, 0), |(pint, gnu), line| {
+                if line.contains('=') { (pint + 1, gnu) } else { (pint, gnu + 1) }
+            });
+
+        if pint > gnu { ImportFormat::Pint } else { ImportFormat::Gnu }
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "gnu" | "units" => Ok(ImportFormat::Gnu),
+            "pint" => Ok(ImportFormat::Pint),
+            _ => Err(ApiError::BadRequest(format!(
+                "Unknown import format '{}', expected 'gnu' or 'pint'", s
+            ))),
+        }
+    }
+}
+
+/// A definition the importer skipped, and why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImportWarning {
+    pub line: usize,
+    pub name: String,
+    pub message: String,
+}
+
+impl Display for ImportWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "line {}: {}: {}", self.line, self.name, self.message)
+    }
+}
+
+/// What an import added to the registry
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportReport {
+    /// Units registered
+    pub units: usize,
+    /// Aliases registered, for imported and existing units alike
+    pub aliases: usize,
+    /// Definitions of units the registry already had
+    pub skipped: usize,
+    /// Definitions that couldn't be imported
+    pub warnings: Vec<ImportWarning>,
+}
+
+/// Reads a GNU units or Pint database into the registry, guessing the format
+/// from the file's contents unless it's given
+///
+/// Only failing to read the file is an error. Definitions that can't be
+/// expressed as a factor and offset, such as nonlinear functions, or whose
+/// dimension no category has, are reported as warnings.
+pub fn import_file<P: AsRef<Path>>(
+    registry: &mut UnitRegistry,
+    path: P,
+    format: Option<ImportFormat>,
+) -> Result<ImportReport, ApiError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|err| ApiError::InternalError(format!(
+        "Failed to read unit database '{}': {}", path.display(), err
+    )))?;
+
+    Ok(import_str(registry, &text, format))
+}
+
+/// Reads a GNU units or Pint database held in a string into the registry
+///
+/// Units are placed in the category with their dimension, and units the
+/// registry already has are left as they are.
+pub fn import_str(registry: &mut UnitRegistry, text: &str, format: Option<ImportFormat>) -> ImportReport {
+    let format = format.unwrap_or_else(|| ImportFormat::detect(text));
+    let database = match format {
+        ImportFormat::Gnu => Database::parse_gnu(text),
+        ImportFormat::Pint => Database::parse_pint(text),
+    };
+
+    let mut report = ImportReport { warnings: database.warnings.clone(), ..Default::default() };
+    let mut units = Vec::new();
+    let mut targets: HashMap<usize, (&'static str, String)> = HashMap::new();
+    let mut planned: HashSet<(&'static str, String)> = HashSet::new();
+
+    {
+        let mut evaluator = Evaluator::new(&database, registry, format);
+
+        for (index, definition) in database.definitions.iter().enumerate() {
+            if database.alias_target(index).is_some() {
+                continue;
+            }
+            let warning = |message: String| ImportWarning {
+                line: definition.line,
+                name: definition.name.clone(),
+                message,
+            };
+
+            let quantity = match evaluator.definition(index) {
+                Ok(quantity) => quantity,
+                Err(message) => {
+                    report.warnings.push(warning(message));
+                    continue;
+                },
+            };
+            // Temperatures defined without an offset, such as Pint's delta_degree_Celsius,
+            // are differences between temperatures, as is anything in GNU units but
+            // its base unit
+            let interval = quantity.dimension == Dimension::TEMPERATURE && quantity.offset.is_none()
+                && !matches!(definition.body, Body::Base(_));
+            let category = match interval {
+                true => registry.get_category(TemperatureIntervalCategory.name()).map(|c| c.name()),
+                false => registry.category_for_dimension(quantity.dimension).map(|c| c.name()),
+            };
+            let Some(category) = category else {
+                report.warnings.push(warning(if quantity.dimension.is_dimensionless() {
+                    "is a plain number, which no category holds".to_string()
+                } else {
+                    format!("has dimension {}, which no category holds", quantity.dimension)
+                }));
+                continue;
+            };
+            if quantity.factor.is_zero() {
+                report.warnings.push(warning("has a factor of zero".to_string()));
+                continue;
+            }
+
+            // Pint names words with underscores, as in light_year
+            let name = match format {
+                ImportFormat::Pint => definition.name.replace('_', " "),
+                ImportFormat::Gnu => definition.name.clone(),
+            };
+            let symbol = definition.symbol.clone().unwrap_or_else(|| name.clone());
+            if let Err(err) = check_name("Unit name", &name).and_then(|_| check_symbol(&symbol)) {
+                report.warnings.push(warning(err.to_string()));
+                continue;
+            }
+
+            // A unit already registered is reused, unless the name could mean several
+            let existing = [&symbol, &name].into_iter()
+                .map(|term| registry.lookup_unit(term, Some(category)))
+                .find(|found| !matches!(found, Ok(None)));
+            match existing {
+                Some(Ok(Some(existing))) => {
+                    targets.insert(index, (category, existing.symbol().to_string()));
+                    report.skipped += 1;
+                    continue;
+                },
+                Some(Err(err)) => {
+                    report.warnings.push(warning(err.to_string()));
+                    continue;
+                },
+                _ => {},
+            }
+            if !planned.insert((category, symbol.clone())) {
+                report.warnings.push(warning(format!(
+                    "has the same symbol as another unit in category '{}'", category
+                )));
+                continue;
+            }
+
+            targets.insert(index, (category, symbol.clone()));
+            let conversion = LinearConversion::new(quantity.factor, quantity.offset.unwrap_or_default());
+            units.push((index, DefinedUnit::new(
+                intern(&name),
+                intern(&symbol),
+                category,
+                Some(quantity.dimension),
+                conversion,
+            )));
+        }
+    }
+
+    for (index, unit) in units {
+        let definition = &database.definitions[index];
+        match registry.register_defined_unit(unit) {
+            Ok(()) => report.units += 1,
+            Err(err) => {
+                targets.remove(&index);
+                report.warnings.push(ImportWarning {
+                    line: definition.line,
+                    name: definition.name.clone(),
+                    message: err.to_string(),
+                });
+            },
+        }
+    }
+
+    // Aliases go in once every unit they could name is registered
+    let mut aliases: Vec<(usize, &str, &str)> = Vec::new();
+    for (index, definition) in database.definitions.iter().enumerate() {
+        let of = definition.name.as_str();
+        aliases.extend(definition.aliases.iter().map(|alias| (definition.line, alias.as_str(), of)));
+        if database.alias_target(index).is_some() {
+            aliases.extend(definition.symbol.iter().map(|symbol| (definition.line, symbol.as_str(), of)));
+            aliases.push((definition.line, of, of));
+        } else if format == ImportFormat::Pint && of.contains('_') {
+            aliases.push((definition.line, of, of));
+        }
+    }
+    for (line, of, names) in &database.extra_aliases {
+        aliases.extend(names.iter().map(|alias| (*line, alias.as_str(), of.as_str())));
+    }
+
+    for (line, alias, of) in aliases {
+        let defined = database.resolve_alias(of);
+        let target = defined
+            .and_then(|index| targets.get(&index).cloned())
+            .or_else(|| {
+                let unit = registry.lookup_unit(database.alias_name(of), None).ok()??;
+                Some((unit.category_name(), unit.symbol().to_string()))
+            });
+        let Some((category, symbol)) = target else {
+            // Units that failed to import were already reported
+            if defined.is_none_or(|index| database.alias_target(index).is_some()) {
+                report.warnings.push(ImportWarning {
+                    line,
+                    name: alias.to_string(),
+                    message: format!("is an alias of unknown unit '{}'", database.alias_name(of)),
+                });
+            }
+            continue;
+        };
+
+        if alias == symbol || !registry.find_units(alias).is_empty() {
+            continue;
+        }
+        if registry.register_alias(category, &symbol, alias).is_ok() {
+            report.aliases += 1;
+        }
+    }
+
+    report
+}
+
+/// How a database defines a unit
+#[derive(Debug, Clone)]
+enum Body {
+    /// A base unit of the given dimension, or `None` for one with no counterpart here
+    Base(Option<Dimension>),
+    /// An expression over numbers and other units, with an offset for units like °C
+    Expression { expression: String, offset: Option<String> },
+}
+
+#[derive(Debug, Clone)]
+struct Definition {
+    line: usize,
+    name: String,
+    symbol: Option<String>,
+    aliases: Vec<String>,
+    body: Body,
+}
+
+/// The definitions read from a database, before they are evaluated
+#[derive(Debug, Default)]
+struct Database {
+    prefixes: HashMap<String, (usize, String)>,
+    definitions: Vec<Definition>,
+    // Names, symbols and aliases to the definitions they name
+    index: HashMap<String, usize>,
+    // Aliases of units defined elsewhere, as with Pint's @alias
+    extra_aliases: Vec<(usize, String, Vec<String>)>,
+    warnings: Vec<ImportWarning>,
+}
+
+impl Database {
+    fn warn(&mut self, line: usize, name: &str, message: impl Into<String>) {
+        self.warnings.push(ImportWarning { line, name: name.to_string(), message: message.into() });
+    }
+
+    fn add_prefix(&mut self, line: usize, name: &str, expression: &str) {
+        if name.is_empty() || name == "_" {
+            return;
+        }
+        if let Some((first, _)) = self.prefixes.get(name) {
+            let message = format!("prefix is defined again, keeping the definition on line {}", first);
+            self.warn(line, name, message);
+            return;
+        }
+        self.prefixes.insert(name.to_string(), (line, expression.to_string()));
+    }
+
+    fn add_definition(&mut self, definition: Definition) {
+        if let Some(&first) = self.index.get(&definition.name) {
+            let message = format!("is defined again, keeping the definition on line {}", self.definitions[first].line);
+            self.warn(definition.line, &definition.name, message);
+            return;
+        }
+
+        let index = self.definitions.len();
+        let names = std::iter::once(&definition.name)
+            .chain(definition.symbol.iter())
+            .chain(definition.aliases.iter());
+        for name in names {
+            self.index.entry(name.clone()).or_insert(index);
+        }
+        self.definitions.push(definition);
+    }
+
+    /// Reads a GNU units database, in which each line holds a name followed by
+    /// its definition, and names ending in `-` define prefixes
+    fn parse_gnu(text: &str) -> Self {
+        let mut database = Self::default();
+        let mut pending = String::new();
+        let mut start = 0;
+
+        for (number, raw) in text.lines().enumerate() {
+            let content = raw.split('#').next().unwrap_or_default();
+            if pending.is_empty() {
+                start = number + 1;
+            }
+            // A trailing backslash continues the definition on the next line
+            if let Some(continued) = content.trim_end().strip_suffix('\\') {
+                pending.push_str(continued);
+                pending.push(' ');
+                continue;
+            }
+            pending.push_str(content);
+            let line = std::mem::take(&mut pending);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // Commands such as !locale and !var select among definitions, which
+            // are all read; the first definition of a name wins
+            if let Some(command) = line.strip_prefix('!') {
+                if command.starts_with("include") {
+                    database.warn(start, line, "included files aren't followed; import them separately");
+                }
+                continue;
+            }
+
+            let (name, definition) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let definition = definition.trim();
+            if let Some(function) = name.split_once(['(', '[']).map(|(function, _)| function) {
+                database.warn(start, function, "is a nonlinear function, which can't be expressed as a factor and offset");
+                continue;
+            }
+            if definition.is_empty() {
+                database.warn(start, name, "has no definition");
+                continue;
+            }
+            if let Some(prefix) = name.strip_suffix('-') {
+                database.add_prefix(start, prefix, definition);
+                continue;
+            }
+
+            let body = match definition.strip_prefix('!') {
+                Some(marker) => Body::Base(gnu_dimension(name, marker.trim())),
+                None => Body::Expression { expression: definition.to_string(), offset: None },
+            };
+            database.add_definition(Definition {
+                line: start,
+                name: name.to_string(),
+                symbol: None,
+                aliases: Vec::new(),
+                body,
+            });
+        }
+
+        database
+    }
+
+    /// Reads a Pint database, in which each line reads
+    /// `name = definition = symbol = alias...`, with `_` for a missing symbol
+    fn parse_pint(text: &str) -> Self {
+        let mut database = Self::default();
+        let mut skipping = false;
+
+        'lines: for (number, raw) in text.lines().enumerate() {
+            let line_number = number + 1;
+            let line = raw.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(directive) = line.strip_prefix('@') {
+                let keyword: String = directive.chars().take_while(char::is_ascii_alphabetic).collect();
+                match keyword.as_str() {
+                    "end" => skipping = false,
+                    // Groups only gather definitions; systems and defaults pick among them
+                    "group" => {},
+                    "system" | "defaults" => skipping = true,
+                    "context" => {
+                        database.warn(line_number, line, "contexts convert between dimensions, which can't be expressed as a factor and offset");
+                        skipping = true;
+                    },
+                    "import" => database.warn(line_number, line, "imported files aren't followed; import them separately"),
+                    "alias" => {
+                        let mut names = directive["alias".len()..].split('=').map(str::trim);
+                        let target = names.next().unwrap_or_default().to_string();
+                        let aliases: Vec<String> = names.filter(|a| !a.is_empty() && *a != "_").map(str::to_string).collect();
+                        database.extra_aliases.push((line_number, target, aliases));
+                    },
+                    _ => database.warn(line_number, line, "unknown directive"),
+                }
+                continue;
+            }
+            // Derived dimensions, like [area] = [length] ** 2, follow from the base units
+            if skipping || line.starts_with('[') {
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split('=').map(str::trim).collect();
+            let name = parts[0];
+            let Some(&value) = parts.get(1) else {
+                database.warn(line_number, name, "has no definition");
+                continue;
+            };
+            // Modifiers such as "; offset: 273.15" follow the definition
+            let (value, modifiers) = match value.split_once(';') {
+                Some((value, modifiers)) => (value.trim(), Some(modifiers)),
+                None => (value, None),
+            };
+
+            if let Some(prefix) = name.strip_suffix('-') {
+                database.add_prefix(line_number, prefix, value);
+                for alias in &parts[2..] {
+                    database.add_prefix(line_number, alias.strip_suffix('-').unwrap_or(alias), value);
+                }
+                continue;
+            }
+
+            let mut offset = None;
+            for modifier in modifiers.into_iter().flat_map(|m| m.split(';')) {
+                let (key, argument) = modifier.split_once(':').unwrap_or((modifier, ""));
+                match key.trim() {
+                    "offset" => offset = Some(argument.trim().to_string()),
+                    key => {
+                        database.warn(line_number, name, format!(
+                            "has a {}, which can't be expressed as a factor and offset", key
+                        ));
+                        continue 'lines;
+                    },
+                }
+            }
+
+            let body = if value.starts_with('[') {
+                Body::Base(pint_dimension(value))
+            } else {
+                Body::Expression { expression: value.to_string(), offset }
+            };
+            let names = |parts: &[&str]| parts.iter()
+                .filter(|part| !part.is_empty() && **part != "_")
+                .map(|part| part.to_string())
+                .collect::<Vec<_>>();
+            database.add_definition(Definition {
+                line: line_number,
+                name: name.to_string(),
+                symbol: names(parts.get(2..3).unwrap_or_default()).pop(),
+                aliases: names(parts.get(3..).unwrap_or_default()),
+                body,
+            });
+        }
+
+        database
+    }
+
+    /// If a definition only names another unit of the database, as GNU's
+    /// "metre meter" does, returns the name it gives
+    fn alias_target(&self, index: usize) -> Option<&str> {
+        match &self.definitions[index].body {
+            Body::Expression { expression, offset: None } => {
+                let target = expression.trim();
+                (self.index.contains_key(target) && target != self.definitions[index].name).then_some(target)
+            },
+            _ => None,
+        }
+    }
+
+    /// Follows aliases from a name to the definition of the unit it names, if
+    /// the database defines one
+    fn resolve_alias(&self, name: &str) -> Option<usize> {
+        let mut index = *self.index.get(name)?;
+        for _ in 0..MAX_ALIAS_DEPTH {
+            match self.alias_target(index).and_then(|target| self.index.get(target)) {
+                Some(&next) => index = next,
+                None => return Some(index),
+            }
+        }
+        None
+    }
+
+    /// Follows aliases from a name to the last name in the chain, which names
+    /// a unit defined elsewhere when the database doesn't define it
+    fn alias_name<'a>(&'a self, name: &'a str) -> &'a str {
+        let mut name = name;
+        for _ in 0..MAX_ALIAS_DEPTH {
+            match self.index.get(name).and_then(|&index| self.alias_target(index)) {
+                Some(target) => name = target,
+                None => break,
+            }
+        }
+        name
+    }
+}
+
+/// Maps the base units GNU units marks with `!` to base quantities
+fn gnu_dimension(name: &str, marker: &str) -> Option<Dimension> {
+    if marker == "dimensionless" {
+        return Some(Dimension::DIMENSIONLESS);
+    }
+    match name {
+        "m" => Some(Dimension::LENGTH),
+        "kg" => Some(Dimension::MASS),
+        "s" => Some(Dimension::TIME),
+        "A" => Some(Dimension::CURRENT),
+        "K" => Some(Dimension::TEMPERATURE),
+        "mol" => Some(Dimension::AMOUNT),
+        "cd" => Some(Dimension::LUMINOSITY),
+        "bit" => Some(Dimension::INFORMATION),
+        _ => None,
+    }
+}
+
+/// Maps Pint's base dimensions to base quantities
+fn pint_dimension(dimension: &str) -> Option<Dimension> {
+    match dimension {
+        "[]" => Some(Dimension::DIMENSIONLESS),
+        "[length]" => Some(Dimension::LENGTH),
+        "[mass]" => Some(Dimension::MASS),
+        "[time]" => Some(Dimension::TIME),
+        "[current]" => Some(Dimension::CURRENT),
+        "[temperature]" => Some(Dimension::TEMPERATURE),
+        "[substance]" => Some(Dimension::AMOUNT),
+        "[luminosity]" => Some(Dimension::LUMINOSITY),
+        "[information]" => Some(Dimension::INFORMATION),
+        _ => None,
+    }
+}
+
+/// A value in coherent SI units, with the offset of units like °C
+#[derive(Debug, Clone)]
+struct Quantity {
+    factor: BigRational,
+    dimension: Dimension,
+    offset: Option<BigRational>,
+}
+
+impl Quantity {
+    fn number(value: BigRational) -> Self {
+        Self { factor: value, dimension: Dimension::DIMENSIONLESS, offset: None }
+    }
+
+    /// Fails for units with an offset, which don't scale from zero
+    fn linear(self) -> Result<Self, String> {
+        match self.offset {
+            Some(offset) if !offset.is_zero() => {
+                Err("combines a unit that has an offset, which can't be expressed as a factor and offset".to_string())
+            },
+            _ => Ok(Self { offset: None, ..self }),
+        }
+    }
+
+    fn multiply(self, other: Self) -> Result<Self, String> {
+        let (a, b) = (self.linear()?, other.linear()?);
+        let dimension = a.dimension.checked_mul(b.dimension).ok_or_else(too_large)?;
+        Self::bounded(a.factor * b.factor, dimension)
+    }
+
+    fn divide(self, other: Self) -> Result<Self, String> {
+        let (a, b) = (self.linear()?, other.linear()?);
+        if b.factor.is_zero() {
+            return Err("divides by zero".to_string());
+        }
+        let dimension = a.dimension.checked_div(b.dimension).ok_or_else(too_large)?;
+        Self::bounded(a.factor / b.factor, dimension)
+    }
+
+    fn add(self, other: Self, negate: bool) -> Result<Self, String> {
+        let (a, b) = (self.linear()?, other.linear()?);
+        if a.dimension != b.dimension {
+            return Err(format!("adds quantities of dimensions {} and {}", a.dimension, b.dimension));
+        }
+        let factor = if negate { a.factor - b.factor } else { a.factor + b.factor };
+        Ok(Self { factor, ..a })
+    }
+
+    fn powi(self, exponent: i32) -> Result<Self, String> {
+        let base = self.linear()?;
+        if base.factor.is_zero() && exponent < 0 {
+            return Err("divides by zero".to_string());
+        }
+        let dimension = base.dimension.checked_powi(exponent).ok_or_else(too_large)?;
+        let factor = exact::checked_pow(&base.factor, exponent).ok_or_else(too_large)?;
+        Ok(Self { factor, dimension, offset: None })
+    }
+
+    /// Builds a linear quantity, failing if its factor has grown too large to
+    /// work with, as when definitions multiply many large powers together
+    fn bounded(factor: BigRational, dimension: Dimension) -> Result<Self, String> {
+        if exact::bits(&factor) > exact::MAX_FACTOR_BITS {
+            return Err(too_large());
+        }
+        Ok(Self { factor, dimension, offset: None })
+    }
+}
+
+/// Describes a definition whose factor or dimension grows too large to hold,
+/// such as one multiplying many large powers of a unit
+fn too_large() -> String {
+    "combines powers too large to represent".to_string()
+}
+
+/// Characters that combine units and numbers within a definition
+const OPERATORS: &str = "*/^|+-()·;,=";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(BigRational),
+    Name(String),
+    Operator(char),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = expression.chars().collect();
+    let mut pos = 0;
+
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c == '*' && chars.get(pos + 1) == Some(&'*') {
+            tokens.push(Token::Operator('^'));
+            pos += 2;
+        } else if OPERATORS.contains(c) {
+            tokens.push(Token::Operator(if c == '·' { '*' } else { c }));
+            pos += 1;
+        } else if c.is_ascii_digit() || (c == '.' && chars.get(pos + 1).is_some_and(char::is_ascii_digit)) {
+            let start = pos;
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            // An exponent needs digits after the e, so "2 erg" isn't read as a number
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let digits = match chars.get(pos + 1) {
+                    Some('+') | Some('-') => pos + 2,
+                    _ => pos + 1,
+                };
+                if chars.get(digits).is_some_and(char::is_ascii_digit) {
+                    pos = digits;
+                    while pos < chars.len() && chars[pos].is_ascii_digit() {
+                        pos += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..pos].iter().collect();
+            let value = exact::parse_rational(&text).ok_or_else(|| format!("has an invalid number '{}'", text))?;
+            tokens.push(Token::Number(value));
+        } else {
+            let start = pos;
+            while pos < chars.len() && !chars[pos].is_whitespace() && !OPERATORS.contains(chars[pos]) {
+                pos += 1;
+            }
+            let name: String = chars[start..pos].iter().collect();
+            tokens.push(if name == "per" { Token::Operator('/') } else { Token::Name(name) });
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Definition(usize),
+    Prefix(String),
+}
+
+/// Evaluates definitions to quantities, resolving the units they name in the
+/// database first and in the registry after
+struct Evaluator<'a> {
+    database: &'a Database,
+    registry: &'a UnitRegistry,
+    format: ImportFormat,
+    // Longest first, so "kilo" is tried before "k"
+    prefixes: Vec<&'a str>,
+    results: HashMap<Key, Result<Quantity, String>>,
+    resolving: Vec<Key>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(database: &'a Database, registry: &'a UnitRegistry, format: ImportFormat) -> Self {
+        let mut prefixes: Vec<&str> = database.prefixes.keys().map(String::as_str).collect();
+        prefixes.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        Self { database, registry, format, prefixes, results: HashMap::new(), resolving: Vec::new() }
+    }
+
+    fn definition(&mut self, index: usize) -> Result<Quantity, String> {
+        self.memoized(Key::Definition(index))
+    }
+
+    fn memoized(&mut self, key: Key) -> Result<Quantity, String> {
+        if let Some(result) = self.results.get(&key) {
+            return result.clone();
+        }
+        if let Some(start) = self.resolving.iter().position(|k| *k == key) {
+            let names: Vec<&str> = self.resolving[start..].iter()
+                .chain(std::iter::once(&key))
+                .map(|key| match key {
+                    Key::Definition(index) => self.database.definitions[*index].name.as_str(),
+                    Key::Prefix(name) => name.as_str(),
+                })
+                .collect();
+            return Err(format!("has a cyclic definition: {}", names.join(" -> ")));
+        }
+
+        self.resolving.push(key.clone());
+        let result = match &key {
+            Key::Definition(index) => self.evaluate_definition(*index),
+            Key::Prefix(name) => {
+                let (_, expression) = &self.database.prefixes[name];
+                self.evaluate(expression)
+            },
+        };
+        self.resolving.pop();
+
+        self.results.insert(key, result.clone());
+        result
+    }
+
+    /// Evaluates a unit another definition uses, saying which one failed if it does
+    fn dependency(&mut self, index: usize) -> Result<Quantity, String> {
+        self.definition(index).map_err(|message| {
+            format!("depends on '{}', which {}", self.database.definitions[index].name, message)
+        })
+    }
+
+    fn evaluate_definition(&mut self, index: usize) -> Result<Quantity, String> {
+        let definition = &self.database.definitions[index];
+        match &definition.body {
+            Body::Base(None) => Err("is a base unit with no counterpart among the base quantities".to_string()),
+            Body::Base(Some(dimension)) => {
+                // Base units needn't be coherent SI units, as Pint's gram isn't
+                let names = std::iter::once(&definition.name).chain(definition.symbol.iter());
+                let factor = names
+                    .filter_map(|name| self.registry.lookup_unit(name, None).ok().flatten())
+                    .find(|unit| unit.dimension() == Some(*dimension))
+                    .and_then(|unit| unit.exact().filter(|c| !c.has_offset()).map(|c| c.factor().clone()))
+                    .unwrap_or_else(BigRational::one);
+                Ok(Quantity { factor, dimension: *dimension, offset: None })
+            },
+            Body::Expression { expression, offset } => {
+                let quantity = self.evaluate(expression)?;
+                match offset {
+                    None => Ok(quantity),
+                    Some(offset) => {
+                        let offset = self.evaluate(offset)?;
+                        if !offset.dimension.is_dimensionless() {
+                            return Err("has an offset that isn't a plain number".to_string());
+                        }
+                        Ok(Quantity { offset: Some(offset.factor), ..quantity.linear()? })
+                    },
+                }
+            },
+        }
+    }
+
+    fn evaluate(&mut self, expression: &str) -> Result<Quantity, String> {
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0, evaluator: self };
+        let quantity = parser.sum()?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(quantity),
+            Some(token) => Err(format!("has an unexpected {}", describe(token))),
+        }
+    }
+
+    /// Looks up a unit the way GNU units does: as written, with a prefix, and
+    /// then without a plural ending
+    fn lookup(&mut self, name: &str) -> Result<Quantity, String> {
+        if let Some(result) = self.lookup_prefixed(name) {
+            return result;
+        }
+        if self.format == ImportFormat::Gnu {
+            for stem in plural_stems(name) {
+                if let Some(result) = self.lookup_prefixed(&stem) {
+                    return result;
+                }
+            }
+        }
+
+        match self.registry.lookup_unit(name, None) {
+            Ok(Some(unit)) => {
+                let (Some(conversion), Some(dimension)) = (unit.exact(), unit.dimension()) else {
+                    return Err(format!("uses '{}', which has no exact definition", name));
+                };
+                if conversion.has_offset() {
+                    return Err(format!("uses '{}', which has an offset", name));
+                }
+                return Ok(Quantity { factor: conversion.factor().clone(), dimension, offset: None });
+            },
+            Err(ApiError::Ambiguous { message, .. }) => return Err(message),
+            _ => {},
+        }
+
+        // GNU units reads a trailing digit as an exponent, as in "m2" or "cm3"
+        if self.format == ImportFormat::Gnu {
+            let digit = name.chars().last().and_then(|c| c.to_digit(10)).filter(|d| *d > 1);
+            if let Some(exponent) = digit {
+                let base = &name[..name.len() - 1];
+                if !base.is_empty() && !base.ends_with(|c: char| c.is_ascii_digit() || c == '_') {
+                    return self.lookup(base)?.powi(exponent as i32);
+                }
+            }
+        }
+
+        Err(format!("uses unknown unit '{}'", name))
+    }
+
+    fn lookup_prefixed(&mut self, name: &str) -> Option<Result<Quantity, String>> {
+        if let Some(&index) = self.database.index.get(name) {
+            return Some(self.dependency(index));
+        }
+        if self.database.prefixes.contains_key(name) {
+            return Some(self.memoized(Key::Prefix(name.to_string())));
+        }
+
+        let prefix = self.prefixes.iter()
+            .find(|&&prefix| name.len() > prefix.len() && name.starts_with(prefix)
+                && self.database.index.contains_key(&name[prefix.len()..]))
+            .copied()?;
+        let unit = self.database.index[&name[prefix.len()..]];
+        Some(self.memoized(Key::Prefix(prefix.to_string()))
+            .and_then(|prefix| prefix.multiply(self.dependency(unit)?)))
+    }
+}
+
+/// Singular forms a GNU units name might be the plural of
+fn plural_stems(name: &str) -> Vec<String> {
+    let mut stems = Vec::new();
+    if let Some(stem) = name.strip_suffix("ies") {
+        stems.push(format!("{}y", stem));
+    }
+    if let Some(stem) = name.strip_suffix("es") {
+        stems.push(stem.to_string());
+    }
+    if let Some(stem) = name.strip_suffix('s') {
+        stems.push(stem.to_string());
+    }
+    stems.retain(|stem| !stem.is_empty());
+    stems
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Number(value) => format!("number {}", exact::format_rational(value)),
+        Token::Name(name) => format!("name '{}'", name),
+        Token::Operator(op) => format!("'{}'", op),
+    }
+}
+
+/// Recursive-descent parser over a definition
+///
+/// As in GNU units, multiplying by juxtaposition binds more tightly than `*`
+/// and `/`, so "J / mol K" is J/(mol·K), and `|` divides numbers, as in "1|3".
+struct Parser<'t, 'e, 'a> {
+    tokens: &'t [Token],
+    pos: usize,
+    evaluator: &'e mut Evaluator<'a>,
+}
+
+impl Parser<'_, '_, '_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Operator(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn sum(&mut self) -> Result<Quantity, String> {
+        let mut quantity = self.product()?;
+        loop {
+            if self.eat('+') {
+                quantity = quantity.add(self.product()?, false)?;
+            } else if self.eat('-') {
+                quantity = quantity.add(self.product()?, true)?;
+            } else {
+                return Ok(quantity);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Quantity, String> {
+        let mut quantity = self.juxtaposition()?;
+        loop {
+            if self.eat('*') {
+                quantity = quantity.multiply(self.juxtaposition()?)?;
+            } else if self.eat('/') {
+                quantity = quantity.divide(self.juxtaposition()?)?;
+            } else {
+                return Ok(quantity);
+            }
+        }
+    }
+
+    fn juxtaposition(&mut self) -> Result<Quantity, String> {
+        let mut quantity = self.power()?;
+        while matches!(self.peek(), Some(Token::Number(_)) | Some(Token::Name(_)) | Some(Token::Operator('('))) {
+            quantity = quantity.multiply(self.power()?)?;
+        }
+        Ok(quantity)
+    }
+
+    fn power(&mut self) -> Result<Quantity, String> {
+        let quantity = self.unary()?;
+        if !self.eat('^') {
+            return Ok(quantity);
+        }
+
+        let parenthesized = self.eat('(');
+        let negative = self.eat('-');
+        if !negative {
+            self.eat('+');
+        }
+        let exponent = match self.peek() {
+            Some(Token::Number(value)) if value.is_integer() => value.to_integer(),
+            Some(Token::Number(_)) => return Err("has a fractional exponent, which can't be expressed".to_string()),
+            Some(token) => return Err(format!("has an unexpected {} as an exponent", describe(token))),
+            None => return Err("ends without an exponent".to_string()),
+        };
+        self.pos += 1;
+        if self.peek() == Some(&Token::Operator('|')) {
+            return Err("has a fractional exponent, which can't be expressed".to_string());
+        }
+        if parenthesized && !self.eat(')') {
+            return Err("has an unclosed parenthesis".to_string());
+        }
+
+        let exponent: i32 = exponent.try_into().ok()
+            .filter(|e: &i32| e.abs() <= MAX_EXPONENT)
+            .ok_or_else(|| "has an exponent that's too large".to_string())?;
+        quantity.powi(if negative { -exponent } else { exponent })
+    }
+
+    fn unary(&mut self) -> Result<Quantity, String> {
+        if self.eat('-') {
+            let quantity = self.unary()?.linear()?;
+            return Ok(Quantity { factor: -quantity.factor, ..quantity });
+        }
+        self.eat('+');
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Quantity, String> {
+        let token = self.peek().cloned().ok_or_else(|| "ends unexpectedly".to_string())?;
+        self.pos += 1;
+
+        match token {
+            Token::Number(value) => {
+                if !self.eat('|') {
+                    return Ok(Quantity::number(value));
+                }
+                match self.peek().cloned() {
+                    Some(Token::Number(denominator)) if !denominator.is_zero() => {
+                        self.pos += 1;
+                        Ok(Quantity::number(value / denominator))
+                    },
+                    _ => Err("has a '|' that doesn't divide two numbers".to_string()),
+                }
+            },
+            Token::Name(name) => {
+                if self.peek() == Some(&Token::Operator('(')) {
+                    return Err(format!("uses the function '{}', which can't be expressed as a factor and offset", name));
+                }
+                self.evaluator.lookup(&name)
+            },
+            Token::Operator('(') => {
+                let quantity = self.sum()?;
+                if !self.eat(')') {
+                    return Err("has an unclosed parenthesis".to_string());
+                }
+                Ok(quantity)
+            },
+            token => Err(format!("has an unexpected {}", describe(&token))),
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use std::sync::Once;
+use std::sync::{Arc, Once};
+use crate::errors::ApiError;
+use crate::models::definitions::{DefinitionSet, DEFINITIONS_DIR};
+use crate::models::import::{self, ImportFormat, ImportReport};
+use crate::models::rates::{self, FileRateProvider};
 use crate::models::registry;
 use crate::models::units::length::{
     LengthCategory,
@@ -18,8 +22,9 @@ use crate::models::units::volume::{
     CubicInch, CubicFoot, CubicYard
 };
 use crate::models::units::temperature::{
-    TemperatureCategory,
-    Kelvin, Celsius, Fahrenheit, Rankine, Reaumur
+    TemperatureCategory, TemperatureIntervalCategory,
+    Kelvin, Celsius, Fahrenheit, Rankine, Reaumur,
+    KelvinInterval, CelsiusInterval, FahrenheitInterval, RankineInterval, ReaumurInterval
 };
 use crate::models::units::area::{
     AreaCategory,
@@ -52,6 +57,19 @@ use crate::models::units::power::{
     BTUPerHour, FootPoundPerSecond, CaloriePerSecond,
     JoulePerSecond, KilocaloriePerHour
 };
+use crate::models::units::force::{
+    ForceCategory,
+    Newton, Kilonewton, Dyne, KilogramForce, PoundForce, Poundal
+};
+use crate::models::units::data::{
+    DataCategory,
+    Bit, Nibble, Byte
+};
+use crate::models::units::data_rate::{
+    DataRateCategory,
+    BitPerSecond, BytePerSecond
+};
+use crate::models::units::currency::register_currencies;
 
 // Ensure initialization happens only once
 static INIT: Once = Once::new();
@@ -127,6 +145,17 @@ pub fn init() {
             let _ = reg.register_unit(Rankine::new());
             let _ = reg.register_unit(Reaumur::new());
             
+            // Register temperature interval category
+            let temperature_interval_category = TemperatureIntervalCategory;
+            let _ = reg.register_category(temperature_interval_category);
+            
+            // Register temperature interval units
+            let _ = reg.register_unit(KelvinInterval::new());
+            let _ = reg.register_unit(CelsiusInterval::new());
+            let _ = reg.register_unit(FahrenheitInterval::new());
+            let _ = reg.register_unit(RankineInterval::new());
+            let _ = reg.register_unit(ReaumurInterval::new());
+            
             // Register area category
             let area_category = AreaCategory;
             let _ = reg.register_category(area_category);
@@ -223,6 +252,130 @@ pub fn init() {
             let _ = reg.register_unit(CaloriePerSecond::new());
             let _ = reg.register_unit(JoulePerSecond::new());
             let _ = reg.register_unit(KilocaloriePerHour::new());
+            
+            // Register force category
+            let force_category = ForceCategory;
+            let _ = reg.register_category(force_category);
+            
+            // Register force units
+            let _ = reg.register_unit(Newton::new());
+            let _ = reg.register_unit(Kilonewton::new());
+            let _ = reg.register_unit(Dyne::new());
+            let _ = reg.register_unit(KilogramForce::new());
+            let _ = reg.register_unit(PoundForce::new());
+            let _ = reg.register_unit(Poundal::new());
+            
+            // Register data category
+            let data_category = DataCategory;
+            let _ = reg.register_category(data_category);
+            
+            // Register data units
+            let _ = reg.register_unit(Bit::new());
+            let _ = reg.register_unit(Nibble::new());
+            let _ = reg.register_unit(Byte::new());
+            
+            // Register data rate category
+            let data_rate_category = DataRateCategory;
+            let _ = reg.register_category(data_rate_category);
+            
+            // Register data rate units
+            let _ = reg.register_unit(BitPerSecond::new());
+            let _ = reg.register_unit(BytePerSecond::new());
+            
+            // Register the currency category and the currencies of the installed
+            // rate provider, whose rates can change after registration
+            let _ = register_currencies(&mut reg);
+            
+            // Generate the SI prefixes of prefixable units (GJ, hPa, mW, ...)
+            // that aren't written out above
+            let _ = reg.register_prefixed_units();
+            
+            // Register common aliases that can't be derived from unit names
+            for (category, symbol, alias) in [
+                ("length", "μm", "micron"),
+                ("length", "μm", "microns"),
+                ("mass", "lb", "lbs"),
+                ("mass", "t", "tonne"),
+                ("mass", "t", "tonnes"),
+                ("volume", "cm³", "cc"),
+                ("volume", "gal", "gallon"),
+                ("volume", "gal", "gallons"),
+                ("volume", "qt", "quart"),
+                ("volume", "qt", "quarts"),
+                ("volume", "pt", "pint"),
+                ("volume", "pt", "pints"),
+                ("volume", "fl oz", "fluid ounce"),
+                ("volume", "fl oz", "fluid ounces"),
+                ("temperature", "°C", "centigrade"),
+                ("time", "s", "sec"),
+                ("time", "s", "secs"),
+                ("time", "min", "mins"),
+                ("time", "h", "hr"),
+                ("time", "h", "hrs"),
+                ("time", "yr", "yrs"),
+                ("speed", "km/h", "kph"),
+                ("speed", "km/h", "kmh"),
+                ("speed", "kn", "kt"),
+                ("energy", "kcal", "Cal"),
+                ("power", "BTU/h", "BTU/hr"),
+                ("data", "bit", "b"),
+                ("data", "B", "octet"),
+                ("data", "B", "octets"),
+                ("data_rate", "bit/s", "bps"),
+                ("data_rate", "kbit/s", "kbps"),
+                ("data_rate", "Mbit/s", "Mbps"),
+                ("data_rate", "Gbit/s", "Gbps"),
+                ("data_rate", "Tbit/s", "Tbps"),
+                ("data_rate", "B/s", "Bps"),
+                ("data_rate", "kB/s", "kBps"),
+                ("data_rate", "MB/s", "MBps"),
+                ("data_rate", "GB/s", "GBps"),
+                ("data_rate", "TB/s", "TBps"),
+            ] {
+                let _ = reg.register_alias(category, symbol, alias);
+            }
         }
     });
-} 
\ No newline at end of file
+} 
+
+/// What `setup` added to the registry beyond the built-in units
+#[derive(Debug, Default)]
+pub struct Setup {
+    /// Units loaded from the definition files
+    pub defined: usize,
+    /// The report of each imported database, with its path
+    pub imports: Vec<(String, ImportReport)>,
+}
+
+/// Initialize the unit registry, then add the units of the definition files under
+/// config/, those of the given GNU units or Pint databases and, when `rates_file`
+/// is given, the currencies it has exchange rates for
+///
+/// Stops at the first source that doesn't load, so the server and the command
+/// line tool both refuse to run with a registry other than the one configured.
+pub fn setup(imports: &[String], format: Option<ImportFormat>, rates_file: Option<&str>) -> Result<Setup, ApiError> {
+    init();
+
+    let registry = || registry().write().map_err(|_| {
+        ApiError::InternalError("Failed to access unit registry".to_string())
+    });
+
+    let mut setup = Setup {
+        defined: DefinitionSet::load_dir(DEFINITIONS_DIR)?.register(&mut *registry()?)?,
+        ..Default::default()
+    };
+
+    for path in imports {
+        let report = import::import_file(&mut *registry()?, path, format)?;
+        setup.imports.push((path.clone(), report));
+    }
+
+    // Convert currencies at the file's rates rather than at rates posted to the
+    // admin endpoint
+    if let Some(path) = rates_file {
+        rates::set_rate_provider(Arc::new(FileRateProvider::load(path)?));
+        register_currencies(&mut *registry()?)?;
+    }
+
+    Ok(setup)
+}
@@ -2,7 +2,33 @@ pub mod unit;
 pub mod registry;
 pub mod units;
 pub mod init;
+pub mod dimension;
+pub mod compound;
+pub mod quantity;
+pub mod tolerance;
+pub mod uncertainty;
+pub mod alias;
+pub mod exact;
+pub mod decimal;
+pub mod prefix;
+pub mod system;
+pub mod defined;
+pub mod rates;
+pub mod definitions;
+pub mod import;
 
 // Re-export key types for easier imports
-pub use unit::{Unit, UnitCategory, Measurement};
-pub use registry::{UnitRegistry, registry, AnyUnit}; 
\ No newline at end of file
+pub use unit::{Unit, UnitCategory, LinearCategory, Measurement};
+pub use registry::{UnitRegistry, registry, AnyUnit, ListingOrder, MatchKind, UnitMatch};
+pub use dimension::Dimension;
+pub use compound::CompoundUnit;
+pub use quantity::Quantity;
+pub use tolerance::Tolerance;
+pub use uncertainty::Uncertainty;
+pub use exact::LinearConversion;
+pub use decimal::DecimalValue;
+pub use prefix::{Prefix, Prefixes};
+pub use system::UnitSystem;
+pub use defined::{DefinedUnit, DefinedCategory};
+pub use rates::{RateProvider, ExchangeRate, RateUpdate};
+pub use definitions::{Definitions, DefinitionSet}; 
\ No newline at end of file
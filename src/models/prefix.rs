@@ -0,0 +1,79 @@
+/// A multiplier that can be attached to a unit's name and symbol,
+/// such as "kilo" (k) for 1000
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prefix {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    // Exact factor, written as a decimal or power of ten
+    pub factor: &'static str,
+}
+
+const fn prefix(name: &'static str, symbol: &'static str, factor: &'static str) -> Prefix {
+    Prefix { name, symbol, factor }
+}
+
+/// SI prefixes from quecto (10⁻³⁰) to quetta (10³⁰)
+pub const SI_PREFIXES: [Prefix; 24] = [
+    prefix("quecto", "q", "1e-30"),
+    prefix("ronto", "r", "1e-27"),
+    prefix("yocto", "y", "1e-24"),
+    prefix("zepto", "z", "1e-21"),
+    prefix("atto", "a", "1e-18"),
+    prefix("femto", "f", "1e-15"),
+    prefix("pico", "p", "1e-12"),
+    prefix("nano", "n", "1e-9"),
+    prefix("micro", "μ", "1e-6"),
+    prefix("milli", "m", "1e-3"),
+    prefix("centi", "c", "1e-2"),
+    prefix("deci", "d", "1e-1"),
+    prefix("deca", "da", "1e1"),
+    prefix("hecto", "h", "1e2"),
+    prefix("kilo", "k", "1e3"),
+    prefix("mega", "M", "1e6"),
+    prefix("giga", "G", "1e9"),
+    prefix("tera", "T", "1e12"),
+    prefix("peta", "P", "1e15"),
+    prefix("exa", "E", "1e18"),
+    prefix("zetta", "Z", "1e21"),
+    prefix("yotta", "Y", "1e24"),
+    prefix("ronna", "R", "1e27"),
+    prefix("quetta", "Q", "1e30"),
+];
+
+/// IEC binary prefixes from kibi (2¹⁰) to yobi (2⁸⁰)
+pub const BINARY_PREFIXES: [Prefix; 8] = [
+    prefix("kibi", "Ki", "1024"),
+    prefix("mebi", "Mi", "1048576"),
+    prefix("gibi", "Gi", "1073741824"),
+    prefix("tebi", "Ti", "1099511627776"),
+    prefix("pebi", "Pi", "1125899906842624"),
+    prefix("exbi", "Ei", "1152921504606846976"),
+    prefix("zebi", "Zi", "1180591620717411303424"),
+    prefix("yobi", "Yi", "1208925819614629174706176"),
+];
+
+/// The set of prefixes a unit accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Prefixes {
+    /// The unit takes no prefixes
+    #[default]
+    None,
+    /// Every SI prefix, as for the meter or the joule
+    Metric,
+    /// SI multiples from kilo upwards plus the binary prefixes, as for the byte
+    Binary,
+}
+
+impl Prefixes {
+    /// Returns the prefixes in the set
+    pub fn list(self) -> Vec<&'static Prefix> {
+        match self {
+            Prefixes::None => Vec::new(),
+            Prefixes::Metric => SI_PREFIXES.iter().collect(),
+            Prefixes::Binary => SI_PREFIXES.iter()
+                .skip_while(|p| p.symbol != "k")
+                .chain(BINARY_PREFIXES.iter())
+                .collect(),
+        }
+    }
+}
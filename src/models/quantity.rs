@@ -0,0 +1,286 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::errors::ApiError;
+use crate::models::compound::{CompoundUnit, UnitFactor};
+use crate::models::dimension::superscript;
+use crate::models::registry::UnitRegistry;
+use crate::models::unit::UnitCategory;
+use crate::models::units::temperature::{TemperatureCategory, TemperatureIntervalCategory};
+
+/// A number with the unit it's expressed in, or a plain number without one
+///
+/// Quantities keep the units they were written in: multiplying and dividing
+/// combines the units, as in `mi/min`, and adding converts the right-hand side
+/// to the unit of the left. Temperatures are points on a scale rather than
+/// amounts, so the difference of two is a temperature interval on the scale of
+/// the first, an interval can be added to or subtracted from one, adding two
+/// is an error, and multiplying or dividing one on a scale with an offset works
+/// in kelvins.
+#[derive(Debug, Clone)]
+pub struct Quantity {
+    number: f64,
+    unit: Option<CompoundUnit>,
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.unit {
+            Some(unit) => write!(f, "{} {}", format_number(self.number), unit.expression()),
+            None => write!(f, "{}", format_number(self.number)),
+        }
+    }
+}
+
+/// Rounds to 12 significant digits, hiding the noise floating point leaves
+/// in results such as 25.749503999999998
+fn format_number(number: f64) -> String {
+    if !number.is_finite() {
+        return number.to_string();
+    }
+    format!("{:.11e}", number).parse::<f64>().unwrap_or(number).to_string()
+}
+
+/// Writes a unit expression from its factors, as in "kg·m²/s²"
+fn unit_expression(factors: &[UnitFactor]) -> String {
+    let power = |symbol: &str, exponent: i32| match exponent {
+        1 => symbol.to_string(),
+        _ if symbol.ends_with(char::is_alphabetic) => format!("{}{}", symbol, superscript(exponent)),
+        _ => format!("{}^{}", symbol, exponent),
+    };
+
+    let numerator: Vec<String> = factors.iter()
+        .filter(|f| f.exponent > 0)
+        .map(|f| power(f.symbol, f.exponent))
+        .collect();
+    let denominator: Vec<String> = factors.iter()
+        .filter(|f| f.exponent < 0)
+        .map(|f| power(f.symbol, -f.exponent))
+        .collect();
+
+    match (numerator.is_empty(), denominator.is_empty()) {
+        (_, true) => numerator.join("·"),
+        (true, false) => factors.iter().map(|f| power(f.symbol, f.exponent)).collect::<Vec<_>>().join("·"),
+        (false, false) => format!("{}/{}", numerator.join("·"), denominator.join("/")),
+    }
+}
+
+/// Cancels temperature units against each other, as in W·Δ°C/K, since within
+/// a product each stands for a temperature difference of its own size, leaving
+/// the first one written on the side that remains
+fn cancel_temperatures(factors: &[UnitFactor]) -> Vec<UnitFactor> {
+    let is_temperature = |factor: &UnitFactor| {
+        factor.category == TemperatureCategory.name() || factor.category == TemperatureIntervalCategory.name()
+    };
+    let exponents: Vec<i32> = factors.iter().filter(|f| is_temperature(f)).map(|f| f.exponent).collect();
+    if !(exponents.iter().any(|&e| e > 0) && exponents.iter().any(|&e| e < 0)) {
+        return factors.to_vec();
+    }
+
+    let net: i32 = exponents.iter().sum();
+    let kept = factors.iter().position(|f| is_temperature(f) && f.exponent.signum() == net.signum());
+    factors.iter().enumerate()
+        .filter(|&(i, f)| !is_temperature(f) || Some(i) == kept)
+        .map(|(i, f)| match Some(i) == kept {
+            true => UnitFactor { exponent: net, ..f.clone() },
+            false => f.clone(),
+        })
+        .collect()
+}
+
+/// Returns the interval unit matching a temperature scale, such as Δ°F for °F,
+/// or `None` for scales without one
+fn interval_unit(scale: &CompoundUnit, registry: &UnitRegistry) -> Result<Option<CompoundUnit>, ApiError> {
+    let [factor] = scale.factors() else {
+        return Ok(None);
+    };
+    match registry.lookup_unit(&format!("Δ{}", factor.symbol), Some(TemperatureIntervalCategory.name()))? {
+        Some(unit) => CompoundUnit::parse(unit.symbol(), registry).map(Some),
+        None => Ok(None),
+    }
+}
+
+impl Quantity {
+    /// Creates a quantity in the given unit
+    pub fn new(number: f64, unit: CompoundUnit) -> Self {
+        Self { number, unit: Some(unit) }
+    }
+
+    /// Creates a plain number without a unit
+    pub fn plain(number: f64) -> Self {
+        Self { number, unit: None }
+    }
+
+    /// Returns the number of units
+    pub fn number(&self) -> f64 {
+        self.number
+    }
+
+    /// Returns the unit, or `None` for a plain number
+    pub fn unit(&self) -> Option<&CompoundUnit> {
+        self.unit.as_ref()
+    }
+
+    /// Returns the quantity with its sign flipped
+    pub fn negate(self) -> Self {
+        Self { number: -self.number, ..self }
+    }
+
+    /// Returns the unit if the quantity is a temperature rather than an amount
+    fn temperature(&self) -> Option<&CompoundUnit> {
+        self.unit.as_ref().filter(|unit| unit.is_temperature())
+    }
+
+    /// Expresses the quantity in the unit `expression`, which is written in terms
+    /// of the units it was combined from, tidying the expression and dropping it
+    /// altogether if the units cancel out
+    fn with_unit(number: f64, expression: &str, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        let unit = CompoundUnit::parse(expression, registry)?;
+        if unit.dimension().is_dimensionless() {
+            return Ok(Self::plain(unit.to_base(number)));
+        }
+
+        let tidy = CompoundUnit::parse(&unit_expression(&cancel_temperatures(unit.factors())), registry)?;
+        Ok(Self { number: unit.convert(number, &tidy)?, unit: Some(tidy) })
+    }
+
+    /// Expresses a temperature interval in degrees of a temperature scale
+    fn degrees_of(&self, scale: &CompoundUnit, registry: &UnitRegistry) -> Result<f64, ApiError> {
+        let Some(unit) = &self.unit else {
+            return Ok(self.number);
+        };
+        match interval_unit(scale, registry)? {
+            Some(interval) => unit.convert(self.number, &interval),
+            // Interval units have no offset, so their base value is in kelvins
+            None => Ok(unit.to_base(self.number) / (scale.to_base(1.0) - scale.to_base(0.0))),
+        }
+    }
+
+    /// Adds another quantity, converted to the unit of this one
+    pub fn add(self, other: Quantity, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        self.sum(other, 1.0, registry)
+    }
+
+    /// Subtracts another quantity, converted to the unit of this one
+    pub fn subtract(self, other: Quantity, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        self.sum(other, -1.0, registry)
+    }
+
+    fn sum(self, other: Quantity, sign: f64, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        let operation = if sign > 0.0 { "add" } else { "subtract" };
+        let (left, right) = match (&self.unit, &other.unit) {
+            (None, None) => return Ok(Self::plain(self.number + sign * other.number)),
+            (Some(left), Some(right)) => (left, right),
+            (Some(unit), None) | (None, Some(unit)) => return Err(ApiError::BadRequest(format!(
+                "Cannot {} a plain number and a quantity in '{}'", operation, unit.expression()
+            ))),
+        };
+        if left.dimension() != right.dimension() {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot {} '{}' ({}) and '{}' ({}): dimensions differ",
+                operation, left.expression(), left.dimension(), right.expression(), right.dimension()
+            )));
+        }
+
+        match (self.temperature(), other.temperature()) {
+            (Some(scale), Some(_)) if sign > 0.0 => Err(ApiError::BadRequest(format!(
+                "Cannot add the temperatures '{}' and '{}'; add a temperature interval such as '{}' instead",
+                self, other, interval_unit(scale, registry)?.as_ref().map_or("ΔK", |unit| unit.expression())
+            ))),
+            // The difference of two temperatures is an interval on the scale of the first
+            (Some(scale), Some(_)) => {
+                let difference = self.number - right.convert(other.number, scale)?;
+                match interval_unit(scale, registry)? {
+                    Some(interval) => Ok(Self::new(difference, interval)),
+                    None => Ok(Self::new(
+                        scale.to_base(difference) - scale.to_base(0.0),
+                        CompoundUnit::parse("ΔK", registry)?,
+                    )),
+                }
+            },
+            (Some(scale), None) => {
+                let degrees = other.degrees_of(scale, registry)?;
+                Ok(Self { number: self.number + sign * degrees, unit: self.unit })
+            },
+            (None, Some(scale)) if sign > 0.0 => {
+                let degrees = self.degrees_of(scale, registry)?;
+                Ok(Self { number: other.number + degrees, unit: other.unit })
+            },
+            (None, Some(_)) => Err(ApiError::BadRequest(format!(
+                "Cannot subtract the temperature '{}' from '{}'", other, self
+            ))),
+            (None, None) => {
+                let number = self.number + sign * right.convert(other.number, left)?;
+                Ok(Self { number, unit: self.unit })
+            },
+        }
+    }
+
+    /// Multiplies by another quantity, combining their units
+    pub fn multiply(self, other: Quantity, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        self.product(other, 1, registry)
+    }
+
+    /// Divides by another quantity, combining their units
+    pub fn divide(self, other: Quantity, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        self.product(other, -1, registry)
+    }
+
+    fn product(self, other: Quantity, sign: i32, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        let (this, other) = (self.in_kelvins(registry)?, other.in_kelvins(registry)?);
+        let number = if sign > 0 { this.number * other.number } else { this.number / other.number };
+        let operator = if sign > 0 { "·" } else { "/" };
+
+        match (&this.unit, &other.unit) {
+            (None, None) => Ok(Self::plain(number)),
+            (Some(_), None) => Ok(Self { number, unit: this.unit }),
+            (None, Some(_)) if sign > 0 => Ok(Self { number, unit: other.unit }),
+            (left, Some(right)) => {
+                let left = left.as_ref().map_or("1", |unit| unit.expression());
+                Self::with_unit(number, &format!("({}){}({})", left, operator, right.expression()), registry)
+            },
+        }
+    }
+
+    /// Expresses a temperature on a scale with an offset, such as °C, in kelvins,
+    /// so that scaling it scales the temperature above absolute zero rather than
+    /// the reading
+    fn in_kelvins(self, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        match &self.unit {
+            Some(unit) if unit.has_offset() && unit.is_temperature() => {
+                self.convert_to(&CompoundUnit::parse("K", registry)?)
+            },
+            _ => Ok(self),
+        }
+    }
+
+    /// Raises the quantity, and its unit, to an integer power
+    pub fn pow(self, exponent: i32, registry: &UnitRegistry) -> Result<Self, ApiError> {
+        let number = self.number.powi(exponent);
+        match (&self.unit, exponent) {
+            (_, 1) => Ok(self),
+            (None, _) | (_, 0) => Ok(Self::plain(number)),
+            (Some(unit), _) => Self::with_unit(number, &format!("({})^{}", unit.expression(), exponent), registry),
+        }
+    }
+
+    /// Converts the quantity to another unit of the same dimension
+    ///
+    /// A plain number converts to a dimensionless unit such as percent, and a
+    /// temperature and a temperature interval don't convert to each other.
+    pub fn convert_to(&self, target: &CompoundUnit) -> Result<Self, ApiError> {
+        let number = match &self.unit {
+            Some(unit) if unit.dimension() == target.dimension() && unit.is_temperature() != target.is_temperature() => {
+                return Err(ApiError::BadRequest(format!(
+                    "Cannot convert '{}' to '{}': only one is a temperature, the other a temperature interval",
+                    self, target.expression()
+                )));
+            },
+            Some(unit) => unit.convert(self.number, target)?,
+            None if target.dimension().is_dimensionless() => target.from_base(self.number),
+            None => return Err(ApiError::BadRequest(format!(
+                "Cannot convert the plain number {} to '{}'", self, target.expression()
+            ))),
+        };
+
+        Ok(Self { number, unit: Some(target.clone()) })
+    }
+}
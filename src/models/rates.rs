@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::errors::ApiError;
+
+/// The value of a currency against a provider's base currency, and when it was quoted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeRate {
+    /// Units of the currency one unit of the base currency buys
+    pub rate: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Exchange rates of a set of currencies against a single base currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateTable {
+    base: String,
+    // When the table last changed, which is when its base currency's rate of 1 was quoted
+    timestamp: DateTime<Utc>,
+    rates: HashMap<String, ExchangeRate>,
+}
+
+/// One row of a CSV rate file
+#[derive(Debug, Deserialize)]
+struct RateRecord {
+    base: String,
+    currency: String,
+    rate: f64,
+    timestamp: DateTime<Utc>,
+}
+
+/// A JSON rate file, quoting every rate at the same time
+#[derive(Debug, Deserialize)]
+struct RateDocument {
+    base: String,
+    timestamp: DateTime<Utc>,
+    rates: HashMap<String, f64>,
+}
+
+impl RateTable {
+    /// Creates a table holding only its base currency
+    pub fn new(base: &str, timestamp: DateTime<Utc>) -> Result<Self, ApiError> {
+        Ok(Self {
+            base: check_code(base)?,
+            timestamp,
+            rates: HashMap::new(),
+        })
+    }
+
+    /// Parses rates from CSV with a `base,currency,rate,timestamp` header,
+    /// where every row has the same base
+    pub fn from_csv(text: &str) -> Result<Self, ApiError> {
+        let mut table: Option<RateTable> = None;
+
+        for (index, record) in csv::Reader::from_reader(text.as_bytes()).deserialize().enumerate() {
+            let record: RateRecord = record.map_err(|err| ApiError::BadRequest(format!(
+                "Invalid exchange rate on line {}: {}", index + 2, err
+            )))?;
+
+            let table = match &mut table {
+                Some(table) => table,
+                None => table.insert(RateTable::new(&record.base, record.timestamp)?),
+            };
+
+            if !record.base.eq_ignore_ascii_case(&table.base) {
+                return Err(ApiError::BadRequest(format!(
+                    "Exchange rates must share a base currency, found '{}' and '{}'",
+                    table.base, record.base
+                )));
+            }
+
+            table.timestamp = table.timestamp.max(record.timestamp);
+            table.set_rate(&record.currency, ExchangeRate { rate: record.rate, timestamp: record.timestamp })?;
+        }
+
+        table.ok_or_else(|| ApiError::BadRequest("Exchange rate file has no rates".to_string()))
+    }
+
+    /// Parses rates from JSON of the form
+    /// `{"base": "USD", "timestamp": "2024-06-03T16:00:00Z", "rates": {"EUR": 0.92}}`
+    pub fn from_json(text: &str) -> Result<Self, ApiError> {
+        let document: RateDocument = serde_json::from_str(text).map_err(|err| ApiError::BadRequest(format!(
+            "Invalid exchange rate file: {}", err
+        )))?;
+
+        let mut table = RateTable::new(&document.base, document.timestamp)?;
+        for (code, rate) in document.rates {
+            table.set_rate(&code, ExchangeRate { rate, timestamp: document.timestamp })?;
+        }
+        Ok(table)
+    }
+
+    /// Returns the code of the base currency
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Returns the rate of a currency, which is 1 for the base currency itself
+    pub fn rate(&self, code: &str) -> Option<ExchangeRate> {
+        if code == self.base {
+            return Some(ExchangeRate { rate: 1.0, timestamp: self.timestamp });
+        }
+        self.rates.get(code).copied()
+    }
+
+    /// Returns the codes of every currency in the table, the base currency first
+    pub fn currencies(&self) -> Vec<String> {
+        let mut codes: Vec<String> = self.rates.keys().cloned().collect();
+        codes.sort();
+        codes.insert(0, self.base.clone());
+        codes
+    }
+
+    /// Sets the rate of a currency, replacing any rate it had
+    pub fn set_rate(&mut self, code: &str, rate: ExchangeRate) -> Result<(), ApiError> {
+        let code = check_code(code)?;
+
+        if !rate.rate.is_finite() || rate.rate <= 0.0 {
+            return Err(ApiError::BadRequest(format!(
+                "Exchange rate for '{}' must be a positive number", code
+            )));
+        }
+
+        if code == self.base {
+            if rate.rate != 1.0 {
+                return Err(ApiError::BadRequest(format!(
+                    "Exchange rate for the base currency '{}' must be 1", code
+                )));
+            }
+            return Ok(());
+        }
+
+        self.rates.insert(code, rate);
+        Ok(())
+    }
+}
+
+/// Checks that a currency code is three ASCII letters, and returns it in upper case
+fn check_code(code: &str) -> Result<String, ApiError> {
+    let code = code.trim();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(ApiError::BadRequest(format!(
+            "Invalid currency code '{}': expected a three-letter ISO 4217 code", code
+        )));
+    }
+    Ok(code.to_ascii_uppercase())
+}
+
+/// New rates for an updatable provider
+///
+/// Every rate is quoted at `timestamp`, which defaults to the time of the update.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RateUpdate {
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub timestamp: Option<DateTime<Utc>>,
+    pub rates: HashMap<String, f64>,
+}
+
+/// A source of exchange rates for the currency category
+///
+/// Currency units read their rates from the installed provider each time they
+/// convert, so rates can change without re-registering the units.
+pub trait RateProvider: Send + Sync {
+    /// Describes where the rates come from, for messages
+    fn source(&self) -> String;
+
+    /// Returns the code of the base currency rates are quoted against
+    fn base(&self) -> String;
+
+    /// Returns the rate of a currency, or None if the provider doesn't have one
+    fn rate(&self, code: &str) -> Option<ExchangeRate>;
+
+    /// Returns the codes of every currency the provider has a rate for
+    fn currencies(&self) -> Vec<String>;
+
+    /// Replaces rates, for providers whose rates can be updated
+    fn update(&self, _update: RateUpdate) -> Result<(), ApiError> {
+        Err(ApiError::BadRequest(format!(
+            "Exchange rates from {} can't be updated", self.source()
+        )))
+    }
+}
+
+/// Exchange rates loaded once from a local CSV or JSON file
+#[derive(Debug, Clone)]
+pub struct FileRateProvider {
+    path: PathBuf,
+    table: RateTable,
+}
+
+impl FileRateProvider {
+    /// Loads rates from a file, choosing the format by its extension
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ApiError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|err| ApiError::InternalError(format!(
+            "Failed to read exchange rates from '{}': {}", path.display(), err
+        )))?;
+
+        let table = match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => RateTable::from_csv(&text)?,
+            Some(ext) if ext.eq_ignore_ascii_case("json") => RateTable::from_json(&text)?,
+            _ => return Err(ApiError::BadRequest(format!(
+                "Exchange rate file '{}' must be a .csv or .json file", path.display()
+            ))),
+        };
+
+        Ok(Self { path: path.to_path_buf(), table })
+    }
+}
+
+impl RateProvider for FileRateProvider {
+    fn source(&self) -> String {
+        format!("file '{}'", self.path.display())
+    }
+
+    fn base(&self) -> String {
+        self.table.base().to_string()
+    }
+
+    fn rate(&self, code: &str) -> Option<ExchangeRate> {
+        self.table.rate(code)
+    }
+
+    fn currencies(&self) -> Vec<String> {
+        self.table.currencies()
+    }
+}
+
+/// Exchange rates held in memory and updated through the admin API
+#[derive(Debug)]
+pub struct MemoryRateProvider {
+    table: RwLock<RateTable>,
+}
+
+impl MemoryRateProvider {
+    /// Creates a provider with no rates besides its base currency
+    pub fn new(base: &str) -> Result<Self, ApiError> {
+        Ok(Self::with_rates(RateTable::new(base, Utc::now())?))
+    }
+
+    /// Creates a provider starting from an existing table of rates
+    pub fn with_rates(table: RateTable) -> Self {
+        Self { table: RwLock::new(table) }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, RateTable> {
+        // A panic while updating can't leave the table half-written, so a poisoned lock is still usable
+        self.table.read().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl RateProvider for MemoryRateProvider {
+    fn source(&self) -> String {
+        "memory".to_string()
+    }
+
+    fn base(&self) -> String {
+        self.read().base().to_string()
+    }
+
+    fn rate(&self, code: &str) -> Option<ExchangeRate> {
+        self.read().rate(code)
+    }
+
+    fn currencies(&self) -> Vec<String> {
+        self.read().currencies()
+    }
+
+    /// Applies every rate of the update or, if any is invalid, none of them
+    fn update(&self, update: RateUpdate) -> Result<(), ApiError> {
+        let mut table = self.table.write().unwrap_or_else(|err| err.into_inner());
+
+        if let Some(base) = &update.base {
+            if !base.trim().eq_ignore_ascii_case(table.base()) {
+                return Err(ApiError::BadRequest(format!(
+                    "Exchange rates must be quoted against '{}', not '{}'", table.base(), base
+                )));
+            }
+        }
+
+        let timestamp = update.timestamp.unwrap_or_else(Utc::now);
+        let mut updated = table.clone();
+        updated.timestamp = updated.timestamp.max(timestamp);
+        for (code, rate) in &update.rates {
+            updated.set_rate(code, ExchangeRate { rate: *rate, timestamp })?;
+        }
+
+        *table = updated;
+        Ok(())
+    }
+}
+
+// The installed provider, which starts out empty and quoted against the US dollar
+static PROVIDER: Lazy<RwLock<Arc<dyn RateProvider>>> = Lazy::new(|| {
+    RwLock::new(Arc::new(MemoryRateProvider::new("USD").expect("USD is a valid currency code")))
+});
+
+/// Get the exchange rate provider currency units convert with
+pub fn rate_provider() -> Arc<dyn RateProvider> {
+    PROVIDER.read().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+/// Install the exchange rate provider currency units convert with
+///
+/// Currencies the new provider has that aren't registered yet need registering
+/// with `units::currency::register_currencies`.
+pub fn set_rate_provider(provider: Arc<dyn RateProvider>) {
+    *PROVIDER.write().unwrap_or_else(|err| err.into_inner()) = provider;
+}
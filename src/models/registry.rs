@@ -1,9 +1,19 @@
 use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::RwLock;
 use std::any::Any;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
 use crate::models::unit::{Unit, UnitCategory};
-use crate::errors::ApiError;
+use crate::errors::{ApiError, quoted_list};
+use crate::models::dimension::Dimension;
+use crate::models::alias::generate_aliases;
+use crate::models::exact::{self, LinearConversion};
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use crate::models::defined::{intern, DefinedUnit, DefinedCategory};
+use bigdecimal::BigDecimal;
 
 /// Trait object for units with type erasure
 pub trait AnyUnit: Any + Send + Sync {
@@ -11,8 +21,17 @@ pub trait AnyUnit: Any + Send + Sync {
     fn name(&self) -> &'static str;
     fn symbol(&self) -> &'static str;
     fn category_name(&self) -> &'static str;
+    fn dimension(&self) -> Option<Dimension>;
     fn to_base(&self, value: f64) -> f64;
+    #[allow(clippy::wrong_self_convention)]
     fn from_base(&self, value: f64) -> f64;
+    fn exact(&self) -> Option<&LinearConversion>;
+    fn to_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal>;
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal>;
+    fn prefixes(&self) -> Prefixes;
+    fn systems(&self) -> &'static [UnitSystem];
+    fn description(&self) -> Option<&'static str>;
 }
 
 impl<T: Unit + 'static> AnyUnit for T {
@@ -32,6 +51,10 @@ impl<T: Unit + 'static> AnyUnit for T {
         self.category().name()
     }
     
+    fn dimension(&self) -> Option<Dimension> {
+        self.category().dimension()
+    }
+    
     fn to_base(&self, value: f64) -> f64 {
         self.to_base(value)
     }
@@ -39,6 +62,119 @@ impl<T: Unit + 'static> AnyUnit for T {
     fn from_base(&self, value: f64) -> f64 {
         self.from_base(value)
     }
+    
+    fn exact(&self) -> Option<&LinearConversion> {
+        self.exact()
+    }
+    
+    fn to_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        self.to_base_decimal(value)
+    }
+    
+    fn from_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        self.from_base_decimal(value)
+    }
+    
+    fn prefixes(&self) -> Prefixes {
+        self.prefixes()
+    }
+    
+    fn systems(&self) -> &'static [UnitSystem] {
+        self.systems()
+    }
+    
+    fn description(&self) -> Option<&'static str> {
+        self.description()
+    }
+}
+
+/// Order in which units and categories are listed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingOrder {
+    /// The order they were registered in, built-in ones first
+    #[default]
+    Registration,
+    /// Smallest to largest, by the size of one unit in the base unit
+    Factor,
+    /// Alphabetically, by symbol for units and by name for categories
+    Name,
+}
+
+impl FromStr for ListingOrder {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "registration" => Ok(ListingOrder::Registration),
+            "factor" | "magnitude" => Ok(ListingOrder::Factor),
+            "name" | "alphabetical" => Ok(ListingOrder::Name),
+            _ => Err(ApiError::BadRequest(format!(
+                "Unknown listing order '{}', expected 'registration', 'factor' or 'name'", s
+            ))),
+        }
+    }
+}
+
+/// How a unit's symbol, name or alias matched a search
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    /// The term is the query, ignoring case
+    Exact,
+    /// The term starts with the query, ignoring case
+    Prefix,
+    /// The term is within a few edits of the query
+    Fuzzy,
+}
+
+/// A unit found by `UnitRegistry::search_units`
+pub struct UnitMatch<'a> {
+    pub unit: &'a dyn AnyUnit,
+    /// The symbol, name or alias that matched
+    pub term: &'a str,
+    pub kind: MatchKind,
+    /// Edits between the query and the term, or for prefix matches the
+    /// number of characters the term adds to the query
+    pub distance: usize,
+}
+
+impl<'a> UnitMatch<'a> {
+    /// Orders matches best first, with ties going to terms that start with the
+    /// query in its own case, then to shorter terms
+    fn rank(&self, query: &str) -> (MatchKind, usize, bool, usize, &'a str) {
+        (self.kind, self.distance, !self.term.starts_with(query), self.term.chars().count(), self.term)
+    }
+}
+
+/// Most suggestions given for a unit that isn't registered
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Most edits a fuzzy match may be from a query of the given length, so that
+/// short queries such as "mm" don't match half the registry
+fn max_edits(length: usize) -> usize {
+    match length {
+        0..=2 => 0,
+        3..=5 => 1,
+        6..=9 => 2,
+        _ => 3,
+    }
+}
+
+/// Matches a term against a lowercased query, returning how and how closely
+fn match_term(query: &str, term: &str, max_edits: usize) -> Option<(MatchKind, usize)> {
+    let folded = term.to_lowercase();
+    if folded == query {
+        return Some((MatchKind::Exact, 0));
+    }
+    if folded.starts_with(query) {
+        return Some((MatchKind::Prefix, folded.chars().count() - query.chars().count()));
+    }
+    if max_edits == 0 || folded.chars().count().abs_diff(query.chars().count()) > max_edits {
+        return None;
+    }
+    let distance = strsim::osa_distance(query, &folded);
+    (distance <= max_edits).then_some((MatchKind::Fuzzy, distance))
 }
 
 /// Registry for storing and accessing available unit categories and units
@@ -49,8 +185,45 @@ pub struct UnitRegistry {
     // Maps (category_name, unit_symbol) to the unit implementation
     units: HashMap<(&'static str, &'static str), Box<dyn AnyUnit>>,
     
-    // Maps category names to the set of unit symbols in that category
-    category_units: HashMap<&'static str, HashSet<&'static str>>,
+    // Category names in the order they were registered
+    category_order: Vec<&'static str>,
+    
+    // Maps category names to the symbols of their units, in the order they were registered
+    category_units: HashMap<&'static str, Vec<&'static str>>,
+    
+    // Maps unit symbols to the categories that define them
+    symbol_categories: HashMap<&'static str, Vec<&'static str>>,
+    
+    // Maps aliases (names, plurals, alternate spellings) to the (category_name, unit_symbol)
+    // pairs they refer to
+    aliases: HashMap<String, Vec<(&'static str, &'static str)>>,
+    
+    // Maps lowercased symbols and aliases to the units they refer to, for case-insensitive lookup
+    folded: HashMap<String, Vec<(&'static str, &'static str)>>,
+    
+    // Units whose symbols carry an SI or binary prefix, generated or written out,
+    // as the case of a prefix matters when looking them up ignoring case
+    prefixed: HashSet<(&'static str, &'static str)>,
+    
+    // Units and categories registered at runtime, which unlike built-in ones can be removed
+    custom_units: HashSet<(&'static str, &'static str)>,
+    custom_categories: HashSet<&'static str>,
+    
+    // Every symbol and alias, longest first, built when first needed and
+    // dropped whenever units or aliases change
+    terms_by_length: OnceCell<Vec<String>>,
+}
+
+/// Adds a unit to the entry for a lookup key, unless it's already there
+fn add_lookup(
+    index: &mut HashMap<String, Vec<(&'static str, &'static str)>>,
+    key: String,
+    unit: (&'static str, &'static str),
+) {
+    let entry = index.entry(key).or_default();
+    if !entry.contains(&unit) {
+        entry.push(unit);
+    }
 }
 
 impl UnitRegistry {
@@ -59,7 +232,15 @@ impl UnitRegistry {
         Self {
             categories: HashMap::new(),
             units: HashMap::new(),
+            category_order: Vec::new(),
             category_units: HashMap::new(),
+            symbol_categories: HashMap::new(),
+            aliases: HashMap::new(),
+            folded: HashMap::new(),
+            prefixed: HashSet::new(),
+            custom_units: HashSet::new(),
+            custom_categories: HashSet::new(),
+            terms_by_length: OnceCell::new(),
         }
     }
     
@@ -72,14 +253,23 @@ impl UnitRegistry {
         }
         
         self.categories.insert(name, Box::new(category));
-        self.category_units.insert(name, HashSet::new());
+        self.category_order.push(name);
+        self.category_units.insert(name, Vec::new());
         
         Ok(())
     }
     
     /// Register a unit
     pub fn register_unit<U: Unit + 'static>(&mut self, unit: U) -> Result<(), ApiError> {
-        let category_name = unit.category().name();
+        self.insert_unit(Box::new(unit), false)
+    }
+    
+    /// Adds a unit to the registry and indexes it for lookup
+    ///
+    /// Generated units aren't indexed under their lowercased symbol, as the
+    /// case of a prefix matters: see `find_matches`.
+    fn insert_unit(&mut self, unit: Box<dyn AnyUnit>, generated: bool) -> Result<(), ApiError> {
+        let category_name = unit.category_name();
         let symbol = unit.symbol();
         
         // Ensure the category exists
@@ -98,21 +288,285 @@ impl UnitRegistry {
             )));
         }
         
+        // Index the unit under its generated aliases
+        let aliases = generate_aliases(unit.name(), symbol);
+        self.terms_by_length.take();
+        
         // Register the unit
-        self.units.insert((category_name, symbol), Box::new(unit));
+        self.units.insert((category_name, symbol), unit);
         
         // Add to the category's units
         if let Some(units) = self.category_units.get_mut(category_name) {
-            units.insert(symbol);
+            units.push(symbol);
+        }
+        
+        self.symbol_categories.entry(symbol).or_default().push(category_name);
+        if !generated {
+            add_lookup(&mut self.folded, symbol.to_lowercase(), (category_name, symbol));
+        }
+        
+        for alias in aliases {
+            add_lookup(&mut self.folded, alias.to_lowercase(), (category_name, symbol));
+            add_lookup(&mut self.aliases, alias, (category_name, symbol));
+        }
+        
+        Ok(())
+    }
+    
+    /// Generate and register a prefixed unit for every prefix a unit accepts,
+    /// skipping symbols the category already has, such as a hand-written km
+    ///
+    /// Returns the number of units generated.
+    pub fn register_prefixes(&mut self, category_name: &str, symbol: &str) -> Result<usize, ApiError> {
+        let unit = self.unit_key(category_name, symbol)
+            .and_then(|key| self.units.get(&key))
+            .map(|unit| unit.as_ref())
+            .ok_or_else(|| ApiError::NotFound(format!(
+                "Unit '{}' not found in category '{}'", symbol, category_name
+            )))?;
+        
+        // Prefixes only make sense for units that scale from zero
+        let conversion = match unit.exact() {
+            Some(conversion) if !conversion.has_offset() => conversion.clone(),
+            _ => return Ok(0),
+        };
+        
+        let category = unit.category_name();
+        let dimension = unit.dimension();
+        let systems = unit.systems();
+        let (written, missing): (Vec<_>, Vec<_>) = unit.prefixes().list().into_iter()
+            .map(|prefix| (format!("{}{}", prefix.symbol, symbol), prefix))
+            .partition(|(prefixed, _)| self.unit_key(category, prefixed).is_some());
+        
+        let generated: Vec<DefinedUnit> = missing.into_iter()
+            .map(|(_, prefix)| {
+                let factor = exact::parse_rational(prefix.factor)
+                    .expect("prefix factors are valid decimals");
+                
+                let name = intern(&format!("{}{}", prefix.name, unit.name()));
+                let symbol = intern(&format!("{}{}", prefix.symbol, symbol));
+                
+                DefinedUnit::new(name, symbol, category, dimension, LinearConversion::scale(factor * conversion.factor()))
+                    .with_systems(systems)
+            })
+            .collect();
+        
+        // Prefixed units written out, such as km, are only found by their exact symbol too
+        for (prefixed, _) in written {
+            if let Some(key) = self.unit_key(category, &prefixed) {
+                self.prefixed.insert(key);
+            }
+        }
+        
+        let count = generated.len();
+        for unit in generated {
+            let key = (unit.category_name(), unit.symbol());
+            self.insert_unit(Box::new(unit), true)?;
+            self.prefixed.insert(key);
+        }
+        
+        Ok(count)
+    }
+    
+    /// Generate prefixed units for every registered unit that accepts prefixes
+    ///
+    /// Returns the number of units generated.
+    pub fn register_prefixed_units(&mut self) -> Result<usize, ApiError> {
+        let mut prefixable: Vec<(&'static str, &'static str)> = self.units.iter()
+            .filter(|(_, unit)| unit.prefixes() != Prefixes::None)
+            .map(|(&key, _)| key)
+            .collect();
+        prefixable.sort();
+        
+        let mut count = 0;
+        for (category_name, symbol) in prefixable {
+            count += self.register_prefixes(category_name, symbol)?;
+        }
+        Ok(count)
+    }
+    
+    /// Register a category defined at runtime, which can later be removed
+    pub fn register_custom_category(&mut self, category: DefinedCategory) -> Result<(), ApiError> {
+        let name = category.name();
+        self.register_category(category)?;
+        self.custom_categories.insert(name);
+        Ok(())
+    }
+    
+    /// Register a unit defined at runtime, which can later be removed
+    pub fn register_custom_unit(&mut self, unit: DefinedUnit) -> Result<(), ApiError> {
+        let key = (unit.category_name(), unit.symbol());
+        self.insert_unit(Box::new(unit), false)?;
+        self.custom_units.insert(key);
+        Ok(())
+    }
+    
+    /// Register a unit loaded from a definition file, which like a built-in
+    /// unit can't be removed
+    pub fn register_defined_unit(&mut self, unit: DefinedUnit) -> Result<(), ApiError> {
+        self.insert_unit(Box::new(unit), false)
+    }
+
+    /// Replace a registered unit with a new definition of the same symbol
+    ///
+    /// The new definition takes the old one's place in registration order.
+    /// Aliases registered for the old unit are dropped along with it. Units
+    /// already generated from it by prefixing keep the old definition.
+    pub fn replace_unit(&mut self, unit: DefinedUnit) -> Result<(), ApiError> {
+        let key = self.unit_key(unit.category_name(), unit.symbol())
+            .ok_or_else(|| ApiError::NotFound(format!(
+                "Unit '{}' not found in category '{}'", unit.symbol(), unit.category_name()
+            )))?;
+        let position = self.category_units[key.0].iter().position(|&s| s == key.1);
+
+        self.remove_unit(key);
+        self.insert_unit(Box::new(unit), false)?;
+        
+        if let (Some(position), Some(units)) = (position, self.category_units.get_mut(key.0)) {
+            if let Some(symbol) = units.pop() {
+                units.insert(position, symbol);
+            }
+        }
+        Ok(())
+    }
+    
+    /// Returns the registry's own copies of a category name and unit symbol
+    fn unit_key(&self, category_name: &str, symbol: &str) -> Option<(&'static str, &'static str)> {
+        self.category_units.get_key_value(category_name)
+            .and_then(|(&category, symbols)| symbols.iter()
+                .find(|&&s| s == symbol)
+                .map(|&symbol| (category, symbol)))
+    }
+
+    /// Returns true if a category has a unit with the given symbol
+    pub fn has_unit(&self, category_name: &str, symbol: &str) -> bool {
+        self.category_units.get(category_name).is_some_and(|symbols| symbols.contains(&symbol))
+    }
+    
+    /// Returns the number of units registered at runtime
+    pub fn custom_unit_count(&self) -> usize {
+        self.custom_units.len()
+    }
+
+    /// Returns a registered unit, in any category, that a new unit with the
+    /// given name and symbol would be confused with
+    ///
+    /// The unit's symbol and the aliases generated from its name are checked
+    /// against symbols, aliases and their lowercased forms, so defining "m" or
+    /// "Meter" anywhere clashes with the meter.
+    pub fn find_clash(&self, name: &str, symbol: &str) -> Option<&dyn AnyUnit> {
+        std::iter::once(symbol.to_string())
+            .chain(generate_aliases(name, symbol))
+            .find_map(|term| self.find_matches(&term, None).into_iter().next())
+            .or_else(|| self.folded.get(&symbol.to_lowercase())
+                .and_then(|units| units.first())
+                .and_then(|&(category, symbol)| self.get_unit(category, symbol)))
+    }
+
+    /// Returns true if a category was registered at runtime
+    pub fn is_custom_category(&self, name: &str) -> bool {
+        self.custom_categories.contains(name)
+    }
+    
+    /// Returns true if a unit was registered at runtime
+    pub fn is_custom_unit(&self, category_name: &str, symbol: &str) -> bool {
+        self.custom_units.iter().any(|&(c, s)| c == category_name && s == symbol)
+    }
+    
+    /// Remove a unit registered at runtime, along with its aliases
+    ///
+    /// Built-in units can't be removed. The unit's name and symbol stay allocated,
+    /// as the registry's strings live for the whole program.
+    pub fn unregister_unit(&mut self, category_name: &str, symbol: &str) -> Result<(), ApiError> {
+        let key = self.unit_key(category_name, symbol)
+            .ok_or_else(|| ApiError::NotFound(format!(
+                "Unit '{}' not found in category '{}'", symbol, category_name
+            )))?;
+        
+        if !self.custom_units.contains(&key) {
+            return Err(ApiError::BadRequest(format!(
+                "Unit '{}' in category '{}' is built in and can't be removed", symbol, category_name
+            )));
+        }
+        
+        self.remove_unit(key);
+        Ok(())
+    }
+    
+    /// Remove a category registered at runtime, along with all of its units
+    ///
+    /// Returns the number of units removed.
+    pub fn unregister_category(&mut self, name: &str) -> Result<usize, ApiError> {
+        let category = self.categories.get_key_value(name)
+            .map(|(&category, _)| category)
+            .ok_or_else(|| ApiError::NotFound(format!("Category '{}' not found", name)))?;
+        
+        if !self.custom_categories.contains(category) {
+            return Err(ApiError::BadRequest(format!(
+                "Category '{}' is built in and can't be removed", name
+            )));
+        }
+        
+        let symbols = self.category_units.remove(category).unwrap_or_default();
+        for &symbol in &symbols {
+            self.remove_unit((category, symbol));
+        }
+        
+        self.categories.remove(category);
+        self.category_order.retain(|&c| c != category);
+        self.custom_categories.remove(category);
+        Ok(symbols.len())
+    }
+    
+    /// Removes a unit from every index
+    fn remove_unit(&mut self, key: (&'static str, &'static str)) {
+        let (category_name, symbol) = key;
+        
+        self.units.remove(&key);
+        self.custom_units.remove(&key);
+        self.prefixed.remove(&key);
+        self.terms_by_length.take();
+        
+        if let Some(units) = self.category_units.get_mut(category_name) {
+            units.retain(|&s| s != symbol);
         }
         
+        if let Some(categories) = self.symbol_categories.get_mut(symbol) {
+            categories.retain(|&c| c != category_name);
+            if categories.is_empty() {
+                self.symbol_categories.remove(symbol);
+            }
+        }
+        
+        for index in [&mut self.aliases, &mut self.folded] {
+            index.retain(|_, units| {
+                units.retain(|&unit| unit != key);
+                !units.is_empty()
+            });
+        }
+    }
+    
+    /// Register an additional alias for a unit, such as "lbs" for the pound
+    pub fn register_alias(&mut self, category_name: &str, symbol: &str, alias: &str) -> Result<(), ApiError> {
+        let unit = self.symbol_categories.get_key_value(symbol)
+            .and_then(|(&symbol, categories)| categories.iter()
+                .find(|&&c| c == category_name)
+                .map(|&c| (c, symbol)))
+            .ok_or_else(|| ApiError::NotFound(format!(
+                "Unit '{}' not found in category '{}'", symbol, category_name
+            )))?;
+        
+        add_lookup(&mut self.folded, alias.to_lowercase(), unit);
+        add_lookup(&mut self.aliases, alias.to_string(), unit);
+        self.terms_by_length.take();
+        
         Ok(())
     }
     
-    /// Get all registered categories
+    /// Get all registered categories, in the order they were registered
     pub fn get_categories(&self) -> Vec<&dyn UnitCategory> {
-        self.categories.values()
-            .map(|c| c.as_ref())
+        self.category_order.iter()
+            .filter_map(|&name| self.get_category(name))
             .collect()
     }
     
@@ -121,7 +575,7 @@ impl UnitRegistry {
         self.categories.get(name).map(|c| c.as_ref())
     }
     
-    /// Get all units for a specific category
+    /// Get all units for a specific category, in the order they were registered
     /// Note: we're using 'static as a workaround for the registry keys which are static,
     /// but this isn't restrictive for how the API is used.
     pub fn get_units_for_category(&self, category_name: &'static str) -> Vec<&dyn AnyUnit> {
@@ -144,6 +598,275 @@ impl UnitRegistry {
     pub fn get_unit(&self, category_name: &'static str, symbol: &'static str) -> Option<&dyn AnyUnit> {
         self.units.get(&(category_name, symbol)).map(|u| u.as_ref())
     }
+    
+    /// Find all units matching a symbol, name or alias, across every category
+    ///
+    /// Exact symbols take precedence over aliases, and exact-case matches over
+    /// case-insensitive ones, so "m" is the meter and "KN" matches both the
+    /// knot and the kilonewton. A symbol with capitals also matches units of
+    /// other categories that differ only in case, so "M" is mach or the meter.
+    pub fn find_units(&self, term: &str) -> Vec<&dyn AnyUnit> {
+        self.find_matches(term, None)
+    }
+    
+    /// Find all units in a category matching a symbol, name or alias
+    pub fn find_units_in_category(&self, category_name: &str, term: &str) -> Vec<&dyn AnyUnit> {
+        self.find_matches(term, Some(category_name))
+    }
+    
+    fn find_matches(&self, term: &str, category_name: Option<&str>) -> Vec<&dyn AnyUnit> {
+        let term = term.trim();
+        
+        let mut symbols: Vec<(&'static str, &'static str)> = self.symbol_categories.get_key_value(term)
+            .map(|(&symbol, categories)| categories.iter().map(|&c| (c, symbol)).collect())
+            .unwrap_or_default();
+        let aliases = self.aliases.get(term).cloned().unwrap_or_default();
+        let lowercase = term.to_lowercase();
+        let mut folded = self.folded.get(&lowercase).cloned().unwrap_or_default();
+        
+        // A symbol written with capitals may also be a miscapitalized unit of
+        // another category, as "M" is mach but also the meter in capitals
+        if !symbols.is_empty() && term.chars().any(char::is_uppercase) {
+            let others: Vec<_> = folded.iter()
+                .filter(|&&(category, _)| !symbols.iter().any(|&(c, _)| c == category))
+                .copied()
+                .collect();
+            symbols.extend(others);
+        }
+        
+        // Prefixed symbols differing only in case, as mW and MW do, are different
+        // units, so a term matching several of them ignoring case is ambiguous, as
+        // is one also matching another unit, as "KN" does the knot, but one
+        // matching a single prefixed unit, such as "gw", isn't taken to mean it
+        folded.retain(|unit| !(self.prefixed.contains(unit) && unit.1.to_lowercase() == lowercase));
+        let prefixed = self.prefixed_variants(term, category_name);
+        if !folded.is_empty() || prefixed.len() > 1 {
+            folded.extend(prefixed);
+        }
+        
+        for tier in [symbols, aliases, folded] {
+            let units: Vec<&dyn AnyUnit> = tier.into_iter()
+                .filter(|&(category, _)| category_name.is_none_or(|name| name == category))
+                .filter_map(|(category, symbol)| self.get_unit(category, symbol))
+                .collect();
+            
+            if !units.is_empty() {
+                return units;
+            }
+        }
+        
+        Vec::new()
+    }
+    
+    /// Finds the prefixed units whose symbols match a term ignoring case
+    fn prefixed_variants(&self, term: &str, category_name: Option<&str>) -> Vec<(&'static str, &'static str)> {
+        let folded = term.trim().to_lowercase();
+        let mut variants: Vec<(&'static str, &'static str)> = self.prefixed.iter()
+            .filter(|&&(category, symbol)| category_name.is_none_or(|name| name == category)
+                && symbol.to_lowercase() == folded)
+            .copied()
+            .collect();
+        variants.sort_unstable();
+        variants
+    }
+    
+    /// Resolve a symbol, name or alias to a single unit, optionally within a category
+    ///
+    /// Fails with `UnitNotFound` if nothing matches, suggesting close matches,
+    /// or `Ambiguous` listing the candidates if several units do.
+    pub fn resolve_unit(&self, term: &str, category_name: Option<&str>) -> Result<&dyn AnyUnit, ApiError> {
+        self.lookup_unit(term, category_name)?
+            .ok_or_else(|| self.unit_not_found(term, category_name))
+    }
+    
+    /// Resolve a symbol, name or alias like `resolve_unit`, but give `None` if
+    /// nothing matches rather than looking for suggestions, for callers that
+    /// expect to miss
+    pub fn lookup_unit(&self, term: &str, category_name: Option<&str>) -> Result<Option<&dyn AnyUnit>, ApiError> {
+        let units = self.find_matches(term, category_name);
+        
+        match units.as_slice() {
+            [unit] => Ok(Some(*unit)),
+            [] => Ok(None),
+            _ => {
+                let candidates: Vec<String> = units.iter()
+                    .map(|u| format!("{} ({}, {})", u.symbol(), u.name(), u.category_name()))
+                    .collect();
+                
+                Err(ApiError::Ambiguous {
+                    message: format!("Unit '{}' could refer to: {}", term, candidates.join("; ")),
+                    candidates,
+                })
+            }
+        }
+    }
+    
+    /// Work out the category two units share, for requests that don't name one
+    ///
+    /// Each term is looked up across every category, so a symbol that is
+    /// ambiguous on its own, such as "KN", is settled by the other unit. Gives
+    /// `None` if either term isn't a registered unit or the two share no
+    /// category, and fails with `Ambiguous` listing the categories if they share
+    /// several.
+    pub fn infer_category(&self, first: &str, second: &str) -> Result<Option<&'static str>, ApiError> {
+        let categories = |term: &str| -> Vec<&'static str> {
+            let mut categories: Vec<&'static str> = self.find_units(term).iter()
+                .map(|u| u.category_name())
+                .collect();
+            categories.sort_unstable();
+            categories.dedup();
+            categories
+        };
+        let second_categories = categories(second);
+        let shared: Vec<&'static str> = categories(first).into_iter()
+            .filter(|category| second_categories.contains(category))
+            .collect();
+        
+        match shared.as_slice() {
+            [] => Ok(None),
+            [category] => Ok(Some(category)),
+            _ => Err(ApiError::Ambiguous {
+                message: if first.trim() == second.trim() {
+                    format!("Unit '{}' is in categories {}; give a category to choose", first.trim(), quoted_list(&shared, "and"))
+                } else {
+                    format!(
+                        "Units '{}' and '{}' are both in categories {}; give a category to choose",
+                        first.trim(), second.trim(), quoted_list(&shared, "and")
+                    )
+                },
+                candidates: shared.iter().map(|category| category.to_string()).collect(),
+            }),
+        }
+    }
+    
+    /// Builds the error for a term that matched no unit, saying which categories
+    /// have it when it was looked for in another, and suggesting close matches
+    ///
+    /// Looking in a category that isn't registered is `NotFound` instead.
+    pub fn unit_not_found(&self, term: &str, category_name: Option<&str>) -> ApiError {
+        let term = term.trim();
+        if let Some(category) = category_name.filter(|&name| self.get_category(name).is_none()) {
+            return ApiError::NotFound(format!("Category '{}' not found", category));
+        }
+        let suggestions = self.suggest_units(term, category_name);
+        
+        if let Some(category) = category_name {
+            let mut elsewhere: Vec<&str> = self.find_units(term).iter().map(|u| u.category_name()).collect();
+            elsewhere.sort_unstable();
+            elsewhere.dedup();
+            
+            return ApiError::unit_not_found(match elsewhere.as_slice() {
+                [] => format!("Unit '{}' not found in category '{}'", term, category),
+                [other] => format!("Unit '{}' is in category '{}', not '{}'", term, other, category),
+                others => format!(
+                    "Unit '{}' is in categories {}, not '{}'", term, quoted_list(others, "and"), category
+                ),
+            }, suggestions);
+        }
+        
+        ApiError::unit_not_found(format!("Unit '{}' not found", term), suggestions)
+    }
+    
+    /// Suggest the symbols, names and aliases closest to a term, best first
+    pub fn suggest_units(&self, term: &str, category_name: Option<&str>) -> Vec<String> {
+        let mut suggestions: Vec<String> = Vec::new();
+        for m in self.search_units(term, category_name) {
+            if suggestions.len() == MAX_SUGGESTIONS {
+                break;
+            }
+            if !suggestions.iter().any(|s| s == m.term) {
+                suggestions.push(m.term.to_string());
+            }
+        }
+        suggestions
+    }
+    
+    /// Search units by symbol, name and alias, optionally within a category,
+    /// best matches first
+    ///
+    /// Case is ignored. Exact matches come first, then terms that start with the
+    /// query, shortest first, then terms a few edits away, such as "kilometer"
+    /// for "kilometre", closest first. Each unit appears once, under its best term.
+    pub fn search_units(&self, query: &str, category_name: Option<&str>) -> Vec<UnitMatch<'_>> {
+        let query = query.trim();
+        let folded = query.to_lowercase();
+        if folded.is_empty() {
+            return Vec::new();
+        }
+        let max_edits = max_edits(folded.chars().count());
+        
+        let terms = self.units.iter()
+            .flat_map(|(&key, unit)| [(unit.symbol(), key), (unit.name(), key)])
+            .chain(self.aliases.iter()
+                .flat_map(|(alias, units)| units.iter().map(move |&key| (alias.as_str(), key))));
+        
+        let mut best: HashMap<(&'static str, &'static str), UnitMatch> = HashMap::new();
+        for (term, key) in terms {
+            if category_name.is_some_and(|name| name != key.0) {
+                continue;
+            }
+            let Some((kind, distance)) = match_term(&folded, term, max_edits) else {
+                continue;
+            };
+            let candidate = UnitMatch { unit: self.units[&key].as_ref(), term, kind, distance };
+            if best.get(&key).is_none_or(|current| candidate.rank(query) < current.rank(query)) {
+                best.insert(key, candidate);
+            }
+        }
+        
+        let mut matches: Vec<UnitMatch> = best.into_values().collect();
+        matches.sort_by(|a, b| a.rank(query).cmp(&b.rank(query))
+            .then_with(|| a.unit.symbol().cmp(b.unit.symbol()))
+            .then_with(|| a.unit.category_name().cmp(b.unit.category_name())));
+        matches
+    }
+    
+    /// Get the aliases of every unit in a category, sorted and keyed by symbol
+    pub fn aliases_in_category(&self, category_name: &str) -> HashMap<&'static str, Vec<&str>> {
+        let mut result: HashMap<&'static str, Vec<&str>> = HashMap::new();
+        for (alias, units) in &self.aliases {
+            for &(category, symbol) in units {
+                if category == category_name && alias != symbol {
+                    result.entry(symbol).or_default().push(alias);
+                }
+            }
+        }
+        for aliases in result.values_mut() {
+            aliases.sort_unstable();
+        }
+        result
+    }
+    
+    /// Get every symbol and alias units can be looked up by
+    pub fn lookup_terms(&self) -> Vec<&str> {
+        self.symbol_categories.keys()
+            .copied()
+            .chain(self.aliases.keys().map(|a| a.as_str()))
+            .collect()
+    }
+    
+    /// Get every symbol and alias units can be looked up by, longest first,
+    /// for matching the longest one at a position in a unit expression
+    pub fn terms_by_length(&self) -> &[String] {
+        self.terms_by_length.get_or_init(|| {
+            let mut terms: Vec<String> = self.lookup_terms().into_iter().map(str::to_string).collect();
+            terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+            terms
+        })
+    }
+    
+    /// Find a category whose units have the given dimension
+    pub fn category_for_dimension(&self, dimension: Dimension) -> Option<&dyn UnitCategory> {
+        self.get_categories()
+            .into_iter()
+            .find(|c| c.dimension() == Some(dimension))
+    }
+}
+
+impl Default for UnitRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // Global registry instance
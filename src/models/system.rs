@@ -0,0 +1,20 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+/// A system of measurement a unit belongs to
+///
+/// Metric covers units accepted alongside the SI, such as the liter and the
+/// hour, and many units of length and mass are shared by the imperial and US
+/// customary systems. Units of neither, such as the nautical mile, belong to none.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    /// The International System of Units
+    Si,
+    /// Metric units outside the SI
+    Metric,
+    /// The British imperial system
+    Imperial,
+    /// United States customary units
+    UsCustomary,
+}
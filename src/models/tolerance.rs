@@ -0,0 +1,78 @@
+use crate::errors::ApiError;
+
+/// ULPs two values may differ by and still be equal when no tolerance is given,
+/// enough to absorb the rounding of a conversion or two
+pub const DEFAULT_ULPS: u64 = 4;
+
+/// How far apart two values may be and still count as equal
+///
+/// Values are equal when they're within any of the three bounds: `absolute`,
+/// in the values' own unit, `relative`, as a fraction of the larger magnitude,
+/// or `ulps`, the number of representable doubles between them. A fixed epsilon
+/// is meaningless across magnitudes, 1 GW and 1e9 W being 1e9 apart in base
+/// units, so the default is a few ULPs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub absolute: f64,
+    pub relative: f64,
+    pub ulps: u64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self { absolute: 0.0, relative: 0.0, ulps: DEFAULT_ULPS }
+    }
+}
+
+impl Tolerance {
+    /// Creates a tolerance from the bounds given, leaving out the others, or the
+    /// default if none are
+    pub fn new(absolute: Option<f64>, relative: Option<f64>, ulps: Option<u64>) -> Result<Self, ApiError> {
+        for (name, bound) in [("Absolute", absolute), ("Relative", relative)] {
+            if let Some(bound) = bound.filter(|b| !b.is_finite() || *b < 0.0) {
+                return Err(ApiError::BadRequest(format!(
+                    "{} tolerance must be a non-negative number, not {}", name, bound
+                )));
+            }
+        }
+
+        if absolute.is_none() && relative.is_none() && ulps.is_none() {
+            return Ok(Self::default());
+        }
+        Ok(Self {
+            absolute: absolute.unwrap_or(0.0),
+            relative: relative.unwrap_or(0.0),
+            ulps: ulps.unwrap_or(0),
+        })
+    }
+
+    /// Returns true if `a` and `b` are within the tolerance of each other
+    pub fn approx_eq(&self, a: f64, b: f64) -> bool {
+        if a == b {
+            return true;
+        }
+        if !a.is_finite() || !b.is_finite() {
+            return false;
+        }
+
+        let difference = (a - b).abs();
+        difference <= self.absolute
+            || difference <= self.relative * a.abs().max(b.abs())
+            || ulps_between(a, b) <= self.ulps
+    }
+}
+
+/// Returns the number of representable doubles from `a` to `b`, counting
+/// across zero, or `u64::MAX` if either is NaN
+pub fn ulps_between(a: f64, b: f64) -> u64 {
+    if a.is_nan() || b.is_nan() {
+        return u64::MAX;
+    }
+
+    // Map the bits onto a line where adjacent doubles are adjacent integers
+    let ordered = |x: f64| {
+        let bits = x.to_bits() as i64;
+        if bits < 0 { i64::MIN as i128 - bits as i128 } else { bits as i128 }
+    };
+    u64::try_from((ordered(a) - ordered(b)).unsigned_abs()).unwrap_or(u64::MAX)
+}
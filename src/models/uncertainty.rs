@@ -0,0 +1,83 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::errors::ApiError;
+
+/// The uncertainty of a measured value, in the value's unit
+///
+/// A standard deviation is propagated to first order, so converting scales it
+/// by the conversion factor and a sum's is the root sum of squares of its terms',
+/// taking them as uncorrelated. Bounds are propagated by interval arithmetic, so
+/// a sum lies between the sums of the bounds. Combining the two treats ±σ as
+/// the bounds [value − σ, value + σ].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Uncertainty {
+    /// One standard deviation either side of the value, ±σ
+    StandardDeviation(f64),
+    /// Bounds the value lies between, not necessarily symmetric around it
+    Interval { lower: f64, upper: f64 },
+}
+
+impl Uncertainty {
+    /// Checks that the uncertainty is finite, a standard deviation isn't
+    /// negative, and an interval contains `value`
+    pub fn check(&self, value: f64) -> Result<(), ApiError> {
+        match *self {
+            Uncertainty::StandardDeviation(sigma) if !sigma.is_finite() || sigma < 0.0 => {
+                Err(ApiError::BadRequest(format!(
+                    "Standard deviation must be a non-negative number, not {}", sigma
+                )))
+            },
+            Uncertainty::Interval { lower, upper } if !((lower..=upper).contains(&value) && (upper - lower).is_finite()) => {
+                Err(ApiError::BadRequest(format!(
+                    "Interval [{}, {}] must contain the value {}", lower, upper, value
+                )))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the bounds of the uncertainty around `value`
+    pub fn bounds(&self, value: f64) -> (f64, f64) {
+        match *self {
+            Uncertainty::StandardDeviation(sigma) => (value - sigma, value + sigma),
+            Uncertainty::Interval { lower, upper } => (lower, upper),
+        }
+    }
+
+    /// Carries the uncertainty through an affine map of the value, such as a
+    /// unit conversion or scaling
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        match *self {
+            Uncertainty::StandardDeviation(sigma) => {
+                // Take the slope over a wide span, so that rounding the offset of
+                // a conversion such as °C to °F doesn't show in it
+                const SPAN: f64 = (1 << 20) as f64;
+                Uncertainty::StandardDeviation(sigma * ((f(SPAN) - f(-SPAN)) / (2.0 * SPAN)).abs())
+            },
+            Uncertainty::Interval { lower, upper } => {
+                let (lower, upper) = (f(lower), f(upper));
+                Uncertainty::Interval { lower: lower.min(upper), upper: lower.max(upper) }
+            },
+        }
+    }
+
+    /// Returns the uncertainty of `a + b` from those of `a` and `b`, which are in
+    /// the same unit
+    pub fn sum(a: f64, a_uncertainty: Option<&Self>, b: f64, b_uncertainty: Option<&Self>) -> Option<Self> {
+        match (a_uncertainty, b_uncertainty) {
+            (None, None) => None,
+            (Some(Uncertainty::StandardDeviation(sa)), Some(Uncertainty::StandardDeviation(sb))) => {
+                Some(Uncertainty::StandardDeviation(sa.hypot(*sb)))
+            },
+            (Some(Uncertainty::StandardDeviation(s)), None) | (None, Some(Uncertainty::StandardDeviation(s))) => {
+                Some(Uncertainty::StandardDeviation(*s))
+            },
+            _ => {
+                let (a_lower, a_upper) = a_uncertainty.map_or((a, a), |u| u.bounds(a));
+                let (b_lower, b_upper) = b_uncertainty.map_or((b, b), |u| u.bounds(b));
+                Some(Uncertainty::Interval { lower: a_lower + b_lower, upper: a_upper + b_upper })
+            },
+        }
+    }
+}
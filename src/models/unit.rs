@@ -3,6 +3,13 @@ use serde::{Serialize, Deserialize};
 use std::cmp::Ordering;
 use std::ops::{Add, Sub, Mul, Div};
 use crate::errors::ApiError;
+use crate::models::dimension::Dimension;
+use crate::models::exact::{self, LinearConversion};
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use crate::models::tolerance::Tolerance;
+use crate::models::uncertainty::Uncertainty;
+use bigdecimal::BigDecimal;
 
 /// A trait for unit categories (length, mass, temperature, etc.)
 pub trait UnitCategory: Display + Send + Sync + 'static {
@@ -11,8 +18,28 @@ pub trait UnitCategory: Display + Send + Sync + 'static {
     
     /// Returns the description of the category
     fn description(&self) -> &'static str;
+    
+    /// Returns the physical dimension shared by all units in the category,
+    /// or `None` if the category can't take part in dimensional analysis
+    fn dimension(&self) -> Option<Dimension> {
+        None
+    }
+    
+    /// Returns the symbol of the unit every other unit in the category is
+    /// defined relative to, or `None` if it isn't fixed
+    fn base_unit(&self) -> Option<&'static str> {
+        None
+    }
 }
 
+/// A category whose measurements are amounts that can be added to and subtracted
+/// from one another
+///
+/// Absolute temperatures aren't: the difference of two is a temperature
+/// interval, and only an interval can be added to one, so the temperature
+/// module implements their arithmetic itself.
+pub trait LinearCategory: UnitCategory {}
+
 /// Represents a specific unit of measurement (meter, kilogram, celsius, etc.)
 pub trait Unit: Display + Send + Sync + 'static {
     /// The category this unit belongs to
@@ -28,24 +55,74 @@ pub trait Unit: Display + Send + Sync + 'static {
     fn category(&self) -> &Self::Category;
     
     /// Converts a value from the base unit of the category to this unit
+    #[allow(clippy::wrong_self_convention)]
     fn from_base(&self, value: f64) -> f64;
     
     /// Converts a value from this unit to the base unit of the category
     fn to_base(&self, value: f64) -> f64;
+    
+    /// Returns the exact rational definition of this unit relative to the base
+    /// unit, or `None` if the unit is only defined by floating-point functions
+    fn exact(&self) -> Option<&LinearConversion> {
+        None
+    }
+    
+    /// Converts an arbitrary-precision decimal from this unit to the base unit,
+    /// or returns `None` if the unit has no exact definition
+    fn to_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        self.exact().map(|exact| exact.to_base_decimal(value))
+    }
+    
+    /// Converts an arbitrary-precision decimal from the base unit to this unit,
+    /// or returns `None` if the unit has no exact definition
+    #[allow(clippy::wrong_self_convention)]
+    fn from_base_decimal(&self, value: &BigDecimal) -> Option<BigDecimal> {
+        self.exact().map(|exact| exact.from_base_decimal(value))
+    }
+    
+    /// Returns the prefixes the registry should generate units for, such as
+    /// kilo and milli for the meter
+    fn prefixes(&self) -> Prefixes {
+        Prefixes::None
+    }
+    
+    /// Returns the systems of measurement the unit belongs to
+    fn systems(&self) -> &'static [UnitSystem] {
+        &[]
+    }
+    
+    /// Returns a human-readable description of the unit, if it has one
+    fn description(&self) -> Option<&'static str> {
+        None
+    }
 }
 
-/// A quantity with a specific unit
+/// A quantity with a specific unit, and optionally its uncertainty
+///
+/// Conversions and arithmetic carry the uncertainty along, as described on
+/// `Uncertainty`. Comparisons only look at the values.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Measurement<U: Unit> {
     value: f64,
     #[serde(skip)]
     unit: U,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uncertainty: Option<Uncertainty>,
 }
 
 impl<U: Unit> Measurement<U> {
     /// Creates a new measurement with the given value and unit
     pub fn new(value: f64, unit: U) -> Self {
-        Self { value, unit }
+        Self { value, unit, uncertainty: None }
+    }
+    
+    /// Returns the measurement with the given uncertainty, in its unit
+    pub fn with_uncertainty(self, uncertainty: Uncertainty) -> Self {
+        Self { uncertainty: Some(uncertainty), ..self }
+    }
+    
+    pub(crate) fn with_uncertainty_of(value: f64, unit: U, uncertainty: Option<Uncertainty>) -> Self {
+        Self { value, unit, uncertainty }
     }
     
     /// Returns the value of the measurement
@@ -58,72 +135,127 @@ impl<U: Unit> Measurement<U> {
         &self.unit
     }
     
+    /// Returns the uncertainty of the measurement, if it has one
+    pub fn uncertainty(&self) -> Option<&Uncertainty> {
+        self.uncertainty.as_ref()
+    }
+    
     /// Converts this measurement to another unit within the same category
     pub fn convert_to<T: Unit<Category = U::Category>>(&self, target_unit: T) -> Result<Measurement<T>, ApiError> {
-        // First convert to base unit, then to target unit
-        let base_value = self.unit.to_base(self.value);
-        let target_value = target_unit.from_base(base_value);
+        let uncertainty = self.uncertainty_in(&target_unit);
+        Ok(Measurement::with_uncertainty_of(self.value_in(&target_unit), target_unit, uncertainty))
+    }
+    
+    /// Returns the value of this measurement in another unit of the same category
+    pub fn value_in<T: Unit<Category = U::Category>>(&self, target_unit: &T) -> f64 {
+        // Compose exact definitions so the value is only rounded once
+        if let (Some(from), Some(to)) = (self.unit.exact(), target_unit.exact()) {
+            if let Some(target_value) = exact::convert_exact(self.value, from, to) {
+                return exact::rational_to_f64(&target_value);
+            }
+        }
         
-        Ok(Measurement::new(target_value, target_unit))
+        // First convert to base unit, then to target unit
+        target_unit.from_base(self.unit.to_base(self.value))
+    }
+    
+    /// Returns the uncertainty of this measurement in another unit of the same category
+    pub fn uncertainty_in<T: Unit<Category = U::Category>>(&self, target_unit: &T) -> Option<Uncertainty> {
+        self.uncertainty.map(|u| u.map(|x| target_unit.from_base(self.unit.to_base(x))))
+    }
+    
+    /// Returns the uncertainty of this measurement plus `sign` times another
+    /// value, both in this measurement's unit
+    pub(crate) fn uncertainty_of_sum(&self, other: f64, other_uncertainty: Option<Uncertainty>, sign: f64) -> Option<Uncertainty> {
+        let other_uncertainty = other_uncertainty.map(|u| u.map(|x| sign * x));
+        Uncertainty::sum(self.value, self.uncertainty.as_ref(), sign * other, other_uncertainty.as_ref())
     }
     
     /// Returns the measurement in the base unit of its category
     pub fn to_base(&self) -> f64 {
         self.unit.to_base(self.value)
     }
+    
+    /// Returns true if this measurement is within `tolerance` of another, both
+    /// expressed in the other's unit
+    pub fn approx_eq<V: Unit<Category = U::Category>>(&self, other: &Measurement<V>, tolerance: &Tolerance) -> bool {
+        tolerance.approx_eq(self.value_in(&other.unit), other.value)
+    }
 }
 
-// Display implementation for Measurement
+// Display implementation for Measurement, as "20 ± 0.5 °C" or "20 °C [19.5, 21]"
 impl<U: Unit> Display for Measurement<U> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{} {}", self.value, self.unit.symbol())
+        match self.uncertainty {
+            None => write!(f, "{} {}", self.value, self.unit.symbol()),
+            Some(Uncertainty::StandardDeviation(sigma)) => write!(f, "{} ± {} {}", self.value, sigma, self.unit.symbol()),
+            Some(Uncertainty::Interval { lower, upper }) => {
+                write!(f, "{} {} [{}, {}]", self.value, self.unit.symbol(), lower, upper)
+            },
+        }
     }
 }
 
 // Implement PartialEq for comparing measurements
-// Two measurements are equal if their base values are equal
+// Two measurements are equal if their base values are equal, compared exactly
+// when both units have exact definitions, or within the default tolerance
 impl<U: Unit, V: Unit<Category = U::Category>> PartialEq<Measurement<V>> for Measurement<U> {
     fn eq(&self, other: &Measurement<V>) -> bool {
-        let self_base = self.to_base();
-        let other_base = other.to_base();
+        if let (Some(self_exact), Some(other_exact)) = (self.unit.exact(), other.unit.exact()) {
+            if let (Some(a), Some(b)) = (exact::rational_from_f64(self.value), exact::rational_from_f64(other.value)) {
+                if self_exact.to_base(&a) == other_exact.to_base(&b) {
+                    return true;
+                }
+            }
+        }
         
-        (self_base - other_base).abs() < f64::EPSILON
+        self.approx_eq(other, &Tolerance::default())
     }
 }
 
 // Implement PartialOrd for comparing measurements
+// Measurements that are equal by PartialEq, exactly or within the default
+// tolerance, are ordered as equal, and the rest by their base values
 impl<U: Unit, V: Unit<Category = U::Category>> PartialOrd<Measurement<V>> for Measurement<U> {
     fn partial_cmp(&self, other: &Measurement<V>) -> Option<Ordering> {
-        let self_base = self.to_base();
-        let other_base = other.to_base();
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        if let (Some(self_exact), Some(other_exact)) = (self.unit.exact(), other.unit.exact()) {
+            if let (Some(a), Some(b)) = (exact::rational_from_f64(self.value), exact::rational_from_f64(other.value)) {
+                return Some(self_exact.to_base(&a).cmp(&other_exact.to_base(&b)));
+            }
+        }
         
-        self_base.partial_cmp(&other_base)
+        self.to_base().partial_cmp(&other.to_base())
     }
 }
 
-// Implement Add for adding measurements of the same category
-impl<U: Unit + Clone, V: Unit<Category = U::Category>> Add<Measurement<V>> for Measurement<U> {
+// Implement Add for adding measurements of the same linear category
+impl<U: Unit + Clone, V: Unit<Category = U::Category>> Add<Measurement<V>> for Measurement<U> where U::Category: LinearCategory {
     type Output = Measurement<U>;
     
     fn add(self, other: Measurement<V>) -> Self::Output {
         let self_base = self.to_base();
         let other_base = other.to_base();
         let result_base = self_base + other_base;
+        let uncertainty = self.uncertainty_of_sum(other.value_in(&self.unit), other.uncertainty_in(&self.unit), 1.0);
         
-        Measurement::new(self.unit.from_base(result_base), self.unit)
+        Measurement::with_uncertainty_of(self.unit.from_base(result_base), self.unit, uncertainty)
     }
 }
 
-// Implement Sub for subtracting measurements of the same category
-impl<U: Unit + Clone, V: Unit<Category = U::Category>> Sub<Measurement<V>> for Measurement<U> {
+// Implement Sub for subtracting measurements of the same linear category
+impl<U: Unit + Clone, V: Unit<Category = U::Category>> Sub<Measurement<V>> for Measurement<U> where U::Category: LinearCategory {
     type Output = Measurement<U>;
     
     fn sub(self, other: Measurement<V>) -> Self::Output {
         let self_base = self.to_base();
         let other_base = other.to_base();
         let result_base = self_base - other_base;
+        let uncertainty = self.uncertainty_of_sum(other.value_in(&self.unit), other.uncertainty_in(&self.unit), -1.0);
         
-        Measurement::new(self.unit.from_base(result_base), self.unit)
+        Measurement::with_uncertainty_of(self.unit.from_base(result_base), self.unit, uncertainty)
     }
 }
 
@@ -132,7 +264,8 @@ impl<U: Unit + Clone> Mul<f64> for Measurement<U> {
     type Output = Measurement<U>;
     
     fn mul(self, scalar: f64) -> Self::Output {
-        Measurement::new(self.value * scalar, self.unit)
+        let uncertainty = self.uncertainty.map(|u| u.map(|x| x * scalar));
+        Measurement::with_uncertainty_of(self.value * scalar, self.unit, uncertainty)
     }
 }
 
@@ -141,6 +274,7 @@ impl<U: Unit + Clone> Div<f64> for Measurement<U> {
     type Output = Measurement<U>;
     
     fn div(self, scalar: f64) -> Self::Output {
-        Measurement::new(self.value / scalar, self.unit)
+        let uncertainty = self.uncertainty.map(|u| u.map(|x| x / scalar));
+        Measurement::with_uncertainty_of(self.value / scalar, self.unit, uncertainty)
     }
 } 
\ No newline at end of file
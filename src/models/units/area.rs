@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The area category for units of area measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for AreaCategory {
     fn description(&self) -> &'static str {
         "Units of area measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::new([2, 0, 0, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("m²")
+    }
 }
 
-// Shared implementation for all area units
+impl LinearCategory for AreaCategory {}
+
+// Shared implementation for all area units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_area_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_area_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(AreaCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_area_unit {
             pub fn new() -> Self {
                 Self(AreaCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_area_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,8 @@ impl_area_unit!(
     SquareMeter, 
     "square meter", 
     "m²", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si]
 );
 
 // Square Kilometer (km²)
@@ -79,8 +123,8 @@ impl_area_unit!(
     SquareKilometer, 
     "square kilometer", 
     "km²", 
-    |value| value * 1_000_000.0,  // to_base: 1 km² = 1,000,000 m²
-    |value| value / 1_000_000.0   // from_base: 1 m² = 0.000001 km²
+    "1000000",                     // 1 km² = 1,000,000 m²
+    &[UnitSystem::Si]
 );
 
 // Square Centimeter (cm²)
@@ -88,8 +132,8 @@ impl_area_unit!(
     SquareCentimeter, 
     "square centimeter", 
     "cm²", 
-    |value| value * 0.0001,  // to_base: 1 cm² = 0.0001 m²
-    |value| value / 0.0001   // from_base: 1 m² = 10,000 cm²
+    "0.0001",                      // 1 cm² = 0.0001 m²
+    &[UnitSystem::Si]
 );
 
 // Square Millimeter (mm²)
@@ -97,8 +141,8 @@ impl_area_unit!(
     SquareMillimeter, 
     "square millimeter", 
     "mm²", 
-    |value| value * 0.000001,  // to_base: 1 mm² = 0.000001 m²
-    |value| value / 0.000001   // from_base: 1 m² = 1,000,000 mm²
+    "0.000001",                    // 1 mm² = 0.000001 m²
+    &[UnitSystem::Si]
 );
 
 // Hectare (ha)
@@ -106,8 +150,8 @@ impl_area_unit!(
     Hectare, 
     "hectare", 
     "ha", 
-    |value| value * 10_000.0,  // to_base: 1 ha = 10,000 m²
-    |value| value / 10_000.0   // from_base: 1 m² = 0.0001 ha
+    "10000",                       // 1 ha = 10,000 m²
+    &[UnitSystem::Metric]
 );
 
 // Are (a)
@@ -115,8 +159,8 @@ impl_area_unit!(
     Are, 
     "are", 
     "a", 
-    |value| value * 100.0,  // to_base: 1 a = 100 m²
-    |value| value / 100.0   // from_base: 1 m² = 0.01 a
+    "100",                         // 1 a = 100 m²
+    &[UnitSystem::Metric]
 );
 
 // Square Inch (in²)
@@ -124,8 +168,8 @@ impl_area_unit!(
     SquareInch, 
     "square inch", 
     "in²", 
-    |value| value * 0.00064516,  // to_base: 1 in² = 0.00064516 m²
-    |value| value / 0.00064516   // from_base: 1 m² = 1,550 in²
+    "0.00064516",                  // 1 in² = 0.00064516 m²
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Square Foot (ft²)
@@ -133,8 +177,8 @@ impl_area_unit!(
     SquareFoot, 
     "square foot", 
     "ft²", 
-    |value| value * 0.092903,  // to_base: 1 ft² = 0.092903 m²
-    |value| value / 0.092903   // from_base: 1 m² = 10.7639 ft²
+    "0.09290304",                  // 1 ft² = 0.09290304 m² (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Square Yard (yd²)
@@ -142,8 +186,8 @@ impl_area_unit!(
     SquareYard, 
     "square yard", 
     "yd²", 
-    |value| value * 0.836127,  // to_base: 1 yd² = 0.836127 m²
-    |value| value / 0.836127   // from_base: 1 m² = 1.19599 yd²
+    "0.83612736",                  // 1 yd² = 0.83612736 m² (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Square Mile (mi²)
@@ -151,8 +195,8 @@ impl_area_unit!(
     SquareMile, 
     "square mile", 
     "mi²", 
-    |value| value * 2_589_988.0,  // to_base: 1 mi² = 2,589,988 m²
-    |value| value / 2_589_988.0   // from_base: 1 m² = 3.861e-7 mi²
+    "2589988.110336",              // 1 mi² = 2,589,988.110336 m² (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Acre (ac)
@@ -160,6 +204,6 @@ impl_area_unit!(
     Acre, 
     "acre", 
     "ac", 
-    |value| value * 4_046.86,  // to_base: 1 ac = 4,046.86 m²
-    |value| value / 4_046.86   // from_base: 1 m² = 0.000247105 ac
+    "4046.8564224",                // 1 ac = 4,046.8564224 m² (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 ); 
\ No newline at end of file
@@ -0,0 +1,137 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::registry::UnitRegistry;
+use crate::models::rates::{rate_provider, ExchangeRate};
+use crate::errors::ApiError;
+
+/// Names of common currencies, used to look them up by name as well as by code
+const CURRENCY_NAMES: [(&str, &str); 20] = [
+    ("AUD", "Australian dollar"),
+    ("BRL", "Brazilian real"),
+    ("CAD", "Canadian dollar"),
+    ("CHF", "Swiss franc"),
+    ("CNY", "Chinese yuan"),
+    ("DKK", "Danish krone"),
+    ("EUR", "euro"),
+    ("GBP", "British pound"),
+    ("HKD", "Hong Kong dollar"),
+    ("INR", "Indian rupee"),
+    ("JPY", "Japanese yen"),
+    ("KRW", "South Korean won"),
+    ("MXN", "Mexican peso"),
+    ("NOK", "Norwegian krone"),
+    ("NZD", "New Zealand dollar"),
+    ("PLN", "Polish zloty"),
+    ("SEK", "Swedish krona"),
+    ("SGD", "Singapore dollar"),
+    ("USD", "US dollar"),
+    ("ZAR", "South African rand"),
+];
+
+/// The currency category, whose conversion factors are exchange rates
+#[derive(Debug, Clone, Copy)]
+pub struct CurrencyCategory;
+
+impl Display for CurrencyCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Currency")
+    }
+}
+
+impl UnitCategory for CurrencyCategory {
+    fn name(&self) -> &'static str {
+        "currency"
+    }
+
+    fn description(&self) -> &'static str {
+        "Currencies, converted at exchange rates from the installed rate provider"
+    }
+}
+
+impl LinearCategory for CurrencyCategory {}
+
+/// A currency, identified by its ISO 4217 code
+///
+/// Unlike other units a currency has no fixed factor: `to_base` and `from_base`
+/// read its current rate from the installed `RateProvider`, with the provider's
+/// base currency as the base unit. A currency the provider has no rate for
+/// converts to NaN.
+#[derive(Debug, Clone, Copy)]
+pub struct Currency {
+    code: &'static str,
+    name: &'static str,
+    category: CurrencyCategory,
+}
+
+impl Currency {
+    /// Creates a currency, naming it after its code unless it's a common one
+    pub fn new(code: &'static str) -> Self {
+        let name = CURRENCY_NAMES.iter()
+            .find(|&&(c, _)| c == code)
+            .map_or(code, |&(_, name)| name);
+        Self { code, name, category: CurrencyCategory }
+    }
+
+    /// Returns the current exchange rate of the currency
+    pub fn rate(&self) -> Option<ExchangeRate> {
+        rate_provider().rate(self.code)
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl Unit for Currency {
+    type Category = CurrencyCategory;
+
+    fn symbol(&self) -> &'static str {
+        self.code
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn category(&self) -> &Self::Category {
+        &self.category
+    }
+
+    fn to_base(&self, value: f64) -> f64 {
+        self.rate().map_or(f64::NAN, |rate| value / rate.rate)
+    }
+
+    fn from_base(&self, value: f64) -> f64 {
+        self.rate().map_or(f64::NAN, |rate| value * rate.rate)
+    }
+}
+
+/// Register the currency category, if it isn't yet, and a unit for every currency
+/// the installed rate provider has that isn't registered yet
+///
+/// Returns the number of currencies registered.
+pub fn register_currencies(reg: &mut UnitRegistry) -> Result<usize, ApiError> {
+    if reg.get_category("currency").is_none() {
+        reg.register_category(CurrencyCategory)?;
+    }
+
+    let registered: Vec<&'static str> = reg.get_units_for_category("currency").iter()
+        .map(|u| u.symbol())
+        .collect();
+
+    let mut count = 0;
+    for code in rate_provider().currencies() {
+        if registered.contains(&code.as_str()) {
+            continue;
+        }
+
+        // Codes live as long as the registry, like the symbols of built-in units
+        let code: &'static str = Box::leak(code.into_boxed_str());
+        reg.register_unit(Currency::new(code))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
@@ -0,0 +1,139 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
+
+/// The data category for units of digital information
+#[derive(Debug, Clone, Copy)]
+pub struct DataCategory;
+
+impl Display for DataCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Data")
+    }
+}
+
+impl UnitCategory for DataCategory {
+    fn name(&self) -> &'static str {
+        "data"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Units of digital information"
+    }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::INFORMATION)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("bit")
+    }
+}
+
+impl LinearCategory for DataCategory {}
+
+// Shared implementation for all data units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
+macro_rules! impl_data_unit {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_data_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $unit(DataCategory);
+        
+        impl $unit {
+            pub fn new() -> Self {
+                Self(DataCategory)
+            }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
+        impl Display for $unit {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                write!(f, $name)
+            }
+        }
+        
+        impl Unit for $unit {
+            type Category = DataCategory;
+            
+            fn symbol(&self) -> &'static str {
+                $symbol
+            }
+            
+            fn name(&self) -> &'static str {
+                $name
+            }
+            
+            fn category(&self) -> &Self::Category {
+                &self.0
+            }
+            
+            fn to_base(&self, value: f64) -> f64 {
+                Self::conversion().to_base_f64(value)
+            }
+            
+            fn from_base(&self, value: f64) -> f64 {
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
+            }
+        }
+    };
+}
+
+// Base unit: Bit (bit)
+impl_data_unit!(
+    Bit, 
+    "bit", 
+    "bit", 
+    "1",                           // direct conversion (base unit)
+    &[],
+    Prefixes::Binary               // kbit, Mbit, Kibit, Mibit and the rest are generated
+);
+
+// Nibble
+impl_data_unit!(
+    Nibble, 
+    "nibble", 
+    "nibble", 
+    "4",                           // 1 nibble = 4 bits
+    &[]
+);
+
+// Byte (B)
+impl_data_unit!(
+    Byte, 
+    "byte", 
+    "B", 
+    "8",                           // 1 B = 8 bits
+    &[],
+    Prefixes::Binary               // kB, MB, KiB, MiB and the rest are generated
+);
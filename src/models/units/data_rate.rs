@@ -0,0 +1,130 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
+
+/// The data rate category for units of data transfer rate
+#[derive(Debug, Clone, Copy)]
+pub struct DataRateCategory;
+
+impl Display for DataRateCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Data Rate")
+    }
+}
+
+impl UnitCategory for DataRateCategory {
+    fn name(&self) -> &'static str {
+        "data_rate"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Units of data transfer rate"
+    }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Dimension::INFORMATION.checked_div(Dimension::TIME)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("bit/s")
+    }
+}
+
+impl LinearCategory for DataRateCategory {}
+
+// Shared implementation for all data rate units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
+macro_rules! impl_data_rate_unit {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_data_rate_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $unit(DataRateCategory);
+        
+        impl $unit {
+            pub fn new() -> Self {
+                Self(DataRateCategory)
+            }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
+        impl Display for $unit {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                write!(f, $name)
+            }
+        }
+        
+        impl Unit for $unit {
+            type Category = DataRateCategory;
+            
+            fn symbol(&self) -> &'static str {
+                $symbol
+            }
+            
+            fn name(&self) -> &'static str {
+                $name
+            }
+            
+            fn category(&self) -> &Self::Category {
+                &self.0
+            }
+            
+            fn to_base(&self, value: f64) -> f64 {
+                Self::conversion().to_base_f64(value)
+            }
+            
+            fn from_base(&self, value: f64) -> f64 {
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
+            }
+        }
+    };
+}
+
+// Base unit: Bit per second (bit/s)
+impl_data_rate_unit!(
+    BitPerSecond, 
+    "bit per second", 
+    "bit/s", 
+    "1",                           // direct conversion (base unit)
+    &[],
+    Prefixes::Binary               // kbit/s, Gbit/s, Mibit/s and the rest are generated
+);
+
+// Byte per second (B/s)
+impl_data_rate_unit!(
+    BytePerSecond, 
+    "byte per second", 
+    "B/s", 
+    "8",                           // 1 B/s = 8 bit/s
+    &[],
+    Prefixes::Binary               // kB/s, MB/s, MiB/s and the rest are generated
+);
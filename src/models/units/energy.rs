@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The energy category for units of energy measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,27 @@ impl UnitCategory for EnergyCategory {
     fn description(&self) -> &'static str {
         "Units of energy measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        // M·L²·T⁻² (force times distance)
+        Some(Dimension::new([2, 1, -2, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("J")
+    }
 }
 
-// Shared implementation for all energy units
+impl LinearCategory for EnergyCategory {}
+
+// Shared implementation for all energy units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_energy_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_energy_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(EnergyCategory);
         
@@ -31,6 +52,18 @@ macro_rules! impl_energy_unit {
             pub fn new() -> Self {
                 Self(EnergyCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +88,23 @@ macro_rules! impl_energy_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +115,9 @@ impl_energy_unit!(
     Joule, 
     "joule", 
     "J", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // kJ, GJ, mJ and the rest are generated
 );
 
 // Kilojoule (kJ)
@@ -79,8 +125,8 @@ impl_energy_unit!(
     Kilojoule, 
     "kilojoule", 
     "kJ", 
-    |value| value * 1000.0,  // to_base: 1 kJ = 1,000 J
-    |value| value / 1000.0   // from_base: 1 J = 0.001 kJ
+    "1000",                        // 1 kJ = 1,000 J
+    &[UnitSystem::Si]
 );
 
 // Megajoule (MJ)
@@ -88,8 +134,8 @@ impl_energy_unit!(
     Megajoule, 
     "megajoule", 
     "MJ", 
-    |value| value * 1_000_000.0,  // to_base: 1 MJ = 1,000,000 J
-    |value| value / 1_000_000.0   // from_base: 1 J = 0.000001 MJ
+    "1000000",                     // 1 MJ = 1,000,000 J
+    &[UnitSystem::Si]
 );
 
 // Calorie (cal)
@@ -97,8 +143,8 @@ impl_energy_unit!(
     Calorie, 
     "calorie", 
     "cal", 
-    |value| value * 4.184,  // to_base: 1 cal = 4.184 J
-    |value| value / 4.184   // from_base: 1 J = 0.239 cal
+    "4.184",                       // 1 cal = 4.184 J
+    &[UnitSystem::Metric]
 );
 
 // Kilocalorie (kcal) - food calorie
@@ -106,8 +152,8 @@ impl_energy_unit!(
     Kilocalorie, 
     "kilocalorie", 
     "kcal", 
-    |value| value * 4184.0,  // to_base: 1 kcal = 4,184 J
-    |value| value / 4184.0   // from_base: 1 J = 0.000239 kcal
+    "4184",                        // 1 kcal = 4,184 J
+    &[UnitSystem::Metric]
 );
 
 // British Thermal Unit (BTU)
@@ -115,8 +161,8 @@ impl_energy_unit!(
     BTU, 
     "british thermal unit", 
     "BTU", 
-    |value| value * 1055.06,  // to_base: 1 BTU = 1,055.06 J
-    |value| value / 1055.06   // from_base: 1 J = 0.000948 BTU
+    "1055.05585262",               // 1 BTU = 1,055.05585262 J (International Table)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Kilowatt-hour (kWh)
@@ -124,8 +170,8 @@ impl_energy_unit!(
     KilowattHour, 
     "kilowatt hour", 
     "kWh", 
-    |value| value * 3_600_000.0,  // to_base: 1 kWh = 3,600,000 J
-    |value| value / 3_600_000.0   // from_base: 1 J = 2.778e-7 kWh
+    "3600000",                     // 1 kWh = 3,600,000 J
+    &[UnitSystem::Metric]
 );
 
 // Watt-hour (Wh)
@@ -133,8 +179,9 @@ impl_energy_unit!(
     WattHour, 
     "watt hour", 
     "Wh", 
-    |value| value * 3600.0,  // to_base: 1 Wh = 3,600 J
-    |value| value / 3600.0   // from_base: 1 J = 0.000278 Wh
+    "3600",                        // 1 Wh = 3,600 J
+    &[UnitSystem::Metric],
+    Prefixes::Metric               // kWh, MWh, GWh and the rest are generated
 );
 
 // Electron-volt (eV)
@@ -142,8 +189,9 @@ impl_energy_unit!(
     ElectronVolt, 
     "electron volt", 
     "eV", 
-    |value| value * 1.602176634e-19,  // to_base: 1 eV = 1.602176634e-19 J
-    |value| value / 1.602176634e-19   // from_base: 1 J = 6.241509e18 eV
+    "1.602176634e-19",             // 1 eV = 1.602176634e-19 J
+    &[UnitSystem::Metric],
+    Prefixes::Metric               // keV, MeV, GeV and the rest are generated
 );
 
 // Therm (therm)
@@ -151,8 +199,8 @@ impl_energy_unit!(
     Therm, 
     "therm", 
     "therm", 
-    |value| value * 105_505_585.257348,  // to_base: 1 therm = 100,000 BTU ≈ 105,505,585 J
-    |value| value / 105_505_585.257348   // from_base: 1 J = 9.478e-9 therm
+    "105505585.257348",            // 1 therm = 100,000 BTU ≈ 105,505,585 J
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Foot-pound (ft⋅lb)
@@ -160,6 +208,6 @@ impl_energy_unit!(
     FootPound, 
     "foot pound", 
     "ft⋅lb", 
-    |value| value * 1.35582,  // to_base: 1 ft⋅lb = 1.35582 J
-    |value| value / 1.35582   // from_base: 1 J = 0.737562 ft⋅lb
+    "1.3558179483314004",          // 1 ft⋅lb = 0.3048 m × 4.4482216152605 N (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 ); 
\ No newline at end of file
@@ -0,0 +1,166 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
+
+/// The force category for units of force measurement
+#[derive(Debug, Clone, Copy)]
+pub struct ForceCategory;
+
+impl Display for ForceCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Force")
+    }
+}
+
+impl UnitCategory for ForceCategory {
+    fn name(&self) -> &'static str {
+        "force"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Units of force measurement"
+    }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        // M·L·T⁻² (mass times acceleration)
+        Some(Dimension::new([1, 1, -2, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("N")
+    }
+}
+
+impl LinearCategory for ForceCategory {}
+
+// Shared implementation for all force units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
+macro_rules! impl_force_unit {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_force_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
+        #[derive(Debug, Clone, Copy)]
+        pub struct $unit(ForceCategory);
+        
+        impl $unit {
+            pub fn new() -> Self {
+                Self(ForceCategory)
+            }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+        
+        impl Display for $unit {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                write!(f, $name)
+            }
+        }
+        
+        impl Unit for $unit {
+            type Category = ForceCategory;
+            
+            fn symbol(&self) -> &'static str {
+                $symbol
+            }
+            
+            fn name(&self) -> &'static str {
+                $name
+            }
+            
+            fn category(&self) -> &Self::Category {
+                &self.0
+            }
+            
+            fn to_base(&self, value: f64) -> f64 {
+                Self::conversion().to_base_f64(value)
+            }
+            
+            fn from_base(&self, value: f64) -> f64 {
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
+            }
+        }
+    };
+}
+
+// Base unit: Newton (N)
+impl_force_unit!(
+    Newton, 
+    "newton", 
+    "N", 
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // kN, MN, mN and the rest are generated
+);
+
+// Kilonewton (kN)
+impl_force_unit!(
+    Kilonewton, 
+    "kilonewton", 
+    "kN", 
+    "1000",                        // 1 kN = 1,000 N
+    &[UnitSystem::Si]
+);
+
+// Dyne (dyn)
+impl_force_unit!(
+    Dyne, 
+    "dyne", 
+    "dyn", 
+    "0.00001",                     // 1 dyn = 0.00001 N
+    &[UnitSystem::Metric]
+);
+
+// Kilogram-force (kgf)
+impl_force_unit!(
+    KilogramForce, 
+    "kilogram-force", 
+    "kgf", 
+    "9.80665",                     // 1 kgf = 9.80665 N (exactly)
+    &[UnitSystem::Metric]
+);
+
+// Pound-force (lbf)
+impl_force_unit!(
+    PoundForce, 
+    "pound-force", 
+    "lbf", 
+    "4.4482216152605",             // 1 lbf = 4.4482216152605 N
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
+);
+
+// Poundal (pdl)
+impl_force_unit!(
+    Poundal, 
+    "poundal", 
+    "pdl", 
+    "0.138254954376",              // 1 pdl = 0.138254954376 N
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
+);
\ No newline at end of file
@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The length category for units of length measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for LengthCategory {
     fn description(&self) -> &'static str {
         "Units of length measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::LENGTH)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("m")
+    }
 }
 
-// Shared implementation for all length units
+impl LinearCategory for LengthCategory {}
+
+// Shared implementation for all length units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_length_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_length_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(LengthCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_length_unit {
             pub fn new() -> Self {
                 Self(LengthCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_length_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,9 @@ impl_length_unit!(
     Meter, 
     "meter", 
     "m", 
-    |value| value,                 // to_base: 1 meter = 1 meter (base unit)
-    |value| value                  // from_base: 1 meter = 1 meter (base unit)
+    "1",                           // 1 meter = 1 meter (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // km, Gm, pm and the rest are generated
 );
 
 // Kilometer (km)
@@ -79,8 +124,8 @@ impl_length_unit!(
     Kilometer, 
     "kilometer", 
     "km", 
-    |value| value * 1000.0,        // to_base: 1 km = 1000 m
-    |value| value / 1000.0         // from_base: 1 m = 0.001 km
+    "1000",                        // 1 km = 1000 m
+    &[UnitSystem::Si]
 );
 
 // Centimeter (cm)
@@ -88,8 +133,8 @@ impl_length_unit!(
     Centimeter, 
     "centimeter", 
     "cm", 
-    |value| value / 100.0,         // to_base: 1 cm = 0.01 m
-    |value| value * 100.0          // from_base: 1 m = 100 cm
+    "0.01",                        // 1 cm = 0.01 m
+    &[UnitSystem::Si]
 );
 
 // Millimeter (mm)
@@ -97,8 +142,8 @@ impl_length_unit!(
     Millimeter, 
     "millimeter", 
     "mm", 
-    |value| value / 1000.0,        // to_base: 1 mm = 0.001 m
-    |value| value * 1000.0         // from_base: 1 m = 1000 mm
+    "0.001",                       // 1 mm = 0.001 m
+    &[UnitSystem::Si]
 );
 
 // Micrometer (μm)
@@ -106,8 +151,8 @@ impl_length_unit!(
     Micrometer, 
     "micrometer", 
     "μm", 
-    |value| value / 1_000_000.0,   // to_base: 1 μm = 0.000001 m
-    |value| value * 1_000_000.0    // from_base: 1 m = 1,000,000 μm
+    "0.000001",                    // 1 μm = 0.000001 m
+    &[UnitSystem::Si]
 );
 
 // Nanometer (nm)
@@ -115,8 +160,8 @@ impl_length_unit!(
     Nanometer, 
     "nanometer", 
     "nm", 
-    |value| value / 1_000_000_000.0, // to_base: 1 nm = 0.000000001 m
-    |value| value * 1_000_000_000.0  // from_base: 1 m = 1,000,000,000 nm
+    "0.000000001",                 // 1 nm = 0.000000001 m
+    &[UnitSystem::Si]
 );
 
 // Inch (in)
@@ -124,8 +169,8 @@ impl_length_unit!(
     Inch, 
     "inch", 
     "in", 
-    |value| value * 0.0254,        // to_base: 1 in = 0.0254 m
-    |value| value / 0.0254         // from_base: 1 m = 39.3701 in
+    "0.0254",                      // 1 in = 0.0254 m
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Foot (ft)
@@ -133,8 +178,8 @@ impl_length_unit!(
     Foot, 
     "foot", 
     "ft", 
-    |value| value * 0.3048,        // to_base: 1 ft = 0.3048 m (exactly)
-    |value| value / 0.3048         // from_base: 1 m = ~3.28084 ft
+    "0.3048",                      // 1 ft = 0.3048 m (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Yard (yd)
@@ -142,8 +187,8 @@ impl_length_unit!(
     Yard, 
     "yard", 
     "yd", 
-    |value| value * 0.9144,        // to_base: 1 yd = 0.9144 m
-    |value| value / 0.9144         // from_base: 1 m = 1.09361 yd
+    "0.9144",                      // 1 yd = 0.9144 m
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Mile (mi)
@@ -151,8 +196,8 @@ impl_length_unit!(
     Mile, 
     "mile", 
     "mi", 
-    |value| value * 1609.344,      // to_base: 1 mi = 1609.344 m
-    |value| value / 1609.344       // from_base: 1 m = 0.000621371 mi
+    "1609.344",                    // 1 mi = 1609.344 m
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Nautical Mile (nmi)
@@ -160,6 +205,6 @@ impl_length_unit!(
     NauticalMile, 
     "nautical mile", 
     "nmi", 
-    |value| value * 1852.0,        // to_base: 1 nmi = 1852 m
-    |value| value / 1852.0         // from_base: 1 m = 0.000539957 nmi
+    "1852",                        // 1 nmi = 1852 m
+    &[]
 ); 
\ No newline at end of file
@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The mass category for units of mass measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for MassCategory {
     fn description(&self) -> &'static str {
         "Units of mass measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::MASS)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("kg")
+    }
 }
 
-// Shared implementation for all mass units
+impl LinearCategory for MassCategory {}
+
+// Shared implementation for all mass units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_mass_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_mass_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(MassCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_mass_unit {
             pub fn new() -> Self {
                 Self(MassCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_mass_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,8 @@ impl_mass_unit!(
     Kilogram, 
     "kilogram", 
     "kg", 
-    |value| value,                 // to_base: 1 kg = 1 kg (base unit)
-    |value| value                  // from_base: 1 kg = 1 kg (base unit)
+    "1",                           // 1 kg = 1 kg (base unit)
+    &[UnitSystem::Si]
 );
 
 // Gram (g)
@@ -79,8 +123,9 @@ impl_mass_unit!(
     Gram, 
     "gram", 
     "g", 
-    |value| value / 1000.0,        // to_base: 1 g = 0.001 kg
-    |value| value * 1000.0         // from_base: 1 kg = 1000 g
+    "0.001",                       // 1 g = 0.001 kg
+    &[UnitSystem::Si],
+    Prefixes::Metric               // mg, Mg, pg and the rest are generated
 );
 
 // Milligram (mg)
@@ -88,8 +133,8 @@ impl_mass_unit!(
     Milligram, 
     "milligram", 
     "mg", 
-    |value| value / 1_000_000.0,   // to_base: 1 mg = 0.000001 kg
-    |value| value * 1_000_000.0    // from_base: 1 kg = 1,000,000 mg
+    "0.000001",                    // 1 mg = 0.000001 kg
+    &[UnitSystem::Si]
 );
 
 // Microgram (μg)
@@ -97,8 +142,8 @@ impl_mass_unit!(
     Microgram, 
     "microgram", 
     "μg", 
-    |value| value / 1_000_000_000.0, // to_base: 1 μg = 0.000000001 kg
-    |value| value * 1_000_000_000.0  // from_base: 1 kg = 1,000,000,000 μg
+    "0.000000001",                 // 1 μg = 0.000000001 kg
+    &[UnitSystem::Si]
 );
 
 // Metric Ton/Tonne (t)
@@ -106,8 +151,8 @@ impl_mass_unit!(
     MetricTon, 
     "metric ton", 
     "t", 
-    |value| value * 1000.0,        // to_base: 1 t = 1000 kg
-    |value| value / 1000.0         // from_base: 1 kg = 0.001 t
+    "1000",                        // 1 t = 1000 kg
+    &[UnitSystem::Metric]
 );
 
 // Pound (lb)
@@ -115,8 +160,8 @@ impl_mass_unit!(
     Pound, 
     "pound", 
     "lb", 
-    |value| value * 0.45359237,    // to_base: 1 lb = 0.45359237 kg (exactly)
-    |value| value / 0.45359237     // from_base: 1 kg = ~2.20462 lb
+    "0.45359237",                  // 1 lb = 0.45359237 kg (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Ounce (oz)
@@ -124,8 +169,8 @@ impl_mass_unit!(
     Ounce, 
     "ounce", 
     "oz", 
-    |value| value * 0.028349523125, // to_base: 1 oz = 0.028349523125 kg
-    |value| value / 0.028349523125  // from_base: 1 kg = ~35.274 oz
+    "0.028349523125",              // 1 oz = 0.028349523125 kg
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Stone (st) - British unit
@@ -133,8 +178,8 @@ impl_mass_unit!(
     Stone, 
     "stone", 
     "st", 
-    |value| value * 6.35029318,    // to_base: 1 st = 6.35029318 kg (14 lb)
-    |value| value / 6.35029318     // from_base: 1 kg = ~0.157473 st
+    "6.35029318",                  // 1 st = 6.35029318 kg (14 lb)
+    &[UnitSystem::Imperial]
 );
 
 // US/Short Ton (US t)
@@ -142,8 +187,8 @@ impl_mass_unit!(
     USTon, 
     "US ton", 
     "US t", 
-    |value| value * 907.18474,     // to_base: 1 US t = 907.18474 kg (2000 lb)
-    |value| value / 907.18474      // from_base: 1 kg = ~0.00110231 US t
+    "907.18474",                   // 1 US t = 907.18474 kg (2000 lb)
+    &[UnitSystem::UsCustomary]
 );
 
 // Imperial/Long Ton (UK t)
@@ -151,6 +196,6 @@ impl_mass_unit!(
     ImperialTon, 
     "imperial ton", 
     "UK t", 
-    |value| value * 1016.0469088,  // to_base: 1 UK t = 1016.0469088 kg (2240 lb)
-    |value| value / 1016.0469088   // from_base: 1 kg = ~0.000984207 UK t
+    "1016.0469088",                // 1 UK t = 1016.0469088 kg (2240 lb)
+    &[UnitSystem::Imperial]
 ); 
\ No newline at end of file
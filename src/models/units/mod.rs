@@ -8,6 +8,10 @@ pub mod speed;
 pub mod pressure;
 pub mod energy;
 pub mod power;
+pub mod force;
+pub mod data;
+pub mod data_rate;
+pub mod currency;
 
 // Re-export units for easier access
 pub use length::*;
@@ -19,4 +23,8 @@ pub use time::*;
 pub use speed::*;
 pub use pressure::*;
 pub use energy::*;
-pub use power::*; 
\ No newline at end of file
+pub use power::*;
+pub use force::*;
+pub use data::*;
+pub use data_rate::*;
+pub use currency::*; 
\ No newline at end of file
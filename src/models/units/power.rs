@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The power category for units of power measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,27 @@ impl UnitCategory for PowerCategory {
     fn description(&self) -> &'static str {
         "Units of power measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        // M·L²·T⁻³ (energy per time)
+        Some(Dimension::new([2, 1, -3, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("W")
+    }
 }
 
-// Shared implementation for all power units
+impl LinearCategory for PowerCategory {}
+
+// Shared implementation for all power units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_power_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_power_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(PowerCategory);
         
@@ -31,6 +52,18 @@ macro_rules! impl_power_unit {
             pub fn new() -> Self {
                 Self(PowerCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +88,23 @@ macro_rules! impl_power_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +115,9 @@ impl_power_unit!(
     Watt, 
     "watt", 
     "W", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // mW, kW, TW and the rest are generated
 );
 
 // Kilowatt (kW)
@@ -79,8 +125,8 @@ impl_power_unit!(
     Kilowatt, 
     "kilowatt", 
     "kW", 
-    |value| value * 1000.0,  // to_base: 1 kW = 1,000 W
-    |value| value / 1000.0   // from_base: 1 W = 0.001 kW
+    "1000",                        // 1 kW = 1,000 W
+    &[UnitSystem::Si]
 );
 
 // Megawatt (MW)
@@ -88,8 +134,8 @@ impl_power_unit!(
     Megawatt, 
     "megawatt", 
     "MW", 
-    |value| value * 1_000_000.0,  // to_base: 1 MW = 1,000,000 W
-    |value| value / 1_000_000.0   // from_base: 1 W = 0.000001 MW
+    "1000000",                     // 1 MW = 1,000,000 W
+    &[UnitSystem::Si]
 );
 
 // Gigawatt (GW)
@@ -97,8 +143,8 @@ impl_power_unit!(
     Gigawatt, 
     "gigawatt", 
     "GW", 
-    |value| value * 1_000_000_000.0,  // to_base: 1 GW = 1,000,000,000 W
-    |value| value / 1_000_000_000.0   // from_base: 1 W = 1e-9 GW
+    "1000000000",                  // 1 GW = 1,000,000,000 W
+    &[UnitSystem::Si]
 );
 
 // Horsepower (mechanical) (hp)
@@ -106,8 +152,8 @@ impl_power_unit!(
     Horsepower, 
     "horsepower", 
     "hp", 
-    |value| value * 745.7,  // to_base: 1 hp = 745.7 W
-    |value| value / 745.7   // from_base: 1 W = 0.00134 hp
+    "745.7",                       // 1 hp = 745.7 W
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // BTU per hour (BTU/h)
@@ -115,8 +161,8 @@ impl_power_unit!(
     BTUPerHour, 
     "btu per hour", 
     "BTU/h", 
-    |value| value * 0.29307107,  // to_base: 1 BTU/h = 0.29307107 W
-    |value| value / 0.29307107   // from_base: 1 W = 3.412142 BTU/h
+    "1055.05585262/3600",          // 1 BTU/h = 1,055.05585262 J per 3600 s
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Foot-pound per second (ft⋅lb/s)
@@ -124,8 +170,8 @@ impl_power_unit!(
     FootPoundPerSecond, 
     "foot pound per second", 
     "ft⋅lb/s", 
-    |value| value * 1.35582,  // to_base: 1 ft⋅lb/s = 1.35582 W
-    |value| value / 1.35582   // from_base: 1 W = 0.737562 ft⋅lb/s
+    "1.3558179483314004",          // 1 ft⋅lb/s = 1.3558179483314004 W (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Calorie per second (cal/s)
@@ -133,8 +179,8 @@ impl_power_unit!(
     CaloriePerSecond, 
     "calorie per second", 
     "cal/s", 
-    |value| value * 4.184,  // to_base: 1 cal/s = 4.184 W
-    |value| value / 4.184   // from_base: 1 W = 0.239 cal/s
+    "4.184",                       // 1 cal/s = 4.184 W
+    &[UnitSystem::Metric]
 );
 
 // Joule per second (J/s) - same as Watt
@@ -142,8 +188,8 @@ impl_power_unit!(
     JoulePerSecond, 
     "joule per second", 
     "J/s", 
-    |value| value,  // to_base: 1 J/s = 1 W
-    |value| value   // from_base: 1 W = 1 J/s
+    "1",                           // 1 J/s = 1 W
+    &[UnitSystem::Si]
 );
 
 // Kilocalorie per hour (kcal/h)
@@ -151,6 +197,6 @@ impl_power_unit!(
     KilocaloriePerHour, 
     "kilocalorie per hour", 
     "kcal/h", 
-    |value| value * 1.163,  // to_base: 1 kcal/h = 1.163 W
-    |value| value / 1.163   // from_base: 1 W = 0.860 kcal/h
+    "4184/3600",                   // 1 kcal/h = 4,184 J per 3600 s
+    &[UnitSystem::Metric]
 ); 
\ No newline at end of file
@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The pressure category for units of pressure measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,27 @@ impl UnitCategory for PressureCategory {
     fn description(&self) -> &'static str {
         "Units of pressure measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        // M·L⁻¹·T⁻² (force per area)
+        Some(Dimension::new([-1, 1, -2, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("Pa")
+    }
 }
 
-// Shared implementation for all pressure units
+impl LinearCategory for PressureCategory {}
+
+// Shared implementation for all pressure units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_pressure_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_pressure_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(PressureCategory);
         
@@ -31,6 +52,18 @@ macro_rules! impl_pressure_unit {
             pub fn new() -> Self {
                 Self(PressureCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +88,23 @@ macro_rules! impl_pressure_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +115,9 @@ impl_pressure_unit!(
     Pascal, 
     "pascal", 
     "Pa", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // hPa, kPa, GPa and the rest are generated
 );
 
 // Kilopascal (kPa)
@@ -79,8 +125,8 @@ impl_pressure_unit!(
     Kilopascal, 
     "kilopascal", 
     "kPa", 
-    |value| value * 1000.0,  // to_base: 1 kPa = 1,000 Pa
-    |value| value / 1000.0   // from_base: 1 Pa = 0.001 kPa
+    "1000",                        // 1 kPa = 1,000 Pa
+    &[UnitSystem::Si]
 );
 
 // Megapascal (MPa)
@@ -88,8 +134,8 @@ impl_pressure_unit!(
     Megapascal, 
     "megapascal", 
     "MPa", 
-    |value| value * 1_000_000.0,  // to_base: 1 MPa = 1,000,000 Pa
-    |value| value / 1_000_000.0   // from_base: 1 Pa = 0.000001 MPa
+    "1000000",                     // 1 MPa = 1,000,000 Pa
+    &[UnitSystem::Si]
 );
 
 // Bar (bar)
@@ -97,8 +143,9 @@ impl_pressure_unit!(
     Bar, 
     "bar", 
     "bar", 
-    |value| value * 100_000.0,  // to_base: 1 bar = 100,000 Pa
-    |value| value / 100_000.0   // from_base: 1 Pa = 0.00001 bar
+    "100000",                      // 1 bar = 100,000 Pa
+    &[UnitSystem::Metric],
+    Prefixes::Metric               // mbar, kbar and the rest are generated
 );
 
 // Millibar (mbar)
@@ -106,8 +153,8 @@ impl_pressure_unit!(
     Millibar, 
     "millibar", 
     "mbar", 
-    |value| value * 100.0,  // to_base: 1 mbar = 100 Pa
-    |value| value / 100.0   // from_base: 1 Pa = 0.01 mbar
+    "100",                         // 1 mbar = 100 Pa
+    &[UnitSystem::Metric]
 );
 
 // Atmosphere (atm)
@@ -115,8 +162,8 @@ impl_pressure_unit!(
     Atmosphere, 
     "atmosphere", 
     "atm", 
-    |value| value * 101_325.0,  // to_base: 1 atm = 101,325 Pa
-    |value| value / 101_325.0   // from_base: 1 Pa = 0.00000986923 atm
+    "101325",                      // 1 atm = 101,325 Pa
+    &[]
 );
 
 // Pounds per square inch (psi)
@@ -124,8 +171,8 @@ impl_pressure_unit!(
     PoundsPerSquareInch, 
     "pounds per square inch", 
     "psi", 
-    |value| value * 6894.76,  // to_base: 1 psi = 6,894.76 Pa
-    |value| value / 6894.76   // from_base: 1 Pa = 0.000145038 psi
+    "4.4482216152605/0.00064516",  // 1 psi = 1 lbf per in² (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Torr (Torr) - almost equivalent to mmHg
@@ -133,8 +180,8 @@ impl_pressure_unit!(
     Torr, 
     "torr", 
     "Torr", 
-    |value| value * 133.322,  // to_base: 1 Torr = 133.322 Pa
-    |value| value / 133.322   // from_base: 1 Pa = 0.0075006 Torr
+    "101325/760",                  // 1 Torr = 1/760 atm (exactly)
+    &[]
 );
 
 // Millimeters of mercury (mmHg)
@@ -142,8 +189,8 @@ impl_pressure_unit!(
     MillimetersOfMercury, 
     "millimeters of mercury", 
     "mmHg", 
-    |value| value * 133.322,  // to_base: 1 mmHg = 133.322 Pa
-    |value| value / 133.322   // from_base: 1 Pa = 0.0075006 mmHg
+    "133.322387415",               // 1 mmHg = 133.322387415 Pa (conventional)
+    &[UnitSystem::Metric]
 );
 
 // Inches of mercury (inHg)
@@ -151,6 +198,6 @@ impl_pressure_unit!(
     InchesOfMercury, 
     "inches of mercury", 
     "inHg", 
-    |value| value * 3386.39,  // to_base: 1 inHg = 3,386.39 Pa
-    |value| value / 3386.39   // from_base: 1 Pa = 0.0002953 inHg
+    "3386.389",                    // 1 inHg = 3,386.389 Pa (conventional)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 ); 
\ No newline at end of file
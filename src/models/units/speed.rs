@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The speed category for units of speed/velocity measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for SpeedCategory {
     fn description(&self) -> &'static str {
         "Units of speed/velocity measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::new([1, 0, -1, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("m/s")
+    }
 }
 
-// Shared implementation for all speed units
+impl LinearCategory for SpeedCategory {}
+
+// Shared implementation for all speed units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_speed_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_speed_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(SpeedCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_speed_unit {
             pub fn new() -> Self {
                 Self(SpeedCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_speed_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,8 @@ impl_speed_unit!(
     MeterPerSecond, 
     "meter per second", 
     "m/s", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si]
 );
 
 // Kilometer per hour (km/h)
@@ -79,8 +123,8 @@ impl_speed_unit!(
     KilometerPerHour, 
     "kilometer per hour", 
     "km/h", 
-    |value| value * 0.277778,  // to_base: 1 km/h = 0.277778 m/s
-    |value| value / 0.277778   // from_base: 1 m/s = 3.6 km/h
+    "1000/3600",                   // 1 km/h = 1000 m per 3600 s (exactly)
+    &[UnitSystem::Metric]
 );
 
 // Miles per hour (mph)
@@ -88,8 +132,8 @@ impl_speed_unit!(
     MilePerHour, 
     "mile per hour", 
     "mph", 
-    |value| value * 0.44704,  // to_base: 1 mph = 0.44704 m/s
-    |value| value / 0.44704   // from_base: 1 m/s = 2.23694 mph
+    "0.44704",                     // 1 mph = 0.44704 m/s
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Knot (kn)
@@ -97,8 +141,8 @@ impl_speed_unit!(
     Knot, 
     "knot", 
     "kn", 
-    |value| value * 0.514444,  // to_base: 1 knot = 0.514444 m/s
-    |value| value / 0.514444   // from_base: 1 m/s = 1.94384 knots
+    "1852/3600",                   // 1 knot = 1852 m per 3600 s (exactly)
+    &[]
 );
 
 // Foot per second (ft/s)
@@ -106,8 +150,8 @@ impl_speed_unit!(
     FootPerSecond, 
     "foot per second", 
     "ft/s", 
-    |value| value * 0.3048,  // to_base: 1 ft/s = 0.3048 m/s
-    |value| value / 0.3048   // from_base: 1 m/s = 3.28084 ft/s
+    "0.3048",                      // 1 ft/s = 0.3048 m/s
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Centimeter per second (cm/s)
@@ -115,8 +159,8 @@ impl_speed_unit!(
     CentimeterPerSecond, 
     "centimeter per second", 
     "cm/s", 
-    |value| value * 0.01,  // to_base: 1 cm/s = 0.01 m/s
-    |value| value / 0.01   // from_base: 1 m/s = 100 cm/s
+    "0.01",                        // 1 cm/s = 0.01 m/s
+    &[UnitSystem::Si]
 );
 
 // Mach (M) - at sea level, standard conditions
@@ -124,6 +168,6 @@ impl_speed_unit!(
     Mach, 
     "mach", 
     "M", 
-    |value| value * 343.0,  // to_base: Mach 1 = 343 m/s (approx. at 20°C, sea level)
-    |value| value / 343.0   // from_base: 1 m/s = 0.00291545 Mach
+    "343",                         // Mach 1 = 343 m/s (approx. at 20°C, sea level)
+    &[]
 ); 
\ No newline at end of file
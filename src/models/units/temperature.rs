@@ -1,5 +1,11 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use std::ops::{Add, Sub};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory, Measurement};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The temperature category for units of temperature measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,17 +25,77 @@ impl UnitCategory for TemperatureCategory {
     fn description(&self) -> &'static str {
         "Units of temperature measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::TEMPERATURE)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("K")
+    }
+}
+
+/// The category for temperature intervals: differences between temperatures,
+/// such as a rise of 10 °F, rather than temperatures themselves
+#[derive(Debug, Clone, Copy)]
+pub struct TemperatureIntervalCategory;
+
+impl Display for TemperatureIntervalCategory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Temperature interval")
+    }
+}
+
+impl UnitCategory for TemperatureIntervalCategory {
+    fn name(&self) -> &'static str {
+        "temperature_interval"
+    }
+    
+    fn description(&self) -> &'static str {
+        "Units of temperature difference"
+    }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::TEMPERATURE)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("ΔK")
+    }
 }
 
-// Shared implementation for all temperature units
+impl LinearCategory for TemperatureIntervalCategory {}
+
+// Shared implementation for all temperature units, each defined by an exact factor and
+// offset relative to the base unit, written as decimals or fractions, the unit
+// systems it belongs to, and optionally the prefixes the registry should
+// generate units for
 macro_rules! impl_temperature_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $offset:expr, $systems:expr) => {
+        impl_temperature_unit!($unit, $name, $symbol, $factor, $offset, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $offset:expr, $systems:expr, $prefixes:expr) => {
+        impl_temperature_unit!(@unit TemperatureCategory, $unit, $name, $symbol, $factor, $offset, $systems, $prefixes);
+    };
+    (@unit $category:ident, $unit:ident, $name:expr, $symbol:expr, $factor:expr, $offset:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
-        pub struct $unit(TemperatureCategory);
+        pub struct $unit($category);
         
         impl $unit {
             pub fn new() -> Self {
-                Self(TemperatureCategory)
+                Self($category)
+            }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, $offset));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
             }
         }
         
@@ -40,7 +106,7 @@ macro_rules! impl_temperature_unit {
         }
         
         impl Unit for $unit {
-            type Category = TemperatureCategory;
+            type Category = $category;
             
             fn symbol(&self) -> &'static str {
                 $symbol
@@ -55,11 +121,80 @@ macro_rules! impl_temperature_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
+            }
+        }
+    };
+}
+
+// Temperature interval units, each defined by its exact factor relative to the
+// kelvin interval and the unit systems it belongs to. Intervals have no offset:
+// a rise of 1 °C is a rise of 1 K.
+macro_rules! impl_temperature_interval_unit {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_temperature_unit!(
+            @unit TemperatureIntervalCategory, $unit, $name, $symbol, $factor, "0", $systems, Prefixes::None
+        );
+    };
+}
+
+// Arithmetic on absolute temperatures, which form an affine space over the
+// intervals: the difference of two temperatures is an interval on the scale of
+// the first, and adding or subtracting an interval gives a temperature on the
+// same scale. Adding two temperatures has no meaning, so isn't implemented.
+macro_rules! impl_temperature_arithmetic {
+    ($($scale:ident => $interval:ident),*) => {
+        impl_temperature_arithmetic!(@each [$($scale),*] $($scale => $interval),*);
+    };
+    (@each $scales:tt $($scale:ident => $interval:ident),*) => {
+        $(impl_temperature_arithmetic!(@scale $scale => $interval, $scales);)*
+    };
+    (@scale $scale:ident => $interval:ident, [$($other:ident),*]) => {
+        $(
+            impl Sub<Measurement<$other>> for Measurement<$scale> {
+                type Output = Measurement<$interval>;
+                
+                fn sub(self, other: Measurement<$other>) -> Self::Output {
+                    let value = other.value_in(self.unit());
+                    let uncertainty = self.uncertainty_of_sum(value, other.uncertainty_in(self.unit()), -1.0);
+                    Measurement::with_uncertainty_of(self.value() - value, $interval::new(), uncertainty)
+                }
+            }
+        )*
+        
+        impl<V: Unit<Category = TemperatureIntervalCategory>> Add<Measurement<V>> for Measurement<$scale> {
+            type Output = Measurement<$scale>;
+            
+            fn add(self, interval: Measurement<V>) -> Self::Output {
+                let value = interval.value_in(&$interval::new());
+                let uncertainty = self.uncertainty_of_sum(value, interval.uncertainty_in(&$interval::new()), 1.0);
+                Measurement::with_uncertainty_of(self.value() + value, *self.unit(), uncertainty)
+            }
+        }
+        
+        impl<V: Unit<Category = TemperatureIntervalCategory>> Sub<Measurement<V>> for Measurement<$scale> {
+            type Output = Measurement<$scale>;
+            
+            fn sub(self, interval: Measurement<V>) -> Self::Output {
+                let value = interval.value_in(&$interval::new());
+                let uncertainty = self.uncertainty_of_sum(value, interval.uncertainty_in(&$interval::new()), -1.0);
+                Measurement::with_uncertainty_of(self.value() - value, *self.unit(), uncertainty)
             }
         }
     };
@@ -70,8 +205,9 @@ impl_temperature_unit!(
     Kelvin, 
     "kelvin", 
     "K", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1", "0",                      // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // mK, μK and the rest are generated
 );
 
 // Celsius (°C)
@@ -79,8 +215,8 @@ impl_temperature_unit!(
     Celsius, 
     "celsius", 
     "°C", 
-    |value| value + 273.15,  // to_base: °C = K - 273.15, so K = °C + 273.15
-    |value| value - 273.15   // from_base: °C = K - 273.15
+    "1", "273.15",                 // K = °C + 273.15
+    &[UnitSystem::Si]
 );
 
 // Fahrenheit (°F)
@@ -88,8 +224,8 @@ impl_temperature_unit!(
     Fahrenheit, 
     "fahrenheit", 
     "°F", 
-    |value| (value - 32.0) * 5.0/9.0 + 273.15,  // to_base: °F -> K
-    |value| (value - 273.15) * 9.0/5.0 + 32.0   // from_base: K -> °F
+    "5/9", "45967/180",            // K = (°F + 459.67) × 5/9
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Rankine (°R)
@@ -97,8 +233,8 @@ impl_temperature_unit!(
     Rankine, 
     "rankine", 
     "°R", 
-    |value| value * 5.0/9.0,  // to_base: K = °R × 5/9
-    |value| value * 9.0/5.0   // from_base: °R = K × 9/5
+    "5/9", "0",                    // K = °R × 5/9
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Réaumur (°Ré)
@@ -106,6 +242,59 @@ impl_temperature_unit!(
     Reaumur, 
     "reaumur", 
     "°Ré", 
-    |value| value * 5.0/4.0 + 273.15,  // to_base: °Ré to K
-    |value| (value - 273.15) * 4.0/5.0  // from_base: K to °Ré
-); 
\ No newline at end of file
+    "5/4", "273.15",               // K = °Ré × 5/4 + 273.15
+    &[]
+); 
+
+// Base interval unit: kelvin interval (ΔK)
+impl_temperature_interval_unit!(
+    KelvinInterval, 
+    "delta kelvin", 
+    "ΔK", 
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si]
+);
+
+// Celsius interval (Δ°C)
+impl_temperature_interval_unit!(
+    CelsiusInterval, 
+    "delta celsius", 
+    "Δ°C", 
+    "1",                           // the same size as the kelvin
+    &[UnitSystem::Si]
+);
+
+// Fahrenheit interval (Δ°F)
+impl_temperature_interval_unit!(
+    FahrenheitInterval, 
+    "delta fahrenheit", 
+    "Δ°F", 
+    "5/9",                         // ΔK = Δ°F × 5/9
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
+);
+
+// Rankine interval (Δ°R)
+impl_temperature_interval_unit!(
+    RankineInterval, 
+    "delta rankine", 
+    "Δ°R", 
+    "5/9",                         // the same size as the fahrenheit
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
+);
+
+// Réaumur interval (Δ°Ré)
+impl_temperature_interval_unit!(
+    ReaumurInterval, 
+    "delta reaumur", 
+    "Δ°Ré", 
+    "5/4",                         // ΔK = Δ°Ré × 5/4
+    &[]
+);
+
+impl_temperature_arithmetic!(
+    Kelvin => KelvinInterval,
+    Celsius => CelsiusInterval,
+    Fahrenheit => FahrenheitInterval,
+    Rankine => RankineInterval,
+    Reaumur => ReaumurInterval
+);
@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The time category for units of time measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for TimeCategory {
     fn description(&self) -> &'static str {
         "Units of time measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::TIME)
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("s")
+    }
 }
 
-// Shared implementation for all time units
+impl LinearCategory for TimeCategory {}
+
+// Shared implementation for all time units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_time_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_time_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(TimeCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_time_unit {
             pub fn new() -> Self {
                 Self(TimeCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_time_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,9 @@ impl_time_unit!(
     Second, 
     "second", 
     "s", 
-    |value| value,           // to_base: direct conversion (base unit)
-    |value| value            // from_base: direct conversion (base unit)
+    "1",                           // direct conversion (base unit)
+    &[UnitSystem::Si],
+    Prefixes::Metric               // ms, ks, ps and the rest are generated
 );
 
 // Millisecond (ms)
@@ -79,8 +124,8 @@ impl_time_unit!(
     Millisecond, 
     "millisecond", 
     "ms", 
-    |value| value * 0.001,  // to_base: 1 ms = 0.001 s
-    |value| value / 0.001   // from_base: 1 s = 1000 ms
+    "0.001",                       // 1 ms = 0.001 s
+    &[UnitSystem::Si]
 );
 
 // Microsecond (μs)
@@ -88,8 +133,8 @@ impl_time_unit!(
     Microsecond, 
     "microsecond", 
     "μs", 
-    |value| value * 0.000001,  // to_base: 1 μs = 0.000001 s
-    |value| value / 0.000001   // from_base: 1 s = 1,000,000 μs
+    "0.000001",                    // 1 μs = 0.000001 s
+    &[UnitSystem::Si]
 );
 
 // Nanosecond (ns)
@@ -97,8 +142,8 @@ impl_time_unit!(
     Nanosecond, 
     "nanosecond", 
     "ns", 
-    |value| value * 0.000000001,  // to_base: 1 ns = 0.000000001 s
-    |value| value / 0.000000001   // from_base: 1 s = 1,000,000,000 ns
+    "0.000000001",                 // 1 ns = 0.000000001 s
+    &[UnitSystem::Si]
 );
 
 // Minute (min)
@@ -106,8 +151,8 @@ impl_time_unit!(
     Minute, 
     "minute", 
     "min", 
-    |value| value * 60.0,  // to_base: 1 min = 60 s
-    |value| value / 60.0   // from_base: 1 s = 1/60 min
+    "60",                          // 1 min = 60 s
+    &[UnitSystem::Metric]
 );
 
 // Hour (h)
@@ -115,8 +160,8 @@ impl_time_unit!(
     Hour, 
     "hour", 
     "h", 
-    |value| value * 3600.0,  // to_base: 1 h = 3600 s
-    |value| value / 3600.0   // from_base: 1 s = 1/3600 h
+    "3600",                        // 1 h = 3600 s
+    &[UnitSystem::Metric]
 );
 
 // Day (d)
@@ -124,8 +169,8 @@ impl_time_unit!(
     Day, 
     "day", 
     "d", 
-    |value| value * 86400.0,  // to_base: 1 d = 86400 s
-    |value| value / 86400.0   // from_base: 1 s = 1/86400 d
+    "86400",                       // 1 d = 86400 s
+    &[UnitSystem::Metric]
 );
 
 // Week (wk)
@@ -133,8 +178,8 @@ impl_time_unit!(
     Week, 
     "week", 
     "wk", 
-    |value| value * 604800.0,  // to_base: 1 week = 604800 s
-    |value| value / 604800.0   // from_base: 1 s = 1/604800 week
+    "604800",                      // 1 week = 604800 s
+    &[]
 );
 
 // Month (avg, mo)
@@ -142,8 +187,8 @@ impl_time_unit!(
     Month, 
     "month", 
     "mo", 
-    |value| value * 2628000.0,  // to_base: 1 month ≈ 30.4375 days ≈ 2628000 s
-    |value| value / 2628000.0   // from_base: 1 s = 1/2628000 month
+    "2628000",                     // 1 month ≈ 30.4375 days ≈ 2628000 s
+    &[]
 );
 
 // Year (yr)
@@ -151,8 +196,8 @@ impl_time_unit!(
     Year, 
     "year", 
     "yr", 
-    |value| value * 31536000.0,  // to_base: 1 year = 365 days = 31536000 s
-    |value| value / 31536000.0   // from_base: 1 s = 1/31536000 year
+    "31536000",                    // 1 year = 365 days = 31536000 s
+    &[]
 );
 
 // Decade (dec)
@@ -160,8 +205,8 @@ impl_time_unit!(
     Decade, 
     "decade", 
     "dec", 
-    |value| value * 315360000.0,  // to_base: 1 decade = 10 years = 315360000 s
-    |value| value / 315360000.0   // from_base: 1 s = 1/315360000 decade
+    "315360000",                   // 1 decade = 10 years = 315360000 s
+    &[]
 );
 
 // Century (c)
@@ -169,6 +214,6 @@ impl_time_unit!(
     Century, 
     "century", 
     "c", 
-    |value| value * 3153600000.0,  // to_base: 1 century = 100 years = 3153600000 s
-    |value| value / 3153600000.0   // from_base: 1 s = 1/3153600000 century
+    "3153600000",                  // 1 century = 100 years = 3153600000 s
+    &[]
 ); 
\ No newline at end of file
@@ -1,5 +1,10 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use crate::models::unit::{Unit, UnitCategory};
+use crate::models::unit::{Unit, UnitCategory, LinearCategory};
+use crate::models::dimension::Dimension;
+use crate::models::exact::LinearConversion;
+use crate::models::prefix::Prefixes;
+use crate::models::system::UnitSystem;
+use once_cell::sync::Lazy;
 
 /// The volume category for units of volume measurement
 #[derive(Debug, Clone, Copy)]
@@ -19,11 +24,26 @@ impl UnitCategory for VolumeCategory {
     fn description(&self) -> &'static str {
         "Units of volume measurement"
     }
+    
+    fn dimension(&self) -> Option<Dimension> {
+        Some(Dimension::new([3, 0, 0, 0, 0, 0, 0]))
+    }
+    
+    fn base_unit(&self) -> Option<&'static str> {
+        Some("m³")
+    }
 }
 
-// Shared implementation for all volume units
+impl LinearCategory for VolumeCategory {}
+
+// Shared implementation for all volume units, each defined by its exact factor
+// relative to the base unit, written as a decimal or fraction, the unit systems
+// it belongs to, and optionally the prefixes the registry should generate units for
 macro_rules! impl_volume_unit {
-    ($unit:ident, $name:expr, $symbol:expr, $to_base:expr, $from_base:expr) => {
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr) => {
+        impl_volume_unit!($unit, $name, $symbol, $factor, $systems, Prefixes::None);
+    };
+    ($unit:ident, $name:expr, $symbol:expr, $factor:expr, $systems:expr, $prefixes:expr) => {
         #[derive(Debug, Clone, Copy)]
         pub struct $unit(VolumeCategory);
         
@@ -31,6 +51,18 @@ macro_rules! impl_volume_unit {
             pub fn new() -> Self {
                 Self(VolumeCategory)
             }
+            
+            // Parsed from the definition on first use
+            fn conversion() -> &'static LinearConversion {
+                static CONVERSION: Lazy<LinearConversion> = Lazy::new(|| LinearConversion::parse($factor, "0"));
+                &CONVERSION
+            }
+        }
+        
+        impl Default for $unit {
+            fn default() -> Self {
+                Self::new()
+            }
         }
         
         impl Display for $unit {
@@ -55,11 +87,23 @@ macro_rules! impl_volume_unit {
             }
             
             fn to_base(&self, value: f64) -> f64 {
-                $to_base(value)
+                Self::conversion().to_base_f64(value)
             }
             
             fn from_base(&self, value: f64) -> f64 {
-                $from_base(value)
+                Self::conversion().from_base_f64(value)
+            }
+            
+            fn exact(&self) -> Option<&LinearConversion> {
+                Some(Self::conversion())
+            }
+            
+            fn prefixes(&self) -> Prefixes {
+                $prefixes
+            }
+            
+            fn systems(&self) -> &'static [UnitSystem] {
+                $systems
             }
         }
     };
@@ -70,8 +114,8 @@ impl_volume_unit!(
     CubicMeter, 
     "cubic meter", 
     "m³", 
-    |value| value,                 // to_base: 1 m³ = 1 m³ (base unit)
-    |value| value                  // from_base: 1 m³ = 1 m³ (base unit)
+    "1",                           // 1 m³ = 1 m³ (base unit)
+    &[UnitSystem::Si]
 );
 
 // Liter (L)
@@ -79,8 +123,9 @@ impl_volume_unit!(
     Liter, 
     "liter", 
     "L", 
-    |value| value / 1000.0,        // to_base: 1 L = 0.001 m³
-    |value| value * 1000.0         // from_base: 1 m³ = 1000 L
+    "0.001",                       // 1 L = 0.001 m³
+    &[UnitSystem::Metric],
+    Prefixes::Metric               // mL, hL, μL and the rest are generated
 );
 
 // Milliliter (mL)
@@ -88,8 +133,8 @@ impl_volume_unit!(
     Milliliter, 
     "milliliter", 
     "mL", 
-    |value| value / 1_000_000.0,   // to_base: 1 mL = 0.000001 m³
-    |value| value * 1_000_000.0    // from_base: 1 m³ = 1,000,000 mL
+    "0.000001",                    // 1 mL = 0.000001 m³
+    &[UnitSystem::Metric]
 );
 
 // Cubic Centimeter (cm³)
@@ -97,8 +142,8 @@ impl_volume_unit!(
     CubicCentimeter, 
     "cubic centimeter", 
     "cm³", 
-    |value| value / 1_000_000.0,   // to_base: 1 cm³ = 0.000001 m³
-    |value| value * 1_000_000.0    // from_base: 1 m³ = 1,000,000 cm³
+    "0.000001",                    // 1 cm³ = 0.000001 m³
+    &[UnitSystem::Si]
 );
 
 // Cubic Decimeter (dm³)
@@ -106,8 +151,8 @@ impl_volume_unit!(
     CubicDecimeter, 
     "cubic decimeter", 
     "dm³", 
-    |value| value / 1000.0,        // to_base: 1 dm³ = 0.001 m³ 
-    |value| value * 1000.0         // from_base: 1 m³ = 1000 dm³
+    "0.001",                       // 1 dm³ = 0.001 m³
+    &[UnitSystem::Si]
 );
 
 // US Gallon (gal)
@@ -115,8 +160,8 @@ impl_volume_unit!(
     USGallon, 
     "US gallon", 
     "gal", 
-    |value| value * 0.00378541,    // to_base: 1 gal = 0.00378541 m³
-    |value| value / 0.00378541     // from_base: 1 m³ = ~264.172 gal
+    "0.003785411784",              // 1 gal = 231 in³ = 0.003785411784 m³ (exactly)
+    &[UnitSystem::UsCustomary]
 );
 
 // UK/Imperial Gallon (UK gal)
@@ -124,8 +169,8 @@ impl_volume_unit!(
     UKGallon, 
     "UK gallon", 
     "UK gal", 
-    |value| value * 0.00454609,    // to_base: 1 UK gal = 0.00454609 m³
-    |value| value / 0.00454609     // from_base: 1 m³ = ~219.969 UK gal
+    "0.00454609",                  // 1 UK gal = 0.00454609 m³
+    &[UnitSystem::Imperial]
 );
 
 // US Quart (qt)
@@ -133,8 +178,8 @@ impl_volume_unit!(
     USQuart, 
     "US quart", 
     "qt", 
-    |value| value * 0.000946353,   // to_base: 1 qt = 0.000946353 m³
-    |value| value / 0.000946353    // from_base: 1 m³ = ~1056.69 qt
+    "0.000946352946",              // 1 qt = 1/4 gal = 0.000946352946 m³
+    &[UnitSystem::UsCustomary]
 );
 
 // US Pint (pt)
@@ -142,8 +187,8 @@ impl_volume_unit!(
     USPint, 
     "US pint", 
     "pt", 
-    |value| value * 0.000473176,   // to_base: 1 pt = 0.000473176 m³
-    |value| value / 0.000473176    // from_base: 1 m³ = ~2113.38 pt
+    "0.000473176473",              // 1 pt = 1/8 gal = 0.000473176473 m³
+    &[UnitSystem::UsCustomary]
 );
 
 // US Cup
@@ -151,8 +196,8 @@ impl_volume_unit!(
     USCup, 
     "US cup", 
     "cup", 
-    |value| value * 0.000236588,   // to_base: 1 cup = 0.000236588 m³
-    |value| value / 0.000236588    // from_base: 1 m³ = ~4226.75 cup
+    "0.0002365882365",             // 1 cup = 1/16 gal = 0.0002365882365 m³
+    &[UnitSystem::UsCustomary]
 );
 
 // US Fluid Ounce (fl oz)
@@ -160,8 +205,8 @@ impl_volume_unit!(
     USFluidOunce, 
     "US fluid ounce", 
     "fl oz", 
-    |value| value * 0.0000295735,  // to_base: 1 fl oz = 0.0000295735 m³
-    |value| value / 0.0000295735   // from_base: 1 m³ = ~33814 fl oz
+    "0.0000295735295625",          // 1 fl oz = 1/128 gal = 0.0000295735295625 m³
+    &[UnitSystem::UsCustomary]
 );
 
 // Cubic Inch (in³)
@@ -169,8 +214,8 @@ impl_volume_unit!(
     CubicInch, 
     "cubic inch", 
     "in³", 
-    |value| value * 0.0000163871,  // to_base: 1 in³ = 0.0000163871 m³
-    |value| value / 0.0000163871   // from_base: 1 m³ = ~61023.7 in³
+    "0.000016387064",              // 1 in³ = 0.000016387064 m³ (exactly)
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Cubic Foot (ft³)
@@ -178,8 +223,8 @@ impl_volume_unit!(
     CubicFoot, 
     "cubic foot", 
     "ft³", 
-    |value| value * 0.0283168,     // to_base: 1 ft³ = 0.0283168 m³ (1728 in³)
-    |value| value / 0.0283168      // from_base: 1 m³ = ~35.3147 ft³
+    "0.028316846592",              // 1 ft³ = 1728 in³ = 0.028316846592 m³
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 );
 
 // Cubic Yard (yd³)
@@ -187,6 +232,6 @@ impl_volume_unit!(
     CubicYard, 
     "cubic yard", 
     "yd³", 
-    |value| value * 0.764555,      // to_base: 1 yd³ = 0.764555 m³
-    |value| value / 0.764555       // from_base: 1 m³ = ~1.30795 yd³
+    "0.764554857984",              // 1 yd³ = 27 ft³ = 0.764554857984 m³
+    &[UnitSystem::Imperial, UnitSystem::UsCustomary]
 ); 
\ No newline at end of file
@@ -0,0 +1,445 @@
+use crate::errors::ApiError;
+use crate::models::{CompoundUnit, UnitRegistry};
+
+/// Words that separate the quantity from the target unit
+const SEPARATORS: [&str; 6] = ["to", "in", "into", "as", "->", "→"];
+
+/// Words that separate the ends of a range, besides a hyphen
+const RANGE_SEPARATORS: [&str; 4] = ["to", "–", "—", "~"];
+
+/// Words that start an open-ended range, bounded below or above
+const LOWER_BOUNDS: [&str; 2] = ["≥", ">="];
+const UPPER_BOUNDS: [&str; 2] = ["≤", "<="];
+
+/// Characters that form words of their own even when written against a number,
+/// as in "68–77" or "≥5"
+const RANGE_MARKS: [char; 5] = ['–', '—', '~', '≥', '≤'];
+
+/// A value with its unit, as written in a query
+#[derive(Debug, Clone)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: CompoundUnit,
+}
+
+/// A range of values in one unit, as written, either end of which may be open
+#[derive(Debug, Clone, PartialEq)]
+pub struct Range {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub unit: String,
+}
+
+/// A parsed free-text conversion query such as "5 ft 3 in to m"
+///
+/// Quantities written next to each other ("5 ft 3 in") or joined with `+` are
+/// summed; quantities after a `-` are subtracted by negating their value.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub quantities: Vec<Quantity>,
+    pub target: CompoundUnit,
+}
+
+impl Query {
+    /// Evaluates the query, returning the total in the target unit
+    pub fn evaluate(&self) -> Result<f64, ApiError> {
+        if let [quantity] = self.quantities.as_slice() {
+            return quantity.unit.convert(quantity.value, &self.target);
+        }
+
+        if let Some(quantity) = self.quantities.iter().find(|q| q.unit.has_offset()) {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot add quantities in '{}' since the unit has an offset",
+                quantity.unit.expression()
+            )));
+        }
+
+        let total: f64 = self.quantities.iter()
+            .map(|q| q.unit.to_base(q.value))
+            .sum();
+
+        Ok(self.target.from_base(total))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Plus,
+    Minus,
+    Word,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    // Character position in the query, for error reporting
+    position: usize,
+    // Byte offsets in the query, for slicing out unit expressions
+    start: usize,
+    end: usize,
+}
+
+fn parse_error(message: String, position: usize, token: &str) -> ApiError {
+    ApiError::ParseError { message, position, token: token.to_string() }
+}
+
+/// Splits a query into numbers, `+`/`-` operators and words
+fn tokenize(input: &str) -> Result<Vec<Token>, ApiError> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let byte_at = |i: usize| chars.get(i).map_or(input.len(), |&(b, _)| b);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i].1;
+        let next = chars.get(i + 1).map(|&(_, n)| n);
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = if c == '+' {
+            i += 1;
+            TokenKind::Plus
+        } else if (c == '-' || c == '−') && next != Some('>') {
+            i += 1;
+            TokenKind::Minus
+        } else if c.is_ascii_digit() || (c == '.' && next.is_some_and(|n| n.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].1.is_ascii_digit() || chars[i].1 == '.') {
+                i += 1;
+            }
+
+            // Scientific notation, only when digits follow the exponent marker
+            if i < chars.len() && (chars[i].1 == 'e' || chars[i].1 == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j].1 == '+' || chars[j].1 == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].1.is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].1.is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+
+            let text = &input[byte_at(start)..byte_at(i)];
+            let value = text.parse::<f64>().map_err(|_| parse_error(
+                format!("Invalid number '{}'", text), start, text
+            ))?;
+            if !value.is_finite() {
+                return Err(parse_error(format!("Number '{}' is too large", text), start, text));
+            }
+            TokenKind::Number(value)
+        } else if RANGE_MARKS.contains(&c) {
+            i += 1;
+            TokenKind::Word
+        } else if (c == '<' || c == '>') && next == Some('=') {
+            i += 2;
+            TokenKind::Word
+        } else {
+            while i < chars.len() && !chars[i].1.is_whitespace() && !RANGE_MARKS.contains(&chars[i].1) {
+                i += 1;
+            }
+            TokenKind::Word
+        };
+
+        tokens.push(Token {
+            kind,
+            text: input[byte_at(start)..byte_at(i)].to_string(),
+            position: start,
+            start: byte_at(start),
+            end: byte_at(i),
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Parses the unit expression spanned by a run of word tokens
+fn parse_unit(input: &str, words: &[Token], registry: &UnitRegistry) -> Result<CompoundUnit, ApiError> {
+    let first = &words[0];
+    let span = &input[first.start..words[words.len() - 1].end];
+
+    CompoundUnit::parse(span, registry).map_err(|err| {
+        let message = match err {
+            ApiError::NotFound(message) | ApiError::BadRequest(message)
+            | ApiError::UnitNotFound { message, .. } => message,
+            other => other.to_string(),
+        };
+        
+        // Positions within the unit expression are given as positions in the query
+        let located = message.find(" at position ").and_then(|at| {
+            let rest = &message[at + " at position ".len()..];
+            let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+            let offset: usize = rest[..digits].parse().ok()?;
+            rest[digits..].starts_with(" in unit expression ")
+                .then(|| (at, first.position + span.get(..offset).map_or(0, |s| s.chars().count())))
+        });
+        match located {
+            Some((at, position)) => parse_error(format!("{} at position {}", &message[..at], position), position, span),
+            None => parse_error(message, first.position, span),
+        }
+    })
+}
+
+/// Parses a free-text query such as "72.5 °F in K", "3 mi + 200 yd to km"
+/// or "5 ft 3 in to m", resolving units through the registry
+///
+/// Errors are reported as `ApiError::ParseError` with the character position
+/// and text of the offending token.
+pub fn parse_query(input: &str, registry: &UnitRegistry) -> Result<Query, ApiError> {
+    let tokens = tokenize(input)?;
+
+    // The separator is the last "to"/"in" followed by a valid unit, since
+    // "in" may also be the unit inch ("3 in in cm")
+    let mut first_error = None;
+    let mut split = None;
+
+    for (index, token) in tokens.iter().enumerate().rev() {
+        let is_separator = token.kind == TokenKind::Word
+            && SEPARATORS.contains(&token.text.to_lowercase().as_str());
+        let target = &tokens[index + 1..];
+
+        if !is_separator || target.is_empty() || target.iter().any(|t| t.kind != TokenKind::Word) {
+            continue;
+        }
+
+        match parse_unit(input, target, registry) {
+            Ok(unit) => {
+                split = Some((index, unit));
+                break;
+            },
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    let (separator, target) = match (split, first_error) {
+        (Some(split), _) => split,
+        (None, Some(err)) => return Err(err),
+        (None, None) => return Err(parse_error(
+            "Expected 'to' or 'in' followed by a target unit".to_string(),
+            input.chars().count(),
+            "",
+        )),
+    };
+
+    let quantities = parse_quantities(input, &tokens[..separator], registry)?;
+
+    if let Some(quantity) = quantities.iter().find(|q| q.unit.dimension() != target.dimension()) {
+        let token = &tokens[separator + 1];
+        return Err(parse_error(
+            format!(
+                "Cannot convert '{}' ({}) to '{}' ({}): dimensions differ",
+                quantity.unit.expression(), quantity.unit.dimension(),
+                target.expression(), target.dimension()
+            ),
+            token.position,
+            target.expression(),
+        ));
+    }
+
+    // Temperatures and temperature intervals share a dimension but not a scale,
+    // and a sum that starts from a temperature is one
+    let from_temperature = quantities.iter().any(|q| q.unit.is_temperature());
+    if from_temperature != target.is_temperature() {
+        let token = &tokens[separator + 1];
+        let quantity = quantities.iter().find(|q| q.unit.is_temperature() == from_temperature).unwrap_or(&quantities[0]);
+        return Err(parse_error(
+            format!(
+                "Cannot convert '{}' to '{}': only one is a temperature, the other a temperature interval",
+                quantity.unit.expression(), target.expression()
+            ),
+            token.position,
+            target.expression(),
+        ));
+    }
+
+    Ok(Query { quantities, target })
+}
+
+/// Parses a sum of quantities, each a number followed by a unit
+fn parse_quantities(input: &str, tokens: &[Token], registry: &UnitRegistry) -> Result<Vec<Quantity>, ApiError> {
+    let mut quantities: Vec<Quantity> = Vec::new();
+    let mut sign = 1.0;
+    let mut i = 0;
+
+    if tokens.is_empty() {
+        return Err(parse_error("Expected a quantity before the target unit".to_string(), 0, ""));
+    }
+
+    while i < tokens.len() {
+        let token = &tokens[i];
+
+        match token.kind {
+            TokenKind::Plus | TokenKind::Minus => {
+                // An operator must start the query or follow a complete quantity,
+                // and must be followed by another quantity
+                let after_operator = i > 0 && matches!(tokens[i - 1].kind, TokenKind::Plus | TokenKind::Minus);
+                if after_operator || i + 1 == tokens.len() {
+                    return Err(parse_error(
+                        format!("Expected a quantity after '{}'", token.text),
+                        token.position,
+                        &token.text,
+                    ));
+                }
+                sign = if token.kind == TokenKind::Minus { -1.0 } else { 1.0 };
+                i += 1;
+            },
+            TokenKind::Number(value) => {
+                let words = tokens[i + 1..].iter()
+                    .take_while(|t| t.kind == TokenKind::Word)
+                    .count();
+
+                if words == 0 {
+                    return Err(parse_error(
+                        format!("Expected a unit after '{}'", token.text),
+                        token.position,
+                        &token.text,
+                    ));
+                }
+
+                let unit_tokens = &tokens[i + 1..i + 1 + words];
+                let unit = parse_unit(input, unit_tokens, registry)?;
+
+                if let Some(first) = quantities.first() {
+                    if first.unit.dimension() != unit.dimension() {
+                        return Err(parse_error(
+                            format!(
+                                "Cannot add '{}' ({}) to '{}' ({}): dimensions differ",
+                                unit.expression(), unit.dimension(),
+                                first.unit.expression(), first.unit.dimension()
+                            ),
+                            unit_tokens[0].position,
+                            unit.expression(),
+                        ));
+                    }
+                }
+
+                quantities.push(Quantity { value: sign * value, unit });
+                i += 1 + words;
+            },
+            TokenKind::Word => {
+                return Err(parse_error(
+                    format!("Expected a number but found '{}'", token.text),
+                    token.position,
+                    &token.text,
+                ));
+            },
+        }
+    }
+
+    Ok(quantities)
+}
+
+/// Parses a range such as "68–77 °F", "-40 to 85 °C", "10 kg - 20 kg", or an
+/// open-ended one such as "≥ 5 bar" or "<= 30 m/s"
+///
+/// The unit is returned as written, for the caller to resolve. It may be given
+/// after each end, but must then be the same. Errors are reported as
+/// `ApiError::ParseError`, as for queries.
+pub fn parse_range(input: &str) -> Result<Range, ApiError> {
+    let tokens = tokenize(input)?;
+    let word = |i: usize| tokens.get(i).filter(|t| t.kind == TokenKind::Word).map(|t| t.text.to_lowercase());
+
+    let mut i = 0;
+    let bound = word(0).filter(|w| LOWER_BOUNDS.contains(&w.as_str()) || UPPER_BOUNDS.contains(&w.as_str()));
+    if bound.is_some() {
+        i += 1;
+    }
+
+    let (first, next) = parse_signed_number(input, &tokens, i)?;
+    let (first_unit, next) = parse_range_unit(input, &tokens, next);
+    i = next;
+
+    // A hyphen or a separating word leads to the upper end of a closed range
+    let separated = bound.is_none() && tokens.get(i).is_some_and(|t| {
+        t.kind == TokenKind::Minus || word(i).is_some_and(|w| RANGE_SEPARATORS.contains(&w.as_str()))
+    });
+    let (second, second_unit) = if separated {
+        let (second, next) = parse_signed_number(input, &tokens, i + 1)?;
+        let (unit, next) = parse_range_unit(input, &tokens, next);
+        i = next;
+        (Some(second), unit)
+    } else {
+        (None, None)
+    };
+
+    if let Some(token) = tokens.get(i) {
+        return Err(parse_error(
+            format!("Unexpected '{}' after the range", token.text),
+            token.position,
+            &token.text,
+        ));
+    }
+
+    let unit = match (first_unit, second_unit) {
+        (Some(first), Some(second)) if first != second => return Err(parse_error(
+            format!("Both ends of the range must be in the same unit, not '{}' and '{}'", first, second),
+            0,
+            input,
+        )),
+        (_, Some(unit)) | (Some(unit), None) => unit,
+        (None, None) => return Err(parse_error(
+            "Expected a unit after the range".to_string(),
+            input.chars().count(),
+            "",
+        )),
+    };
+
+    let (min, max) = match (bound.as_deref(), second) {
+        (Some(bound), _) if UPPER_BOUNDS.contains(&bound) => (None, Some(first)),
+        (Some(_), _) => (Some(first), None),
+        (None, Some(second)) => (Some(first), Some(second)),
+        (None, None) => return Err(parse_error(
+            "Expected a second value, or '≥' or '≤' before the first, to make a range".to_string(),
+            input.chars().count(),
+            "",
+        )),
+    };
+    Ok(Range { min, max, unit })
+}
+
+/// Parses a number at `index`, with an optional sign, returning it and the
+/// index of the token after it
+fn parse_signed_number(input: &str, tokens: &[Token], index: usize) -> Result<(f64, usize), ApiError> {
+    let sign = match tokens.get(index).map(|t| t.kind) {
+        Some(TokenKind::Minus) => -1.0,
+        Some(TokenKind::Plus) => 1.0,
+        _ => return parse_number(input, tokens, index).map(|value| (value, index + 1)),
+    };
+    parse_number(input, tokens, index + 1).map(|value| (sign * value, index + 2))
+}
+
+fn parse_number(input: &str, tokens: &[Token], index: usize) -> Result<f64, ApiError> {
+    match tokens.get(index) {
+        Some(Token { kind: TokenKind::Number(value), .. }) => Ok(*value),
+        Some(token) => Err(parse_error(
+            format!("Expected a number but found '{}'", token.text),
+            token.position,
+            &token.text,
+        )),
+        None => Err(parse_error("Expected a number".to_string(), input.chars().count(), "")),
+    }
+}
+
+/// Returns the unit spanned by the words from `index` up to a range separator,
+/// if there are any, and the index of the token after them
+fn parse_range_unit(input: &str, tokens: &[Token], index: usize) -> (Option<String>, usize) {
+    let words = tokens[index.min(tokens.len())..].iter()
+        .take_while(|t| {
+            t.kind == TokenKind::Word && !RANGE_SEPARATORS.contains(&t.text.to_lowercase().as_str())
+        })
+        .count();
+
+    match words {
+        0 => (None, index),
+        _ => (Some(input[tokens[index].start..tokens[index + words - 1].end].to_string()), index + words),
+    }
+}
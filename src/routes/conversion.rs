@@ -1,25 +1,119 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpResponse, Responder, ResponseError};
 use serde::{Serialize, Deserialize};
-use crate::services::{ConversionService, ConversionRequest};
+use utoipa::{IntoParams, ToSchema};
+use crate::config::AppConfig;
+use crate::models::{ListingOrder, Tolerance};
+use crate::services::{
+    ConversionService, ConversionRequest, RangeConversionRequest, CalculationService, CalculationRequest
+};
+
+/// Query parameters for the category and unit listings
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListingQuery {
+    /// Order to list in: registration, factor or name, defaulting to the configured order
+    #[param(value_type = Option<ListingOrder>)]
+    pub sort: Option<String>,
+    /// Number of entries to skip
+    #[serde(default)]
+    pub offset: usize,
+    /// Most entries to return, or all of them if omitted
+    pub limit: Option<usize>,
+}
+
+impl ListingQuery {
+    /// Returns the requested order, or the configured one if none was given
+    fn order(&self, config: Option<&web::Data<AppConfig>>) -> Result<ListingOrder, crate::errors::ApiError> {
+        match &self.sort {
+            Some(sort) => sort.parse(),
+            None => Ok(config.map(|config| config.listing_order).unwrap_or_default()),
+        }
+    }
+    
+    /// Responds with the requested page of a listing, giving the length of
+    /// the whole listing in the X-Total-Count header
+    fn page<T: Serialize>(&self, items: Vec<T>) -> HttpResponse {
+        let total = items.len();
+        let page: Vec<T> = items.into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        HttpResponse::Ok()
+            .insert_header(("X-Total-Count", total.to_string()))
+            .json(page)
+    }
+}
 
 /// Get all available unit categories
-pub async fn get_categories() -> impl Responder {
-    match ConversionService::list_categories() {
-        Ok(categories) => HttpResponse::Ok().json(categories),
-        Err(err) => HttpResponse::InternalServerError().json(serde_json::json!({
-            "error": "failed_to_list_categories",
-            "message": err.to_string()
-        }))
+#[utoipa::path(
+    get,
+    path = "/api/categories",
+    tag = "conversion",
+    params(ListingQuery),
+    responses(
+        (status = 200, description = "A page of the categories", body = [CategoryInfo],
+            headers(("X-Total-Count" = usize, description = "Number of categories in every page"))),
+        (status = 400, description = "The order is unknown or can't apply to categories", body = ErrorResponse),
+    ),
+)]
+pub async fn get_categories(
+    query: web::Query<ListingQuery>,
+    config: Option<web::Data<AppConfig>>,
+) -> impl Responder {
+    // A configured factor order only applies to units
+    let order = match (&query.sort, query.order(config.as_ref())) {
+        (None, Ok(ListingOrder::Factor)) => Ok(ListingOrder::Registration),
+        (_, order) => order,
+    };
+    let categories = order.and_then(ConversionService::describe_categories);
+    match categories {
+        Ok(categories) => query.page(categories),
+        Err(err) => match err {
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_listing",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "failed_to_list_categories",
+                "message": err.to_string()
+            }))
+        }
     }
 }
 
 /// Get all units for a specific category
-pub async fn get_units(path: web::Path<String>) -> impl Responder {
+#[utoipa::path(
+    get,
+    path = "/api/categories/{category}/units",
+    tag = "conversion",
+    params(("category" = String, Path, description = "Category to list the units of"), ListingQuery),
+    responses(
+        (status = 200, description = "A page of the category's units", body = [UnitInfo],
+            headers(("X-Total-Count" = usize, description = "Number of units in every page"))),
+        (status = 400, description = "The order is unknown", body = ErrorResponse),
+        (status = 404, description = "No such category", body = ErrorResponse),
+    ),
+)]
+pub async fn get_units(
+    path: web::Path<String>,
+    query: web::Query<ListingQuery>,
+    config: Option<web::Data<AppConfig>>,
+) -> impl Responder {
     let category = path.into_inner();
     
-    match ConversionService::list_units(&category) {
-        Ok(units) => HttpResponse::Ok().json(units),
+    let units = query.order(config.as_ref())
+        .and_then(|order| ConversionService::describe_units(&category, order));
+    match units {
+        Ok(units) => query.page(units),
         Err(err) => match err {
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_listing",
+                    "message": err.to_string()
+                }))
+            },
             crate::errors::ApiError::NotFound(_) => {
                 HttpResponse::NotFound().json(serde_json::json!({
                     "error": "category_not_found",
@@ -35,10 +129,114 @@ pub async fn get_units(path: web::Path<String>) -> impl Responder {
 }
 
 /// Convert a value from one unit to another
+#[utoipa::path(
+    post,
+    path = "/api/convert",
+    tag = "conversion",
+    request_body = ConversionRequest,
+    responses(
+        (status = 200, description = "The converted value", body = ConversionResponse),
+        (status = 400, description = "The units can't be converted between, or a unit is ambiguous", body = ErrorResponse),
+        (status = 404, description = "A unit or category isn't registered", body = ErrorResponse),
+    ),
+)]
 pub async fn convert(request: web::Json<ConversionRequest>) -> impl Responder {
     match ConversionService::convert(request.into_inner()) {
         Ok(result) => HttpResponse::Ok().json(result),
         Err(err) => match err {
+            crate::errors::ApiError::Ambiguous { .. }
+            | crate::errors::ApiError::UnitNotFound { .. } => err.error_response(),
+            crate::errors::ApiError::NotFound(_) => {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "unit_not_found",
+                    "message": err.to_string()
+                }))
+            },
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_conversion",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "conversion_failed",
+                "message": err.to_string()
+            }))
+        }
+    }
+}
+
+/// Query parameters for free-text conversions
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ConvertQuery {
+    /// Query such as "5 ft 3 in to m"
+    pub q: String,
+}
+
+/// Convert a free-text query such as "5 ft 3 in to m"
+#[utoipa::path(
+    get,
+    path = "/api/convert",
+    tag = "conversion",
+    params(ConvertQuery),
+    responses(
+        (status = 200, description = "The total of the query's quantities in the target unit", body = QueryResponse),
+        (status = 400, description = "The query can't be parsed or converted", body = ErrorResponse),
+    ),
+)]
+pub async fn convert_query(query: web::Query<ConvertQuery>) -> impl Responder {
+    match ConversionService::convert_query(&query.q) {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(err) => match err {
+            crate::errors::ApiError::ParseError { .. } => err.error_response(),
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_conversion",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "conversion_failed",
+                "message": err.to_string()
+            }))
+        }
+    }
+}
+
+/// Convert a batch of values, returning a result or an error for each item
+#[utoipa::path(
+    post,
+    path = "/api/convert/batch",
+    tag = "conversion",
+    request_body = [ConversionRequest],
+    responses(
+        (status = 200, description = "A result or an error for each request, by index", body = [BatchConversionResult]),
+    ),
+)]
+pub async fn convert_batch(request: web::Json<Vec<ConversionRequest>>) -> impl Responder {
+    HttpResponse::Ok().json(ConversionService::convert_batch(request.into_inner()))
+}
+
+/// Convert a range, such as "68–77 °F", either end of which may be open
+#[utoipa::path(
+    post,
+    path = "/api/convert/range",
+    tag = "conversion",
+    request_body = RangeConversionRequest,
+    responses(
+        (status = 200, description = "Both ends of the range, converted", body = RangeConversionResponse),
+        (status = 400, description = "The range can't be parsed or converted, or a unit is ambiguous", body = ErrorResponse),
+        (status = 404, description = "A unit or category isn't registered", body = ErrorResponse),
+    ),
+)]
+pub async fn convert_range(request: web::Json<RangeConversionRequest>) -> impl Responder {
+    match ConversionService::convert_range(&request) {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(err) => match err {
+            crate::errors::ApiError::Ambiguous { .. }
+            | crate::errors::ApiError::UnitNotFound { .. }
+            | crate::errors::ApiError::ParseError { .. } => err.error_response(),
             crate::errors::ApiError::NotFound(_) => {
                 HttpResponse::NotFound().json(serde_json::json!({
                     "error": "unit_not_found",
@@ -60,33 +258,71 @@ pub async fn convert(request: web::Json<ConversionRequest>) -> impl Responder {
 }
 
 /// Request DTO for comparing two measurements
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// When `category` is omitted, it's inferred from the units. The measurements
+/// are equal when the first, expressed in `unit2`, is within any of the given
+/// tolerances of the second: `abs_tolerance` in `unit2`, `rel_tolerance` as a
+/// fraction of the larger value, or `ulp_tolerance` representable doubles. With
+/// none given, they may differ by 4 ULPs.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CompareRequest {
     pub value1: f64,
     pub unit1: String,
     pub value2: f64,
     pub unit2: String,
-    pub category: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    #[schema(example = 0.001)]
+    pub abs_tolerance: Option<f64>,
+    #[serde(default)]
+    #[schema(example = 1e-9)]
+    pub rel_tolerance: Option<f64>,
+    #[serde(default)]
+    pub ulp_tolerance: Option<u64>,
 }
 
 /// Response DTO for comparison results
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// `result` is -1, 0 or 1 as the first measurement is less than, equal to or
+/// greater than the second, and `relation` names it. `difference` is the first
+/// minus the second and `ratio` the first over the second, both in `unit2`;
+/// `ratio` is null when the second value is zero.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CompareResponse {
     pub result: i8,
+    #[schema(example = "less_than")]
     pub relation: String,
+    pub difference: f64,
+    pub ratio: Option<f64>,
 }
 
 /// Compare two measurements
+#[utoipa::path(
+    post,
+    path = "/api/compare",
+    tag = "conversion",
+    request_body = CompareRequest,
+    responses(
+        (status = 200, description = "How the first measurement relates to the second", body = CompareResponse),
+        (status = 400, description = "A unit is ambiguous, the units are in different categories, or a tolerance is negative", body = ErrorResponse),
+        (status = 404, description = "A unit or the category isn't registered", body = ErrorResponse),
+    ),
+)]
 pub async fn compare(request: web::Json<CompareRequest>) -> impl Responder {
-    match ConversionService::compare(
-        request.value1,
-        &request.unit1,
-        request.value2,
-        &request.unit2,
-        &request.category
-    ) {
-        Ok(result) => {
-            let relation = match result {
+    let comparison = Tolerance::new(request.abs_tolerance, request.rel_tolerance, request.ulp_tolerance)
+        .and_then(|tolerance| ConversionService::compare_within(
+            request.value1,
+            &request.unit1,
+            request.value2,
+            &request.unit2,
+            request.category.as_deref(),
+            &tolerance
+        ));
+    
+    match comparison {
+        Ok(comparison) => {
+            let relation = match comparison.result {
                 -1 => "less_than",
                 0 => "equal",
                 1 => "greater_than",
@@ -94,21 +330,69 @@ pub async fn compare(request: web::Json<CompareRequest>) -> impl Responder {
             };
             
             HttpResponse::Ok().json(CompareResponse {
-                result,
+                result: comparison.result,
                 relation: relation.to_string(),
+                difference: comparison.difference,
+                ratio: comparison.ratio,
             })
         },
         Err(err) => match err {
+            crate::errors::ApiError::Ambiguous { .. }
+            | crate::errors::ApiError::UnitNotFound { .. } => err.error_response(),
             crate::errors::ApiError::NotFound(_) => {
                 HttpResponse::NotFound().json(serde_json::json!({
                     "error": "unit_or_category_not_found",
                     "message": err.to_string()
                 }))
             },
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_comparison",
+                    "message": err.to_string()
+                }))
+            },
             _ => HttpResponse::InternalServerError().json(serde_json::json!({
                 "error": "comparison_failed",
                 "message": err.to_string()
             }))
         }
     }
-} 
\ No newline at end of file
+} 
+
+/// Evaluate arithmetic on quantities with units
+#[utoipa::path(
+    post,
+    path = "/api/calculate",
+    tag = "conversion",
+    request_body = CalculationRequest,
+    responses(
+        (status = 200, description = "The result, in the requested unit", body = CalculationResponse),
+        (status = 400, description = "The calculation is malformed, or combines units that can't be", body = ErrorResponse),
+        (status = 404, description = "A unit isn't registered", body = ErrorResponse),
+    ),
+)]
+pub async fn calculate(request: web::Json<CalculationRequest>) -> impl Responder {
+    match CalculationService::calculate(&request) {
+        Ok(response) => HttpResponse::Ok().json(response),
+        Err(err) => match err {
+            crate::errors::ApiError::Ambiguous { .. }
+            | crate::errors::ApiError::UnitNotFound { .. } => err.error_response(),
+            crate::errors::ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_calculation",
+                    "message": err.to_string()
+                }))
+            },
+            crate::errors::ApiError::NotFound(_) => {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "unit_not_found",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "calculation_failed",
+                "message": err.to_string()
+            }))
+        }
+    }
+}
@@ -1,8 +1,23 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use actix_web::http::header;
+use crate::config::AppConfig;
+use crate::errors::ApiError;
 
 mod conversion;
+mod openapi;
+mod rates;
+mod units;
 
 // Health check endpoint for Kubernetes/monitoring
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "health",
+    responses(
+        (status = 200, description = "The service is up", body = Object,
+            example = json!({ "status": "healthy" })),
+    ),
+)]
 pub async fn health_check() -> impl Responder {
     HttpResponse::Ok().json(serde_json::json!({ "status": "healthy" }))
 }
@@ -12,13 +27,58 @@ pub async fn index() -> impl Responder {
     HttpResponse::Ok().body("Unit Conversion API")
 }
 
+/// Checks the request carries the configured admin token as a bearer token
+fn authorize(request: &HttpRequest, config: Option<&web::Data<AppConfig>>) -> Result<(), ApiError> {
+    let expected = config
+        .and_then(|config| config.admin_token.as_deref())
+        .ok_or_else(|| ApiError::Unauthorized("Admin endpoints are disabled".to_string()))?;
+
+    let token = request.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    match token {
+        Some(token) if constant_time_eq(token.as_bytes(), expected.as_bytes()) => Ok(()),
+        _ => Err(ApiError::Unauthorized("Missing or invalid admin token".to_string())),
+    }
+}
+
+/// Compares two byte strings in time that depends only on their lengths, not
+/// on where they first differ, so a token can't be guessed byte by byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut difference = a.len() ^ b.len();
+    for i in 0..a.len().max(b.len()) {
+        let (x, y) = (a.get(i).copied().unwrap_or(0), b.get(i).copied().unwrap_or(0));
+        difference |= usize::from(x ^ y);
+    }
+    std::hint::black_box(difference) == 0
+}
+
 // Configure routes
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.route("/", web::get().to(index))
        .route("/api/health", web::get().to(health_check))
+       // API contract and its interactive documentation
+       .route("/api/openapi.json", web::get().to(openapi::openapi_json))
+       .route("/api/docs", web::get().to(openapi::docs))
+       .route("/api/docs/assets/{file}", web::get().to(openapi::docs_asset))
        // Conversion routes
        .route("/api/categories", web::get().to(conversion::get_categories))
        .route("/api/categories/{category}/units", web::get().to(conversion::get_units))
        .route("/api/convert", web::post().to(conversion::convert))
-       .route("/api/compare", web::post().to(conversion::compare));
+       .route("/api/convert", web::get().to(conversion::convert_query))
+       .route("/api/convert/batch", web::post().to(conversion::convert_batch))
+       .route("/api/convert/range", web::post().to(conversion::convert_range))
+       .route("/api/compare", web::post().to(conversion::compare))
+       .route("/api/calculate", web::post().to(conversion::calculate))
+       // Unit definition routes, whose deletions are admin-only
+       .route("/api/categories", web::post().to(units::define_category))
+       .route("/api/categories/{category}", web::delete().to(units::delete_category))
+       .route("/api/units/search", web::get().to(units::search_units))
+       .route("/api/units", web::post().to(units::define_unit))
+       .route("/api/units/{category}/{symbol}", web::delete().to(units::delete_unit))
+       // Exchange rate routes
+       .route("/api/rates", web::get().to(rates::get_rates))
+       .route("/api/admin/rates", web::put().to(rates::update_rates));
 } 
\ No newline at end of file
@@ -0,0 +1,263 @@
+use std::path::Path;
+use actix_web::{web, HttpResponse, Responder, ResponseError};
+use serde::Serialize;
+use serde_json::{json, Value};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi, ToSchema};
+use crate::config::AppConfig;
+use crate::errors::ApiError;
+use crate::models::{registry, DecimalValue, ExchangeRate, RateUpdate, ListingOrder, MatchKind, Uncertainty, UnitSystem};
+use crate::services::{
+    ConversionRequest, ConversionResponse, QueryResponse, QueryQuantity,
+    BatchConversionResult, BatchConversionError, RangeConversionRequest, RangeConversionResponse,
+    RatesResponse, CategoryInfo, UnitInfo,
+    UnitDefinition, BaseUnitDefinition, CategoryDefinition, UnitResponse, CategoryResponse,
+    UnitSearchResult, CalculationRequest, CalculationResponse, CalculationNode, CalculationOperation,
+    CalculationOperator, CalculationQuantity,
+};
+use super::conversion::{CompareRequest, CompareResponse};
+use super::units::CategoryRemoval;
+
+/// Request properties that name a registered category, as (schema, property)
+const CATEGORY_PROPERTIES: [(&str, &str); 4] = [
+    ("ConversionRequest", "from_category"),
+    ("RangeConversionRequest", "from_category"),
+    ("CompareRequest", "category"),
+    ("UnitDefinition", "category"),
+];
+
+/// Request properties that name a registered unit, as (schema, property)
+const UNIT_PROPERTIES: [(&str, &str); 3] = [
+    ("CompareRequest", "unit1"),
+    ("CompareRequest", "unit2"),
+    ("UnitDefinition", "unit"),
+];
+
+/// Request properties that name a registered unit, or a unit expression when
+/// the request has no category
+const UNIT_EXPRESSION_PROPERTIES: [(&str, &str); 4] = [
+    ("ConversionRequest", "from_unit"),
+    ("ConversionRequest", "to_unit"),
+    ("RangeConversionRequest", "unit"),
+    ("RangeConversionRequest", "to_unit"),
+];
+
+/// Release of Swagger UI the docs page loads from unpkg, pinned so the script
+/// it runs only changes with this file
+const SWAGGER_UI_VERSION: &str = "5.17.14";
+
+/// Swagger UI files the docs page loads, with their content types
+const SWAGGER_UI_ASSETS: [(&str, &str); 2] = [
+    ("swagger-ui.css", "text/css; charset=utf-8"),
+    ("swagger-ui-bundle.js", "text/javascript; charset=utf-8"),
+];
+
+/// Path the Swagger UI files are served under when they're kept locally
+const SWAGGER_UI_PATH: &str = "/api/docs/assets";
+
+/// Interactive documentation, rendered by Swagger UI from the document, with
+/// its files loaded from `assets`
+fn docs_page(assets: &str) -> String {
+    format!(r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Unit Conversion API</title>
+  <link rel="stylesheet" href="{assets}/swagger-ui.css" crossorigin="anonymous">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{assets}/swagger-ui-bundle.js" crossorigin="anonymous"></script>
+  <script>
+    window.onload = () => {{
+      window.ui = SwaggerUIBundle({{ url: "/api/openapi.json", dom_id: "#swagger-ui" }});
+    }};
+  </script>
+</body>
+</html>
+"##)
+}
+
+/// Body of an error response
+///
+/// `candidates` lists the units an ambiguous name could mean, `suggestions` the
+/// closest symbols and names to one that isn't registered, and `position` and
+/// `token` locate a parse error in a free-text query. The handlers build these
+/// bodies directly, so this type only describes them.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    #[schema(example = "unit_not_found")]
+    pub error: String,
+    pub message: String,
+    pub candidates: Option<Vec<String>>,
+    pub suggestions: Option<Vec<String>>,
+    pub position: Option<usize>,
+    pub token: Option<String>,
+}
+
+/// Declares the bearer token the admin endpoints take
+struct AdminToken;
+
+impl Modify for AdminToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Unit Conversion API",
+        description = "Converts and compares measurements across categories of units",
+        license(name = "MIT"),
+    ),
+    paths(
+        super::health_check,
+        super::conversion::get_categories,
+        super::conversion::get_units,
+        super::conversion::convert,
+        super::conversion::convert_query,
+        super::conversion::convert_batch,
+        super::conversion::convert_range,
+        super::conversion::compare,
+        super::conversion::calculate,
+        super::units::search_units,
+        super::units::define_category,
+        super::units::delete_category,
+        super::units::define_unit,
+        super::units::delete_unit,
+        super::rates::get_rates,
+        super::rates::update_rates,
+    ),
+    components(schemas(
+        CategoryInfo, UnitInfo, UnitSystem, ListingOrder, UnitSearchResult, MatchKind,
+        ConversionRequest, ConversionResponse, Uncertainty, QueryResponse, QueryQuantity,
+        BatchConversionResult, BatchConversionError, RangeConversionRequest, RangeConversionResponse,
+        CompareRequest, CompareResponse,
+        CalculationRequest, CalculationResponse, CalculationNode, CalculationOperation,
+        CalculationOperator, CalculationQuantity,
+        UnitDefinition, BaseUnitDefinition, CategoryDefinition, UnitResponse, CategoryResponse,
+        CategoryRemoval, RatesResponse, ExchangeRate, RateUpdate, DecimalValue, ErrorResponse,
+    )),
+    modifiers(&AdminToken),
+    tags(
+        (name = "conversion", description = "Converting, comparing and calculating with measurements"),
+        (name = "units", description = "Searching units, and defining and removing units and categories at runtime"),
+        (name = "rates", description = "Exchange rates for currency conversion"),
+    ),
+)]
+struct ApiDoc;
+
+/// Builds the OpenAPI document, with the `Category` and `Unit` enums listing
+/// what the registry holds right now, including units defined at runtime
+pub fn document() -> Result<Value, ApiError> {
+    let mut openapi = ApiDoc::openapi();
+    // The package's authors are a placeholder, not a contact for the API
+    openapi.info.contact = None;
+    let mut document = serde_json::to_value(openapi)
+        .map_err(|err| ApiError::InternalError(err.to_string()))?;
+
+    let registry = registry().read().map_err(|_| {
+        ApiError::InternalError("Failed to access unit registry".to_string())
+    })?;
+    let mut categories: Vec<&str> = registry.get_categories().iter().map(|c| c.name()).collect();
+    categories.sort_unstable();
+    let mut units = registry.lookup_terms();
+    units.sort_unstable();
+    units.dedup();
+
+    let schemas = &mut document["components"]["schemas"];
+    schemas["Category"] = json!({
+        "type": "string",
+        "description": "Name of a registered category",
+        "enum": categories,
+    });
+    schemas["Unit"] = json!({
+        "type": "string",
+        "description": "Symbol, name or alias of a registered unit",
+        "enum": units,
+    });
+
+    for (schema, property) in CATEGORY_PROPERTIES {
+        refer(&mut schemas[schema]["properties"][property], json!({ "$ref": "#/components/schemas/Category" }));
+    }
+    for (schema, property) in UNIT_PROPERTIES {
+        refer(&mut schemas[schema]["properties"][property], json!({ "$ref": "#/components/schemas/Unit" }));
+    }
+    for (schema, property) in UNIT_EXPRESSION_PROPERTIES {
+        schemas[schema]["properties"][property] = json!({
+            "anyOf": [
+                { "$ref": "#/components/schemas/Unit" },
+                { "type": "string", "description": "Unit expression such as \"kg/m³\", when from_category is omitted" },
+            ],
+        });
+    }
+
+    // Parameters named category take a registered one too
+    if let Some(paths) = document["paths"].as_object_mut() {
+        let parameters = paths.values_mut()
+            .filter_map(Value::as_object_mut)
+            .flat_map(|operations| operations.values_mut())
+            .filter_map(|operation| operation.get_mut("parameters").and_then(Value::as_array_mut))
+            .flatten();
+        for parameter in parameters {
+            if parameter["name"] == "category" {
+                parameter["schema"] = json!({ "$ref": "#/components/schemas/Category" });
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Points a property at a shared schema, keeping its description and nullability
+fn refer(property: &mut Value, reference: Value) {
+    let mut replacement = json!({ "allOf": [reference] });
+    for key in ["description", "nullable"] {
+        if let Some(value) = property.get(key) {
+            replacement[key] = value.clone();
+        }
+    }
+    *property = replacement;
+}
+
+/// Serve the OpenAPI document
+pub async fn openapi_json() -> impl Responder {
+    match document() {
+        Ok(document) => HttpResponse::Ok().json(document),
+        Err(err) => err.error_response(),
+    }
+}
+
+/// Returns the directory the Swagger UI files are served from, if one is configured
+fn swagger_ui_dir(config: Option<&web::Data<AppConfig>>) -> Option<&str> {
+    config.and_then(|config| config.swagger_ui_dir.as_deref())
+}
+
+/// Serve the interactive documentation page
+pub async fn docs(config: Option<web::Data<AppConfig>>) -> impl Responder {
+    let assets = match swagger_ui_dir(config.as_ref()) {
+        Some(_) => SWAGGER_UI_PATH.to_string(),
+        None => format!("https://unpkg.com/swagger-ui-dist@{}", SWAGGER_UI_VERSION),
+    };
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(docs_page(&assets))
+}
+
+/// Serve one of the Swagger UI files from the configured directory
+pub async fn docs_asset(file: web::Path<String>, config: Option<web::Data<AppConfig>>) -> impl Responder {
+    let asset = SWAGGER_UI_ASSETS.iter().find(|(name, _)| *name == file.as_str());
+    let (Some(dir), Some(&(name, content_type))) = (swagger_ui_dir(config.as_ref()), asset) else {
+        return ApiError::NotFound(format!("No documentation asset '{}'", file)).error_response();
+    };
+    match std::fs::read(Path::new(dir).join(name)) {
+        Ok(body) => HttpResponse::Ok().content_type(content_type).body(body),
+        Err(err) => ApiError::InternalError(format!("Failed to read {} from {}: {}", name, dir, err)).error_response(),
+    }
+}
@@ -0,0 +1,57 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder, ResponseError};
+use crate::config::AppConfig;
+use crate::errors::ApiError;
+use crate::models::RateUpdate;
+use crate::services::RateService;
+
+/// Get the exchange rates currencies convert at
+#[utoipa::path(
+    get,
+    path = "/api/rates",
+    tag = "rates",
+    responses(
+        (status = 200, description = "The current rates against the base currency", body = RatesResponse),
+    ),
+)]
+pub async fn get_rates() -> impl Responder {
+    HttpResponse::Ok().json(RateService::list_rates())
+}
+
+/// Replace exchange rates, for admins holding the configured token
+#[utoipa::path(
+    put,
+    path = "/api/admin/rates",
+    tag = "rates",
+    request_body = RateUpdate,
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The rates after the update", body = RatesResponse),
+        (status = 400, description = "A rate or currency code is invalid", body = ErrorResponse),
+        (status = 401, description = "The admin token is missing or wrong", body = ErrorResponse),
+    ),
+)]
+pub async fn update_rates(
+    request: HttpRequest,
+    config: Option<web::Data<AppConfig>>,
+    update: web::Json<RateUpdate>,
+) -> impl Responder {
+    if let Err(err) = super::authorize(&request, config.as_ref()) {
+        return err.error_response();
+    }
+
+    match RateService::update_rates(update.into_inner()) {
+        Ok(rates) => HttpResponse::Ok().json(rates),
+        Err(err) => match err {
+            ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_rates",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "rate_update_failed",
+                "message": err.to_string()
+            }))
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder, ResponseError};
+use crate::config::AppConfig;
+use crate::errors::ApiError;
+use serde::{Serialize, Deserialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::services::{UnitService, UnitDefinition, CategoryDefinition};
+
+/// Response DTO for a removed category
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryRemoval {
+    pub category: String,
+    pub units_removed: usize,
+}
+
+/// Builds the response for a failed definition or removal
+fn error_response(err: ApiError, not_found: &str, invalid: &str) -> HttpResponse {
+    match err {
+        ApiError::Ambiguous { .. } | ApiError::UnitNotFound { .. } | ApiError::Unauthorized(_) => {
+            err.error_response()
+        },
+        ApiError::NotFound(_) => {
+            HttpResponse::NotFound().json(serde_json::json!({
+                "error": not_found,
+                "message": err.to_string()
+            }))
+        },
+        ApiError::BadRequest(_) => {
+            HttpResponse::BadRequest().json(serde_json::json!({
+                "error": invalid,
+                "message": err.to_string()
+            }))
+        },
+        _ => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": "registry_update_failed",
+            "message": err.to_string()
+        }))
+    }
+}
+
+/// Default number of search results
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Query parameters for a unit search
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Symbol, name or alias to search for, or the start of one, such as "kilom"
+    pub q: String,
+    /// Category to search, instead of every category
+    pub category: Option<String>,
+    /// Most results to return, defaulting to 20
+    pub limit: Option<usize>,
+}
+
+/// Search units by symbol, name and alias, for autocompletion
+#[utoipa::path(
+    get,
+    path = "/api/units/search",
+    tag = "units",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching units, exact matches first, then prefix and fuzzy matches", body = [UnitSearchResult]),
+        (status = 400, description = "The query is empty", body = ErrorResponse),
+        (status = 404, description = "No such category", body = ErrorResponse),
+    ),
+)]
+pub async fn search_units(query: web::Query<SearchQuery>) -> impl Responder {
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    match UnitService::search(&query.q, query.category.as_deref(), limit) {
+        Ok(results) => HttpResponse::Ok().json(results),
+        Err(err) => match err {
+            ApiError::NotFound(_) => {
+                HttpResponse::NotFound().json(serde_json::json!({
+                    "error": "category_not_found",
+                    "message": err.to_string()
+                }))
+            },
+            ApiError::BadRequest(_) => {
+                HttpResponse::BadRequest().json(serde_json::json!({
+                    "error": "invalid_search",
+                    "message": err.to_string()
+                }))
+            },
+            _ => HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "search_failed",
+                "message": err.to_string()
+            }))
+        }
+    }
+}
+
+/// Define a unit in terms of an existing unit, for admins holding the configured token
+#[utoipa::path(
+    post,
+    path = "/api/units",
+    tag = "units",
+    request_body = UnitDefinition,
+    security(("admin_token" = [])),
+    responses(
+        (status = 201, description = "The unit was registered", body = UnitResponse),
+        (status = 400, description = "The definition is invalid or its unit is ambiguous", body = ErrorResponse),
+        (status = 401, description = "The admin token is missing or wrong", body = ErrorResponse),
+        (status = 404, description = "The unit it's defined in terms of isn't registered", body = ErrorResponse),
+    ),
+)]
+pub async fn define_unit(
+    request: HttpRequest,
+    config: Option<web::Data<AppConfig>>,
+    definition: web::Json<UnitDefinition>,
+) -> impl Responder {
+    match super::authorize(&request, config.as_ref())
+        .and_then(|_| UnitService::define_unit(definition.into_inner()))
+    {
+        Ok(unit) => HttpResponse::Created().json(unit),
+        Err(err) => error_response(err, "unit_not_found", "invalid_unit"),
+    }
+}
+
+/// Define a category along with its base unit, for admins holding the configured token
+#[utoipa::path(
+    post,
+    path = "/api/categories",
+    tag = "units",
+    request_body = CategoryDefinition,
+    security(("admin_token" = [])),
+    responses(
+        (status = 201, description = "The category and its base unit were registered", body = CategoryResponse),
+        (status = 400, description = "The definition is invalid", body = ErrorResponse),
+        (status = 401, description = "The admin token is missing or wrong", body = ErrorResponse),
+    ),
+)]
+pub async fn define_category(
+    request: HttpRequest,
+    config: Option<web::Data<AppConfig>>,
+    definition: web::Json<CategoryDefinition>,
+) -> impl Responder {
+    match super::authorize(&request, config.as_ref())
+        .and_then(|_| UnitService::define_category(definition.into_inner()))
+    {
+        Ok(category) => HttpResponse::Created().json(category),
+        Err(err) => error_response(err, "category_not_found", "invalid_category"),
+    }
+}
+
+/// Remove a unit defined at runtime, for admins holding the configured token
+#[utoipa::path(
+    delete,
+    path = "/api/units/{category}/{symbol}",
+    tag = "units",
+    params(
+        ("category" = String, Path, description = "Category of the unit"),
+        ("symbol" = String, Path, description = "Symbol of the unit"),
+    ),
+    security(("admin_token" = [])),
+    responses(
+        (status = 204, description = "The unit was removed"),
+        (status = 400, description = "The unit wasn't defined at runtime", body = ErrorResponse),
+        (status = 401, description = "The admin token is missing or wrong", body = ErrorResponse),
+        (status = 404, description = "No such unit", body = ErrorResponse),
+    ),
+)]
+pub async fn delete_unit(
+    request: HttpRequest,
+    config: Option<web::Data<AppConfig>>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let (category, symbol) = path.into_inner();
+
+    match super::authorize(&request, config.as_ref())
+        .and_then(|_| UnitService::remove_unit(&category, &symbol))
+    {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(err) => error_response(err, "unit_not_found", "invalid_unit"),
+    }
+}
+
+/// Remove a category defined at runtime along with all of its units,
+/// for admins holding the configured token
+#[utoipa::path(
+    delete,
+    path = "/api/categories/{category}",
+    tag = "units",
+    params(("category" = String, Path, description = "Category to remove")),
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The category and its units were removed", body = CategoryRemoval),
+        (status = 400, description = "The category wasn't defined at runtime", body = ErrorResponse),
+        (status = 401, description = "The admin token is missing or wrong", body = ErrorResponse),
+        (status = 404, description = "No such category", body = ErrorResponse),
+    ),
+)]
+pub async fn delete_category(
+    request: HttpRequest,
+    config: Option<web::Data<AppConfig>>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let category = path.into_inner();
+
+    match super::authorize(&request, config.as_ref())
+        .and_then(|_| UnitService::remove_category(&category))
+    {
+        Ok(units_removed) => HttpResponse::Ok().json(CategoryRemoval { category, units_removed }),
+        Err(err) => error_response(err, "category_not_found", "invalid_category"),
+    }
+}
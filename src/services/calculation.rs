@@ -0,0 +1,142 @@
+use crate::models::{registry, CompoundUnit, Quantity, UnitRegistry};
+use crate::errors::ApiError;
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+
+/// An arithmetic operation, applied to its operands from left to right, where
+/// subtracting a single operand negates it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CalculationOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+/// A value in a calculation, in `unit` or as a plain number if it has none
+///
+/// `unit` may be any unit expression, such as "cup" or "kg/m³".
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CalculationQuantity {
+    pub value: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+}
+
+/// An operation on one or more operands, each a quantity or another operation
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CalculationOperation {
+    pub op: CalculationOperator,
+    pub operands: Vec<CalculationNode>,
+}
+
+/// A node of a calculation's expression tree
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum CalculationNode {
+    Operation(CalculationOperation),
+    Quantity(CalculationQuantity),
+}
+
+/// Data transfer object for calculation requests
+///
+/// Give either `expression`, a tree of operations, or `operands`, a list of
+/// quantities (or operations) to add up. The result is expressed in `to_unit`,
+/// or in the unit the arithmetic produced when it's omitted: the unit of the
+/// first operand of a sum, and a compound unit such as "mi/min" for products
+/// and quotients.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CalculationRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expression: Option<CalculationNode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operands: Option<Vec<CalculationNode>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_unit: Option<String>,
+}
+
+/// Data transfer object for calculation responses
+///
+/// `unit` and `category` are null when the result is a plain number, as when
+/// the units cancel out. `category` is the dimension, such as "L·T⁻²", when no
+/// registered category matches it.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CalculationResponse {
+    pub value: f64,
+    pub unit: Option<String>,
+    pub category: Option<String>,
+}
+
+/// Service for arithmetic on quantities with units
+pub struct CalculationService;
+
+impl CalculationService {
+    /// Evaluate a calculation, following the rules of `Quantity`: temperatures
+    /// only take intervals added to or subtracted from them, and multiplying or
+    /// dividing quantities combines their units
+    pub fn calculate(request: &CalculationRequest) -> Result<CalculationResponse, ApiError> {
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        let mut result = match (&request.expression, &request.operands) {
+            (Some(expression), None) => Self::evaluate(expression, &registry)?,
+            (None, Some(operands)) => Self::apply(CalculationOperator::Add, operands, &registry)?,
+            _ => return Err(ApiError::BadRequest(
+                "Give either an expression or a list of operands".to_string()
+            )),
+        };
+        if let Some(to_unit) = &request.to_unit {
+            result = result.convert_to(&CompoundUnit::parse(to_unit, &registry)?)?;
+        }
+        if !result.number().is_finite() {
+            return Err(ApiError::BadRequest(format!("The result, {}, isn't a finite number", result)));
+        }
+
+        // Report the category of a lone unit, or the one matching the dimension
+        let category = result.unit().map(|unit| match unit.factors() {
+            [factor] if factor.exponent == 1 => factor.category.to_string(),
+            _ => registry.category_for_dimension(unit.dimension())
+                .map(|c| c.name().to_string())
+                .unwrap_or_else(|| unit.dimension().to_string()),
+        });
+
+        Ok(CalculationResponse {
+            value: result.number(),
+            unit: result.unit().map(|unit| unit.expression().to_string()),
+            category,
+        })
+    }
+
+    fn evaluate(node: &CalculationNode, registry: &UnitRegistry) -> Result<Quantity, ApiError> {
+        match node {
+            CalculationNode::Quantity(quantity) => match &quantity.unit {
+                Some(unit) => Ok(Quantity::new(quantity.value, CompoundUnit::parse(unit, registry)?)),
+                None => Ok(Quantity::plain(quantity.value)),
+            },
+            CalculationNode::Operation(operation) => Self::apply(operation.op, &operation.operands, registry),
+        }
+    }
+
+    fn apply(operator: CalculationOperator, operands: &[CalculationNode], registry: &UnitRegistry) -> Result<Quantity, ApiError> {
+        let (first, rest) = operands.split_first().ok_or_else(|| {
+            ApiError::BadRequest("An operation needs at least one operand".to_string())
+        })?;
+
+        let mut result = Self::evaluate(first, registry)?;
+        if operator == CalculationOperator::Subtract && rest.is_empty() {
+            return Ok(result.negate());
+        }
+        for operand in rest {
+            let operand = Self::evaluate(operand, registry)?;
+            result = match operator {
+                CalculationOperator::Add => result.add(operand, registry)?,
+                CalculationOperator::Subtract => result.subtract(operand, registry)?,
+                CalculationOperator::Multiply => result.multiply(operand, registry)?,
+                CalculationOperator::Divide => result.divide(operand, registry)?,
+            };
+        }
+        Ok(result)
+    }
+}
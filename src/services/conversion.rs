@@ -1,6 +1,17 @@
-use crate::models::{registry};
+use crate::models::{registry, CompoundUnit, ListingOrder, Tolerance, Uncertainty, UnitSystem};
+use crate::models::alias::pluralize;
+use crate::models::{exact, decimal, DecimalValue};
+use crate::models::registry::{AnyUnit, UnitRegistry};
+use crate::models::unit::UnitCategory;
+use crate::models::units::temperature::TemperatureIntervalCategory;
+use crate::models::Dimension;
+use crate::models::units::currency::Currency;
 use crate::errors::ApiError;
+use crate::parser::{parse_query, parse_range, Range};
 use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
 
@@ -9,10 +20,6 @@ static CATEGORY_CACHE: Lazy<RwLock<HashMap<String, &'static str>>> = Lazy::new(|
     RwLock::new(HashMap::new())
 });
 
-static UNIT_CACHE: Lazy<RwLock<HashMap<String, &'static str>>> = Lazy::new(|| {
-    RwLock::new(HashMap::new())
-});
-
 use std::sync::RwLock;
 
 // Helper to get or cache a 'static string
@@ -21,7 +28,7 @@ fn get_static_str(s: &str, cache: &RwLock<HashMap<String, &'static str>>) -> &'s
         // Try to read from cache first
         if let Ok(cache_read) = cache.read() {
             if let Some(static_str) = cache_read.get(s) {
-                return *static_str;
+                return static_str;
             }
         }
     }
@@ -38,22 +45,192 @@ fn get_static_str(s: &str, cache: &RwLock<HashMap<String, &'static str>>) -> &'s
 }
 
 /// Data transfer object for conversion requests
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// When `from_category` is omitted, it's inferred from `from_unit` and `to_unit`
+/// if both are registered units sharing a category, and a request whose units
+/// share several is rejected listing them. Otherwise the units are parsed as
+/// unit expressions (such as "N·m" or "kg/m³") and converted by dimensional analysis.
+/// `value` may be a JSON number or a decimal string such as "0.1". Setting `exact`
+/// asks for the unrounded result as a fraction, and setting `precision` asks for
+/// it as a decimal string rounded to that many significant digits. `uncertainty`
+/// is the value's standard deviation or bounds, in `from_unit`.
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
 pub struct ConversionRequest {
-    pub value: f64,
-    pub from_category: String,
+    pub value: DecimalValue,
+    #[serde(default)]
+    pub from_category: Option<String>,
     pub from_unit: String,
     pub to_unit: String,
+    #[serde(default)]
+    pub exact: bool,
+    #[serde(default)]
+    pub precision: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<Uncertainty>,
 }
 
 /// Data transfer object for conversion responses
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// `exact_value` holds the result as a fraction such as "1250/381", and is only
+/// present when the request set `exact`. `decimal_value` holds the result as a
+/// decimal string such as "0.3", and is only present when the request set `precision`.
+/// `rate_timestamp` is when the exchange rates of a currency conversion were quoted.
+/// `uncertainty` is the request's uncertainty carried through the conversion, in
+/// `to_unit`, and is only present when the request gave one.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ConversionResponse {
     pub from_value: f64,
     pub from_unit: String,
     pub to_value: f64,
     pub to_unit: String,
     pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exact_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimal_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_timestamp: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncertainty: Option<Uncertainty>,
+}
+
+/// A single quantity parsed from a free-text query
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueryQuantity {
+    pub value: f64,
+    pub unit: String,
+}
+
+/// Data transfer object for free-text query responses
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct QueryResponse {
+    pub query: String,
+    pub quantities: Vec<QueryQuantity>,
+    pub to_value: f64,
+    pub to_unit: String,
+    pub category: String,
+}
+
+/// Error details for a single item of a batch conversion
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchConversionError {
+    pub error: String,
+    pub message: String,
+}
+
+/// Outcome of a single item of a batch conversion, tagged with its input index
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BatchConversionResult {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<ConversionResponse>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchConversionError>,
+}
+
+/// Data transfer object for range conversion requests
+///
+/// The range is either written out in `range`, as in "68–77 °F", "-40 to 85 °C"
+/// or "≥ 5 bar", or given as `min`, `max` and `unit`, leaving out either end for
+/// an open-ended range. `from_category` is inferred when omitted, as for single
+/// conversions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RangeConversionRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "68–77 °F")]
+    pub range: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_category: Option<String>,
+    pub to_unit: String,
+}
+
+/// Data transfer object for range conversion responses
+///
+/// `from_min`/`to_min` and `from_max`/`to_max` are null for the open end of an
+/// open-ended range.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RangeConversionResponse {
+    pub from_min: Option<f64>,
+    pub from_max: Option<f64>,
+    pub from_unit: String,
+    pub to_min: Option<f64>,
+    pub to_max: Option<f64>,
+    pub to_unit: String,
+    pub category: String,
+}
+
+/// How one measurement compares to another
+///
+/// `result` is -1, 0 or 1 as the first is less than, equal to or greater than
+/// the second. `difference` is the first minus the second, and `ratio` the first
+/// divided by the second, both in the second measurement's unit; `ratio` is
+/// `None` when the second is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Comparison {
+    pub result: i8,
+    pub difference: f64,
+    pub ratio: Option<f64>,
+}
+
+/// A registered category, as listed by the categories endpoint
+///
+/// `base_unit` is the symbol of the unit every other unit in the category is
+/// defined relative to, and is null for currencies, whose base is the rate
+/// provider's base currency. `dimension` is null for categories outside
+/// dimensional analysis.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryInfo {
+    #[schema(example = "length")]
+    pub name: String,
+    pub description: String,
+    pub base_unit: Option<String>,
+    #[schema(example = "L")]
+    pub dimension: Option<String>,
+    pub units: usize,
+}
+
+/// A registered unit, as listed by the units endpoint of its category
+///
+/// `aliases` holds the other names the unit can be looked up by, and `factor`
+/// and `offset` define it relative to the base unit of its category as exact
+/// fractions such as "381/1250", so that base = value × factor + offset. They
+/// are null for currencies, whose factors are exchange rates.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnitInfo {
+    #[schema(example = "foot")]
+    pub name: String,
+    #[schema(example = "ft")]
+    pub symbol: String,
+    pub category: String,
+    pub aliases: Vec<String>,
+    #[schema(example = "feet")]
+    pub plural: String,
+    pub is_base: bool,
+    #[schema(example = "381/1250")]
+    pub factor: Option<String>,
+    #[schema(example = "0")]
+    pub offset: Option<String>,
+    pub systems: Vec<UnitSystem>,
+    pub description: Option<String>,
+}
+
+/// Compares units by the size of one of each in the base unit, exactly when
+/// both are defined exactly, with units of unknown size such as currencies
+/// without a rate last
+fn compare_magnitude(a: &dyn AnyUnit, b: &dyn AnyUnit) -> Ordering {
+    match (a.exact(), b.exact()) {
+        (Some(a), Some(b)) => a.factor().cmp(b.factor()),
+        _ => {
+            let size = |unit: &dyn AnyUnit| unit.to_base(1.0) - unit.to_base(0.0);
+            size(a).total_cmp(&size(b))
+        }
+    }
 }
 
 /// Service for handling unit conversions
@@ -61,48 +238,314 @@ pub struct ConversionService;
 
 impl ConversionService {
     /// Convert a value from one unit to another
+    ///
+    /// Without a category, it's inferred from the units when both are registered
+    /// units sharing one, and otherwise they're converted as unit expressions.
     pub fn convert(request: ConversionRequest) -> Result<ConversionResponse, ApiError> {
+        let response = Self::convert_unchecked(request)?;
+        Self::check_finite(response.to_value, &response.to_unit)?;
+        Ok(response)
+    }
+    
+    /// Fails if a result is too large for an f64, which JSON would show as null
+    fn check_finite(value: f64, unit: &str) -> Result<(), ApiError> {
+        match value.is_finite() {
+            true => Ok(()),
+            false => Err(ApiError::BadRequest(format!("The result, {} {}, isn't a finite number", value, unit))),
+        }
+    }
+    
+    /// Convert a value like `convert`, but leave checking the result to the caller
+    fn convert_unchecked(request: ConversionRequest) -> Result<ConversionResponse, ApiError> {
         let registry = registry().read().map_err(|_| {
             ApiError::InternalError("Failed to access unit registry".to_string())
         })?;
         
-        // Convert to static strings for registry lookup
-        let from_category = get_static_str(&request.from_category, &CATEGORY_CACHE);
-        let from_unit = get_static_str(&request.from_unit, &UNIT_CACHE);
-        let to_unit = get_static_str(&request.to_unit, &UNIT_CACHE);
-        
-        // Get the source unit
-        let from_unit_impl = registry.get_unit(from_category, from_unit)
-            .ok_or_else(|| ApiError::NotFound(format!(
-                "Unit '{}' not found in category '{}'", 
-                request.from_unit, request.from_category
-            )))?;
-            
-        // Get the target unit
-        let to_unit_impl = registry.get_unit(from_category, to_unit)
-            .ok_or_else(|| ApiError::NotFound(format!(
-                "Unit '{}' not found in category '{}'", 
-                request.to_unit, request.from_category
-            )))?;
+        let category = match &request.from_category {
+            Some(category) => category.as_str(),
+            None => match registry.infer_category(&request.from_unit, &request.to_unit)? {
+                Some(category) => category,
+                None => {
+                    // Units of different categories aren't converted by dimension,
+                    // which would turn a temperature interval into a temperature
+                    let from = registry.lookup_unit(&request.from_unit, None);
+                    let to = registry.lookup_unit(&request.to_unit, None);
+                    if let (Ok(Some(from)), Ok(Some(to))) = (from, to) {
+                        return Err(Self::different_categories(from, to));
+                    }
+                    drop(registry);
+                    return Self::convert_expression(&request);
+                }
+            },
+        };
+        
+        // Get the source and target units by symbol, name or alias
+        let from_unit_impl = registry.resolve_unit(&request.from_unit, Some(category))?;
+        let to_unit_impl = registry.resolve_unit(&request.to_unit, Some(category))?;
             
         // Ensure units are compatible (same category)
         if from_unit_impl.category_name() != to_unit_impl.category_name() {
-            return Err(ApiError::BadRequest(format!(
-                "Cannot convert between different categories: '{}' and '{}'",
-                from_unit_impl.category_name(), to_unit_impl.category_name()
-            )));
+            return Err(Self::different_categories(from_unit_impl, to_unit_impl));
+        }
+        
+        // Currencies convert at the rates of the moment, which must exist
+        let rate_timestamp = Self::rate_timestamp(from_unit_impl, to_unit_impl)?;
+        
+        // Compose the exact definitions of both units so the value is only rounded once
+        let exact_value = match (from_unit_impl.exact(), to_unit_impl.exact()) {
+            (Some(from), Some(to)) => Some(from.to(to).to_base(&request.value.to_rational())),
+            _ => None,
+        };
+        
+        if request.exact && exact_value.is_none() {
+            return Err(Self::no_exact_conversion(from_unit_impl, to_unit_impl));
         }
         
-        // Convert to base unit then to target unit
-        let base_value = from_unit_impl.to_base(request.value);
-        let target_value = to_unit_impl.from_base(base_value);
+        // Otherwise convert to base unit then to target unit
+        let target_value = match &exact_value {
+            Some(value) => exact::rational_to_f64(value),
+            None => to_unit_impl.from_base(from_unit_impl.to_base(request.value.to_f64())),
+        };
+        
+        // In precision mode, convert on arbitrary-precision decimals instead
+        let decimal_value = match request.precision {
+            Some(precision) => {
+                let precision = Self::check_precision(precision)?;
+                let target = from_unit_impl.to_base_decimal(request.value.as_decimal())
+                    .and_then(|base| to_unit_impl.from_base_decimal(&base))
+                    .ok_or_else(|| Self::no_exact_conversion(from_unit_impl, to_unit_impl))?;
+                Some(decimal::format_decimal(&target, precision))
+            },
+            None => None,
+        };
+        
+        let uncertainty = Self::convert_uncertainty(&request, |x| to_unit_impl.from_base(from_unit_impl.to_base(x)))?;
         
         Ok(ConversionResponse {
-            from_value: request.value,
+            from_value: request.value.to_f64(),
             from_unit: from_unit_impl.symbol().to_string(),
             to_value: target_value,
             to_unit: to_unit_impl.symbol().to_string(),
             category: from_unit_impl.category_name().to_string(),
+            exact_value: exact_value
+                .filter(|_| request.exact)
+                .map(|value| exact::format_rational(&value)),
+            decimal_value,
+            rate_timestamp,
+            uncertainty,
+        })
+    }
+    
+    /// Returns when the exchange rates behind a conversion were quoted, which is
+    /// the older of the two rates, or None if neither unit is a currency
+    fn rate_timestamp(from: &dyn AnyUnit, to: &dyn AnyUnit) -> Result<Option<DateTime<Utc>>, ApiError> {
+        let mut timestamp: Option<DateTime<Utc>> = None;
+        
+        for unit in [from, to] {
+            if let Some(currency) = unit.as_any().downcast_ref::<Currency>() {
+                let rate = currency.rate().ok_or_else(|| ApiError::NotFound(format!(
+                    "No exchange rate for currency '{}'", unit.symbol()
+                )))?;
+                timestamp = Some(timestamp.map_or(rate.timestamp, |t| t.min(rate.timestamp)));
+            }
+        }
+        
+        Ok(timestamp)
+    }
+    
+    /// Carries the uncertainty of a request's value, if it has one, through a conversion
+    fn convert_uncertainty(request: &ConversionRequest, convert: impl Fn(f64) -> f64) -> Result<Option<Uncertainty>, ApiError> {
+        match &request.uncertainty {
+            Some(uncertainty) => {
+                uncertainty.check(request.value.to_f64())?;
+                Ok(Some(uncertainty.map(convert)))
+            },
+            None => Ok(None),
+        }
+    }
+    
+    fn different_categories(from: &dyn AnyUnit, to: &dyn AnyUnit) -> ApiError {
+        ApiError::BadRequest(format!(
+            "Cannot convert between different categories: '{}' and '{}'",
+            from.category_name(), to.category_name()
+        ))
+    }
+    
+    fn no_exact_conversion(from: &dyn AnyUnit, to: &dyn AnyUnit) -> ApiError {
+        ApiError::BadRequest(format!(
+            "No exact conversion from '{}' to '{}'", from.symbol(), to.symbol()
+        ))
+    }
+    
+    /// Checks that a requested number of significant digits is supported
+    fn check_precision(precision: u64) -> Result<u64, ApiError> {
+        if precision == 0 || precision > decimal::MAX_PRECISION {
+            return Err(ApiError::BadRequest(format!(
+                "Precision must be between 1 and {} significant digits", decimal::MAX_PRECISION
+            )));
+        }
+        Ok(precision)
+    }
+    
+    /// Convert between two unit expressions whose dimensions match, such as
+    /// "N·m" and "ft·lbf", without needing a category
+    pub fn convert_expression(request: &ConversionRequest) -> Result<ConversionResponse, ApiError> {
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        
+        let from_unit = CompoundUnit::parse(&request.from_unit, &registry)?;
+        let to_unit = CompoundUnit::parse(&request.to_unit, &registry)?;
+        
+        let (target_value, exact_value) = if request.exact {
+            let value = from_unit.convert_exact(&request.value.to_rational(), &to_unit)?;
+            (exact::rational_to_f64(&value), Some(exact::format_rational(&value)))
+        } else {
+            (from_unit.convert(request.value.to_f64(), &to_unit)?, None)
+        };
+        
+        let decimal_value = match request.precision {
+            Some(precision) => {
+                let precision = Self::check_precision(precision)?;
+                let target = from_unit.convert_decimal(request.value.as_decimal(), &to_unit)?;
+                Some(decimal::format_decimal(&target, precision))
+            },
+            None => None,
+        };
+        
+        let uncertainty = Self::convert_uncertainty(request, |x| to_unit.from_base(from_unit.to_base(x)))?;
+        
+        let category = Self::category_of(&registry, &to_unit);
+        
+        Ok(ConversionResponse {
+            from_value: request.value.to_f64(),
+            from_unit: from_unit.expression().to_string(),
+            to_value: target_value,
+            to_unit: to_unit.expression().to_string(),
+            category,
+            exact_value,
+            decimal_value,
+            rate_timestamp: None,
+            uncertainty,
+        })
+    }
+    
+    /// Returns the category matching a unit expression's dimension, or the
+    /// dimension itself, telling temperature intervals from temperatures
+    fn category_of(registry: &UnitRegistry, unit: &CompoundUnit) -> String {
+        if unit.dimension() == Dimension::TEMPERATURE && !unit.is_temperature() {
+            return TemperatureIntervalCategory.name().to_string();
+        }
+        registry.category_for_dimension(unit.dimension())
+            .map(|c| c.name().to_string())
+            .unwrap_or_else(|| unit.dimension().to_string())
+    }
+    
+    /// Convert a free-text query such as "3 mi + 200 yd to km"
+    pub fn convert_query(query: &str) -> Result<QueryResponse, ApiError> {
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        
+        let parsed = parse_query(query, &registry)?;
+        let target_value = parsed.evaluate()?;
+        Self::check_finite(target_value, parsed.target.expression())?;
+        
+        let category = Self::category_of(&registry, &parsed.target);
+        
+        Ok(QueryResponse {
+            query: query.to_string(),
+            quantities: parsed.quantities.iter()
+                .map(|q| QueryQuantity {
+                    value: q.value,
+                    unit: q.unit.expression().to_string(),
+                })
+                .collect(),
+            to_value: target_value,
+            to_unit: parsed.target.expression().to_string(),
+            category,
+        })
+    }
+    
+    /// Convert a batch of values, collecting a result or an error for each item
+    /// so that one bad item doesn't fail the whole batch
+    pub fn convert_batch(requests: Vec<ConversionRequest>) -> Vec<BatchConversionResult> {
+        requests.into_iter()
+            .enumerate()
+            .map(|(index, request)| match Self::convert(request) {
+                Ok(result) => BatchConversionResult {
+                    index,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(err) => {
+                    let error = match err {
+                        ApiError::NotFound(_) | ApiError::UnitNotFound { .. } => "unit_not_found",
+                        ApiError::Ambiguous { .. } => "ambiguous_unit",
+                        ApiError::BadRequest(_) => "invalid_conversion",
+                        _ => "conversion_failed",
+                    };
+                    
+                    BatchConversionResult {
+                        index,
+                        result: None,
+                        error: Some(BatchConversionError {
+                            error: error.to_string(),
+                            message: err.to_string(),
+                        }),
+                    }
+                }
+            })
+            .collect()
+    }
+    
+    /// Convert a range, either end of which may be open
+    ///
+    /// Each end is converted as a value of its own, so that a unit's offset
+    /// applies to both: 68–77 °F is 20–25 °C, not 20 °C wide.
+    pub fn convert_range(request: &RangeConversionRequest) -> Result<RangeConversionResponse, ApiError> {
+        let range = match (&request.range, &request.unit) {
+            (Some(range), None) if request.min.is_none() && request.max.is_none() => parse_range(range)?,
+            (None, Some(unit)) => Range { min: request.min, max: request.max, unit: unit.clone() },
+            _ => return Err(ApiError::BadRequest(
+                "Give either a range, or a unit with the range's min and max".to_string()
+            )),
+        };
+        match (range.min, range.max) {
+            (None, None) => return Err(ApiError::BadRequest(
+                "A range needs a min, a max or both".to_string()
+            )),
+            (Some(min), Some(max)) if min > max => return Err(ApiError::BadRequest(format!(
+                "The min of a range, {}, can't be above its max, {}", min, max
+            ))),
+            _ => {},
+        }
+        
+        let convert = |value: f64| Self::convert_unchecked(ConversionRequest {
+            value: value.into(),
+            from_category: request.from_category.clone(),
+            from_unit: range.unit.clone(),
+            to_unit: request.to_unit.clone(),
+            ..Default::default()
+        }).and_then(|response| match response.to_value.is_finite() {
+            true => Ok(response),
+            false => Err(ApiError::BadRequest(format!(
+                "The end {} {} of the range is {} {}, which isn't a finite number",
+                value, range.unit, response.to_value, response.to_unit
+            ))),
+        });
+        let min = range.min.map(convert).transpose()?;
+        let max = range.max.map(convert).transpose()?;
+        
+        let end = min.as_ref().or(max.as_ref()).expect("a range has at least one end");
+        Ok(RangeConversionResponse {
+            from_min: range.min,
+            from_max: range.max,
+            from_unit: end.from_unit.clone(),
+            to_min: min.as_ref().map(|r| r.to_value),
+            to_max: max.as_ref().map(|r| r.to_value),
+            to_unit: end.to_unit.clone(),
+            category: end.category.clone(),
         })
     }
     
@@ -137,46 +580,141 @@ impl ConversionService {
             .collect())
     }
     
-    /// Compare two measurements
+    /// Describe every registered category, in the given order
+    pub fn describe_categories(order: ListingOrder) -> Result<Vec<CategoryInfo>, ApiError> {
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        
+        let mut categories = registry.get_categories();
+        match order {
+            ListingOrder::Registration => {},
+            ListingOrder::Name => categories.sort_by_key(|c| c.name()),
+            ListingOrder::Factor => return Err(ApiError::BadRequest(
+                "Categories can't be ordered by factor, only by registration or name".to_string()
+            )),
+        }
+        
+        Ok(categories.iter()
+            .map(|c| CategoryInfo {
+                name: c.name().to_string(),
+                description: c.description().to_string(),
+                base_unit: c.base_unit().map(str::to_string),
+                dimension: c.dimension().map(|d| d.to_string()),
+                units: registry.get_units_for_category(c.name()).len(),
+            })
+            .collect())
+    }
+    
+    /// Describe every unit in a category, in the given order
+    pub fn describe_units(category_name: &str, order: ListingOrder) -> Result<Vec<UnitInfo>, ApiError> {
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        
+        let category = registry.get_category(category_name)
+            .ok_or_else(|| ApiError::NotFound(format!("Category '{}' not found", category_name)))?;
+        let base_unit = category.base_unit();
+        let mut aliases = registry.aliases_in_category(category.name());
+        
+        let mut units = registry.get_units_for_category(category.name());
+        match order {
+            ListingOrder::Registration => {},
+            ListingOrder::Factor => units.sort_by(|a, b| compare_magnitude(*a, *b)),
+            ListingOrder::Name => units.sort_by_key(|u| u.symbol()),
+        }
+        
+        Ok(units.iter()
+            .map(|u| UnitInfo {
+                name: u.name().to_string(),
+                symbol: u.symbol().to_string(),
+                category: u.category_name().to_string(),
+                aliases: aliases.remove(u.symbol()).unwrap_or_default()
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+                plural: pluralize(u.name()),
+                is_base: base_unit == Some(u.symbol()),
+                factor: u.exact().map(|e| exact::format_rational(e.factor())),
+                offset: u.exact().map(|e| exact::format_rational(e.offset())),
+                systems: u.systems().to_vec(),
+                description: u.description().map(str::to_string),
+            })
+            .collect())
+    }
+    
+    /// Compare two measurements, inferring the category from the units if it
+    /// isn't given, and treating them as equal within the default tolerance
     pub fn compare(
         value1: f64, 
         unit1: &str, 
         value2: f64, 
         unit2: &str, 
-        category: &str
+        category: Option<&str>
     ) -> Result<i8, ApiError> {
+        Self::compare_within(value1, unit1, value2, unit2, category, &Tolerance::default())
+            .map(|comparison| comparison.result)
+    }
+    
+    /// Compare two measurements, treating them as equal when they're within
+    /// `tolerance` of each other in the second measurement's unit
+    pub fn compare_within(
+        value1: f64, 
+        unit1: &str, 
+        value2: f64, 
+        unit2: &str, 
+        category: Option<&str>,
+        tolerance: &Tolerance
+    ) -> Result<Comparison, ApiError> {
         let registry = registry().read().map_err(|_| {
             ApiError::InternalError("Failed to access unit registry".to_string())
         })?;
         
-        // Convert to static strings for registry lookup
-        let static_category = get_static_str(category, &CATEGORY_CACHE);
-        let static_unit1 = get_static_str(unit1, &UNIT_CACHE);
-        let static_unit2 = get_static_str(unit2, &UNIT_CACHE);
+        let category = match category {
+            Some(category) => Some(category),
+            None => registry.infer_category(unit1, unit2)?,
+        };
         
-        // Get the first unit
-        let unit1_impl = registry.get_unit(static_category, static_unit1)
-            .ok_or_else(|| ApiError::NotFound(format!(
-                "Unit '{}' not found in category '{}'", unit1, category
-            )))?;
-            
-        // Get the second unit
-        let unit2_impl = registry.get_unit(static_category, static_unit2)
-            .ok_or_else(|| ApiError::NotFound(format!(
-                "Unit '{}' not found in category '{}'", unit2, category
-            )))?;
-            
-        // Convert both to base units for comparison
-        let base_value1 = unit1_impl.to_base(value1);
-        let base_value2 = unit2_impl.to_base(value2);
-        
-        // Compare and return the result
-        if (base_value1 - base_value2).abs() < f64::EPSILON {
-            Ok(0) // Equal
-        } else if base_value1 < base_value2 {
-            Ok(-1) // Less than
-        } else {
-            Ok(1) // Greater than
+        // Get both units by symbol, name or alias
+        let unit1_impl = registry.resolve_unit(unit1, category)?;
+        let unit2_impl = registry.resolve_unit(unit2, category)?;
+        if unit1_impl.category_name() != unit2_impl.category_name() {
+            return Err(ApiError::BadRequest(format!(
+                "Cannot compare different categories: '{}' and '{}'",
+                unit1_impl.category_name(), unit2_impl.category_name()
+            )));
+        }
+        Self::rate_timestamp(unit1_impl, unit2_impl)?;
+        
+        // Order exactly when both units have exact definitions, and express the
+        // first value in the second unit with a single rounding
+        let mut ordering = None;
+        let mut converted = None;
+        if let (Some(exact1), Some(exact2)) = (unit1_impl.exact(), unit2_impl.exact()) {
+            if let (Some(v1), Some(v2)) = (exact::rational_from_f64(value1), exact::rational_from_f64(value2)) {
+                ordering = Some(exact1.to_base(&v1).cmp(&exact2.to_base(&v2)));
+            }
+            converted = exact::convert_exact(value1, exact1, exact2).map(|v| exact::rational_to_f64(&v));
         }
+        let value1 = converted.unwrap_or_else(|| unit2_impl.from_base(unit1_impl.to_base(value1)));
+        
+        let ordering = match ordering {
+            Some(Ordering::Equal) => Ordering::Equal,
+            _ if tolerance.approx_eq(value1, value2) => Ordering::Equal,
+            Some(ordering) => ordering,
+            None => value1.partial_cmp(&value2).ok_or_else(|| ApiError::BadRequest(format!(
+                "Cannot compare {} {} and {} {}: the result isn't a number", value1, unit2, value2, unit2
+            )))?,
+        };
+        
+        Ok(Comparison {
+            result: match ordering {
+                Ordering::Less => -1,
+                Ordering::Equal => 0,
+                Ordering::Greater => 1,
+            },
+            difference: value1 - value2,
+            ratio: (value2 != 0.0).then(|| value1 / value2),
+        })
     }
 } 
\ No newline at end of file
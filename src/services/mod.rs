@@ -1,4 +1,23 @@
+pub mod calculation;
 pub mod conversion;
+pub mod rates;
+pub mod repl;
+pub mod units;
 
 // Re-export key types for easier imports
-pub use conversion::{ConversionService, ConversionRequest, ConversionResponse}; 
\ No newline at end of file
+pub use conversion::{
+    ConversionService, ConversionRequest, ConversionResponse,
+    BatchConversionResult, BatchConversionError, Comparison,
+    RangeConversionRequest, RangeConversionResponse,
+    QueryResponse, QueryQuantity, CategoryInfo, UnitInfo
+};
+pub use calculation::{
+    CalculationService, CalculationRequest, CalculationResponse,
+    CalculationNode, CalculationOperation, CalculationOperator, CalculationQuantity
+};
+pub use rates::{RateService, RatesResponse};
+pub use units::{
+    UnitService, UnitDefinition, BaseUnitDefinition, CategoryDefinition,
+    UnitResponse, CategoryResponse, UnitSearchResult
+};
+pub use repl::{ReplSession, ReplOutput};
\ No newline at end of file
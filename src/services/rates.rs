@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use crate::errors::ApiError;
+use crate::models::registry;
+use crate::models::rates::{rate_provider, ExchangeRate, RateProvider, RateUpdate};
+use crate::models::units::currency::register_currencies;
+
+/// Data transfer object listing the exchange rates currencies convert at
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RatesResponse {
+    pub base: String,
+    pub source: String,
+    pub rates: BTreeMap<String, ExchangeRate>,
+}
+
+/// Service for reading and updating exchange rates
+pub struct RateService;
+
+impl RateService {
+    /// List the rates of the installed provider
+    pub fn list_rates() -> RatesResponse {
+        Self::rates_of(rate_provider().as_ref())
+    }
+
+    /// Update the rates of the installed provider, registering any currencies
+    /// that are new, and return the rates as they now are
+    pub fn update_rates(update: RateUpdate) -> Result<RatesResponse, ApiError> {
+        let provider = rate_provider();
+        provider.update(update)?;
+
+        let mut registry = registry().write().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        register_currencies(&mut registry)?;
+
+        Ok(Self::rates_of(provider.as_ref()))
+    }
+
+    fn rates_of(provider: &dyn RateProvider) -> RatesResponse {
+        RatesResponse {
+            base: provider.base(),
+            source: provider.source(),
+            rates: provider.currencies().into_iter()
+                .filter_map(|code| provider.rate(&code).map(|rate| (code, rate)))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use crate::errors::ApiError;
+use crate::models::{registry, CompoundUnit, Quantity, UnitRegistry};
+use crate::services::ConversionService;
+
+/// Words that separate an expression from the unit to show it in
+const SEPARATORS: [&str; 6] = ["to", "in", "into", "as", "->", "→"];
+
+/// Characters that end a name within an expression
+const OPERATORS: [char; 12] = ['+', '-', '*', '/', '^', '(', ')', '=', '·', '⋅', '×', '÷'];
+
+/// Commands the session understands, for completion and `:help`
+const COMMANDS: [(&str, &str); 5] = [
+    (":units", "[category]  list the categories, or the units of a category"),
+    (":vars", "            list the variables"),
+    (":clear", "           forget every variable"),
+    (":help", "            show this help"),
+    (":quit", "            leave the session"),
+];
+
+/// Largest exponent accepted in `^`, which keeps unit exponents from overflowing
+const MAX_EXPONENT: i32 = 64;
+
+/// What evaluating a line produced
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplOutput {
+    /// Text to show, such as a result or a listing
+    Text(String),
+    /// Nothing to show, as for a blank line
+    Empty,
+    /// The user asked to leave
+    Quit,
+}
+
+/// An interactive calculator session over quantities with units
+///
+/// Each line is an expression such as `12 mi / 45 min to km/h`, an assignment
+/// such as `d = 12 mi`, or a command such as `:units length`. Arithmetic follows
+/// `Quantity`, so multiplying and dividing combines units, as in `mi/min`.
+/// Variables take precedence over units of the same name, so `t = 45 min` can be
+/// used even though `t` is also the tonne.
+#[derive(Debug, Default)]
+pub struct ReplSession {
+    variables: BTreeMap<String, Quantity>,
+}
+
+impl ReplSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluates one line of input
+    pub fn eval(&mut self, line: &str) -> Result<ReplOutput, ApiError> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(ReplOutput::Empty);
+        }
+        if let Some(command) = line.strip_prefix(':') {
+            return self.command(command);
+        }
+
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        let (name, expression) = match line.split_once('=') {
+            Some((name, expression)) if is_variable_name(name.trim()) => (Some(name.trim()), expression),
+            _ => (None, line),
+        };
+
+        let value = self.evaluate(expression, &registry)?;
+        if !value.number().is_finite() {
+            return Err(ApiError::BadRequest(format!("The result, {}, isn't a finite number", value)));
+        }
+        let output = match name {
+            Some(name) => {
+                let output = format!("{} = {}", name, value);
+                self.variables.insert(name.to_string(), value);
+                output
+            },
+            None => value.to_string(),
+        };
+
+        Ok(ReplOutput::Text(output))
+    }
+
+    /// Returns where the word being typed at `pos` starts, along with the
+    /// variables, unit symbols, aliases or commands it could be completed to
+    pub fn complete(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before = &line[..pos];
+        let start = before
+            .rfind(|c: char| c.is_whitespace() || OPERATORS.contains(&c))
+            .map(|i| i + before[i..].chars().next().map_or(1, char::len_utf8))
+            .unwrap_or(0);
+        let word = &before[start..];
+
+        let mut candidates: Vec<String> = if before[..start].trim() == ":units" {
+            ConversionService::list_categories().unwrap_or_default()
+        } else if start == 0 && word.starts_with(':') {
+            COMMANDS.iter().map(|(command, _)| command.to_string()).collect()
+        } else if word.is_empty() {
+            Vec::new()
+        } else {
+            let registry = match registry().read() {
+                Ok(registry) => registry,
+                Err(_) => return (start, Vec::new()),
+            };
+            self.variables.keys()
+                .map(|name| name.to_string())
+                .chain(registry.lookup_terms().into_iter()
+                    // Names with spaces or operators can't be written in an expression
+                    .filter(|term| !term.contains(|c: char| c.is_whitespace() || OPERATORS.contains(&c)))
+                    .map(|term| term.to_string()))
+                .collect()
+        };
+
+        candidates.retain(|candidate| candidate.starts_with(word));
+        candidates.sort();
+        candidates.dedup();
+        (start, candidates)
+    }
+
+    fn command(&mut self, command: &str) -> Result<ReplOutput, ApiError> {
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or("");
+        let argument = words.collect::<Vec<_>>().join(" ");
+
+        let text = match (name, argument.is_empty()) {
+            ("units", true) => {
+                let mut categories = ConversionService::list_categories()?;
+                categories.sort();
+                categories.join("\n")
+            },
+            ("units", false) => {
+                let mut units = ConversionService::list_units(&argument)?;
+                units.sort();
+                units.join(", ")
+            },
+            ("vars", true) if self.variables.is_empty() => "No variables defined".to_string(),
+            ("vars", true) => self.variables.iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ("clear", true) => {
+                self.variables.clear();
+                return Ok(ReplOutput::Empty);
+            },
+            ("help", true) => help(),
+            ("quit" | "q" | "exit", true) => return Ok(ReplOutput::Quit),
+            _ => return Err(ApiError::BadRequest(format!(
+                "Unknown command ':{}'; :help lists the commands", command.trim()
+            ))),
+        };
+
+        Ok(ReplOutput::Text(text))
+    }
+
+    /// Evaluates an expression, converting it to the unit after `to` if there is one
+    fn evaluate(&self, text: &str, registry: &UnitRegistry) -> Result<Quantity, ApiError> {
+        let (expression, target) = split_target(text);
+
+        let tokens = tokenize(expression)?;
+        let mut parser = Parser { tokens: &tokens, pos: 0, session: self, registry };
+        let value = parser.parse_expression()?;
+        if let Some(token) = tokens.get(parser.pos) {
+            return Err(ApiError::BadRequest(format!("Unexpected '{}' in '{}'", token, expression.trim())));
+        }
+
+        match target {
+            Some(target) => value.convert_to(&CompoundUnit::parse(target, registry)?),
+            None => Ok(value),
+        }
+    }
+}
+
+fn help() -> String {
+    let mut lines = vec![
+        "Enter an expression such as '12 mi / 45 min to km/h', or assign one to a".to_string(),
+        "variable with 'd = 12 mi'. Commands:".to_string(),
+    ];
+    lines.extend(COMMANDS.iter().map(|(command, description)| format!("  {} {}", command, description)));
+    lines.join("\n")
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !SEPARATORS.contains(&name)
+}
+
+/// Splits off the target unit after the last separator word that has
+/// something on both sides, so "5 in to cm" converts inches
+fn split_target(text: &str) -> (&str, Option<&str>) {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                words.push((s, i));
+                start = None;
+            },
+            (false, None) => start = Some(i),
+            _ => {},
+        }
+    }
+
+    for (index, &(start, end)) in words.iter().enumerate().rev() {
+        if index > 0 && index + 1 < words.len() && SEPARATORS.contains(&&text[start..end]) {
+            return (&text[..start], Some(text[end..].trim()));
+        }
+    }
+
+    (text, None)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Name(String),
+    Operator(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Name(name) => write!(f, "{}", name),
+            Token::Operator(operator) => write!(f, "{}", operator),
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, ApiError> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let starts_number = c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit()));
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '*' && chars.get(i + 1) == Some(&'*') {
+            tokens.push(Token::Operator('^'));
+            i += 2;
+        } else if OPERATORS.contains(&c) {
+            let operator = match c {
+                '·' | '⋅' | '×' => '*',
+                '÷' => '/',
+                '=' => return Err(ApiError::BadRequest(format!("Unexpected '{}' in '{}'", c, expression.trim()))),
+                c => c,
+            };
+            tokens.push(Token::Operator(operator));
+            i += 1;
+        } else if starts_number {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // An exponent only counts when digits follow, so "2e" is 2 times e
+            if matches!(chars.get(i), Some('e' | 'E')) {
+                let digits = if matches!(chars.get(i + 1), Some('+' | '-')) { i + 2 } else { i + 1 };
+                if chars.get(digits).is_some_and(|c| c.is_ascii_digit()) {
+                    i = digits;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text.parse::<f64>()
+                .map_err(|_| ApiError::BadRequest(format!("Invalid number '{}'", text)))?;
+            tokens.push(Token::Number(number));
+        } else {
+            let start = i;
+            while i < chars.len() && !chars[i].is_whitespace() && !OPERATORS.contains(&chars[i]) {
+                i += 1;
+            }
+            tokens.push(Token::Name(chars[start..i].iter().collect()));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser that evaluates as it goes
+///
+/// Writing quantities next to each other multiplies them and binds tighter than
+/// `*` and `/`, so `12 mi / 45 min` divides 12 miles by 45 minutes.
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    session: &'a ReplSession,
+    registry: &'a UnitRegistry,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_expression(&mut self) -> Result<Quantity, ApiError> {
+        let mut value = self.parse_term()?;
+        while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek() {
+            let add = *operator == '+';
+            self.pos += 1;
+            let rhs = self.parse_term()?;
+            value = if add { value.add(rhs, self.registry)? } else { value.subtract(rhs, self.registry)? };
+        }
+        Ok(value)
+    }
+
+    fn parse_term(&mut self) -> Result<Quantity, ApiError> {
+        let mut value = self.parse_unary()?;
+        while let Some(Token::Operator(operator @ ('*' | '/'))) = self.peek() {
+            let multiply = *operator == '*';
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            value = if multiply { value.multiply(rhs, self.registry)? } else { value.divide(rhs, self.registry)? };
+        }
+        Ok(value)
+    }
+
+    fn parse_unary(&mut self) -> Result<Quantity, ApiError> {
+        match self.peek() {
+            Some(Token::Operator('-')) => {
+                self.pos += 1;
+                Ok(self.parse_unary()?.negate())
+            },
+            Some(Token::Operator('+')) => {
+                self.pos += 1;
+                self.parse_unary()
+            },
+            _ => self.parse_product(),
+        }
+    }
+
+    fn parse_product(&mut self) -> Result<Quantity, ApiError> {
+        let mut value = self.parse_power()?;
+        while matches!(self.peek(), Some(Token::Number(_) | Token::Name(_) | Token::Operator('('))) {
+            let unit_follows = matches!(self.peek(), Some(Token::Name(name)) if !self.session.variables.contains_key(name));
+            let rhs = self.parse_power()?;
+            value = match (value.unit(), rhs.unit()) {
+                // A number followed by a unit is a reading in that unit, such as 20 °C,
+                // rather than a quantity scaled by a number
+                (None, Some(unit)) if unit_follows => Quantity::new(value.number() * rhs.number(), unit.clone()),
+                _ => value.multiply(rhs, self.registry)?,
+            };
+        }
+        Ok(value)
+    }
+
+    fn parse_power(&mut self) -> Result<Quantity, ApiError> {
+        let value = self.parse_primary()?;
+        if self.peek() != Some(&Token::Operator('^')) {
+            return Ok(value);
+        }
+        self.pos += 1;
+
+        let negative = self.peek() == Some(&Token::Operator('-'));
+        if negative {
+            self.pos += 1;
+        }
+        let exponent = match self.peek() {
+            Some(Token::Number(n)) if n.fract() == 0.0 && *n <= MAX_EXPONENT as f64 => *n as i32,
+            _ => return Err(ApiError::BadRequest(format!(
+                "Exponents must be whole numbers no larger than {}", MAX_EXPONENT
+            ))),
+        };
+        self.pos += 1;
+
+        value.pow(if negative { -exponent } else { exponent }, self.registry)
+    }
+
+    fn parse_primary(&mut self) -> Result<Quantity, ApiError> {
+        let token = self.peek().cloned()
+            .ok_or_else(|| ApiError::BadRequest("Unexpected end of expression".to_string()))?;
+        self.pos += 1;
+
+        match token {
+            Token::Number(number) => Ok(Quantity::plain(number)),
+            Token::Name(name) => match self.session.variables.get(&name) {
+                Some(value) => Ok(value.clone()),
+                None => Ok(Quantity::new(1.0, CompoundUnit::parse(&name, self.registry)?)),
+            },
+            Token::Operator('(') => {
+                let value = self.parse_expression()?;
+                if self.peek() != Some(&Token::Operator(')')) {
+                    return Err(ApiError::BadRequest("Missing ')'".to_string()));
+                }
+                self.pos += 1;
+                Ok(value)
+            },
+            token => Err(ApiError::BadRequest(format!("Unexpected '{}'", token))),
+        }
+    }
+}
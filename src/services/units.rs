@@ -0,0 +1,234 @@
+use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
+use num_traits::Signed;
+use crate::errors::ApiError;
+use crate::models::{registry, exact, DecimalValue, DefinedUnit, DefinedCategory, LinearConversion, MatchKind};
+use crate::models::defined::{check_name, check_symbol, check_unused, check_category_name, intern};
+use crate::models::registry::{AnyUnit, UnitRegistry};
+
+/// Most units that can be defined at runtime, counting the base units of
+/// runtime categories, as each one's name and symbol stay allocated for good
+pub const MAX_CUSTOM_UNITS: usize = 1000;
+
+/// Data transfer object for defining a unit in terms of an existing one
+///
+/// One of the new unit equals `factor` times `unit`, plus `offset`, so a smoot is
+/// `{"name": "smoot", "symbol": "smoot", "factor": "1.7018", "unit": "m"}`.
+/// The new unit joins the category of `unit`, which `category` can pin down
+/// when the unit's symbol or name is ambiguous.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnitDefinition {
+    pub name: String,
+    pub symbol: String,
+    pub factor: DecimalValue,
+    #[serde(default)]
+    pub offset: Option<DecimalValue>,
+    pub unit: String,
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+/// The unit every other unit of a new category is defined in terms of
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BaseUnitDefinition {
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Data transfer object for defining a category along with its base unit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub base_unit: BaseUnitDefinition,
+}
+
+/// Data transfer object describing a unit defined at runtime, with the exact
+/// factor and offset that convert it to its category's base unit
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnitResponse {
+    pub name: String,
+    pub symbol: String,
+    pub category: String,
+    pub factor: String,
+    pub offset: String,
+}
+
+/// Data transfer object describing a category defined at runtime
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CategoryResponse {
+    pub name: String,
+    pub description: String,
+    pub base_unit: String,
+}
+
+/// A unit found by a search, with the symbol, name or alias that matched
+///
+/// `distance` is the number of edits between the query and `matched` for fuzzy
+/// matches, and the number of characters `matched` adds to the query for
+/// prefix matches.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnitSearchResult {
+    #[schema(example = "km")]
+    pub symbol: String,
+    #[schema(example = "kilometer")]
+    pub name: String,
+    #[schema(example = "length")]
+    pub category: String,
+    #[schema(example = "kilometer")]
+    pub matched: String,
+    pub match_kind: MatchKind,
+    pub distance: usize,
+}
+
+/// Service for searching, defining and removing units and categories
+pub struct UnitService;
+
+impl UnitService {
+    /// Search every unit, or those of one category, by symbol, name and alias,
+    /// returning at most `limit` results, best first
+    pub fn search(query: &str, category: Option<&str>, limit: usize) -> Result<Vec<UnitSearchResult>, ApiError> {
+        if query.trim().is_empty() {
+            return Err(ApiError::BadRequest("Search query must not be empty".to_string()));
+        }
+
+        let registry = registry().read().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+        if let Some(category) = category {
+            if registry.get_category(category).is_none() {
+                return Err(ApiError::NotFound(format!("Category '{}' not found", category)));
+            }
+        }
+
+        Ok(registry.search_units(query, category)
+            .into_iter()
+            .take(limit)
+            .map(|m| UnitSearchResult {
+                symbol: m.unit.symbol().to_string(),
+                name: m.unit.name().to_string(),
+                category: m.unit.category_name().to_string(),
+                matched: m.term.to_string(),
+                match_kind: m.kind,
+                distance: m.distance,
+            })
+            .collect())
+    }
+
+    /// Define a unit in terms of an existing unit and register it
+    pub fn define_unit(definition: UnitDefinition) -> Result<UnitResponse, ApiError> {
+        let mut registry = registry().write().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        let name = check_name("Unit name", &definition.name)?;
+        let symbol = check_symbol(&definition.symbol)?;
+        check_capacity(&registry)?;
+        check_unused(&registry, name, symbol)?;
+
+        let reference = registry.resolve_unit(&definition.unit, definition.category.as_deref())?;
+        let reference_conversion = reference.exact().ok_or_else(|| ApiError::BadRequest(format!(
+            "Unit '{}' has no exact definition, so units can't be defined in terms of it",
+            reference.symbol()
+        )))?;
+
+        let factor = definition.factor.to_rational();
+        if !factor.is_positive() {
+            return Err(ApiError::BadRequest(format!("Unit factor must be positive, not {}", definition.factor)));
+        }
+        let offset = definition.offset.as_ref().map(|o| o.to_rational()).unwrap_or_default();
+
+        // Convert through the reference unit in one step, so the new unit is as exact as it
+        let conversion = LinearConversion::new(factor, offset).then(reference_conversion);
+        let unit = DefinedUnit::new(
+            intern(name),
+            intern(symbol),
+            reference.category_name(),
+            reference.dimension(),
+            conversion,
+        );
+        let response = Self::unit_response(&unit);
+
+        registry.register_custom_unit(unit)?;
+        Ok(response)
+    }
+
+    /// Define a category along with its base unit and register both
+    pub fn define_category(definition: CategoryDefinition) -> Result<CategoryResponse, ApiError> {
+        let mut registry = registry().write().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        let name = check_category_name(&definition.name)?;
+        let description = definition.description.as_deref().map(str::trim).unwrap_or_default();
+        let unit_name = check_name("Unit name", &definition.base_unit.name)?;
+        let symbol = check_symbol(&definition.base_unit.symbol)?;
+
+        if registry.get_category(name).is_some() {
+            return Err(ApiError::BadRequest(format!("Category '{}' already registered", name)));
+        }
+        check_capacity(&registry)?;
+        check_unused(&registry, unit_name, symbol)?;
+
+        let category_name = intern(name);
+        let base_symbol = intern(symbol);
+        let category = DefinedCategory::new(category_name, intern(description), base_symbol);
+        let base_unit = DefinedUnit::new(
+            intern(unit_name),
+            base_symbol,
+            category_name,
+            None,
+            LinearConversion::parse("1", "0"),
+        );
+
+        registry.register_custom_category(category)?;
+        registry.register_custom_unit(base_unit)?;
+
+        Ok(CategoryResponse {
+            name: name.to_string(),
+            description: description.to_string(),
+            base_unit: symbol.to_string(),
+        })
+    }
+
+    /// Remove a unit defined at runtime
+    pub fn remove_unit(category_name: &str, symbol: &str) -> Result<(), ApiError> {
+        let mut registry = registry().write().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        registry.unregister_unit(category_name, symbol)
+    }
+
+    /// Remove a category defined at runtime along with all of its units,
+    /// returning the number of units removed
+    pub fn remove_category(name: &str) -> Result<usize, ApiError> {
+        let mut registry = registry().write().map_err(|_| {
+            ApiError::InternalError("Failed to access unit registry".to_string())
+        })?;
+
+        registry.unregister_category(name)
+    }
+
+    fn unit_response(unit: &DefinedUnit) -> UnitResponse {
+        let conversion = unit.exact().expect("defined units have exact conversions");
+        UnitResponse {
+            name: unit.name().to_string(),
+            symbol: unit.symbol().to_string(),
+            category: unit.category_name().to_string(),
+            factor: exact::format_rational(conversion.factor()),
+            offset: exact::format_rational(conversion.offset()),
+        }
+    }
+}
+
+/// Checks there's room for another unit defined at runtime
+fn check_capacity(registry: &UnitRegistry) -> Result<(), ApiError> {
+    if registry.custom_unit_count() >= MAX_CUSTOM_UNITS {
+        return Err(ApiError::BadRequest(format!(
+            "No more than {} units can be defined at runtime; remove some first", MAX_CUSTOM_UNITS
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::alias::pluralize;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::registry;
+use unit_conversion_api::services::ConversionService;
+use common::convert;
+
+fn resolve(term: &str, category: Option<&str>) -> Result<String, ApiError> {
+    let reg = registry().read().unwrap();
+    reg.resolve_unit(term, category).map(|u| u.symbol().to_string())
+}
+
+#[test]
+fn test_pluralize() {
+    assert_eq!(pluralize("meter"), "meters");
+    assert_eq!(pluralize("foot"), "feet");
+    assert_eq!(pluralize("square foot"), "square feet");
+    assert_eq!(pluralize("inch"), "inches");
+    assert_eq!(pluralize("century"), "centuries");
+    assert_eq!(pluralize("mile per hour"), "miles per hour");
+    assert_eq!(pluralize("pound-force"), "pounds-force");
+    assert_eq!(pluralize("celsius"), "celsius");
+    assert_eq!(pluralize("horsepower"), "horsepower");
+}
+
+#[test]
+fn test_alias_lookup() {
+    init();
+
+    // Names, plurals and British spellings
+    assert_eq!(resolve("meters", None).unwrap(), "m");
+    assert_eq!(resolve("metre", None).unwrap(), "m");
+    assert_eq!(resolve("kilometres", None).unwrap(), "km");
+    assert_eq!(resolve("feet", None).unwrap(), "ft");
+    assert_eq!(resolve("square feet", None).unwrap(), "ft²");
+    assert_eq!(resolve("miles per hour", None).unwrap(), "mph");
+    assert_eq!(resolve("pound per square inch", None).unwrap(), "psi");
+    assert_eq!(resolve("litres", None).unwrap(), "L");
+
+    // ASCII stand-ins for °, ², ³ and µ
+    assert_eq!(resolve("deg C", None).unwrap(), "°C");
+    assert_eq!(resolve("degF", None).unwrap(), "°F");
+    assert_eq!(resolve("degrees celsius", None).unwrap(), "°C");
+    assert_eq!(resolve("m2", None).unwrap(), "m²");
+    assert_eq!(resolve("sq ft", None).unwrap(), "ft²");
+    assert_eq!(resolve("cu m", None).unwrap(), "m³");
+    assert_eq!(resolve("um", None).unwrap(), "μm");
+    assert_eq!(resolve("µg", None).unwrap(), "μg");
+    assert_eq!(resolve("ft*lb", None).unwrap(), "ft⋅lb");
+
+    // Registered extras
+    assert_eq!(resolve("lbs", None).unwrap(), "lb");
+    assert_eq!(resolve("hrs", None).unwrap(), "h");
+}
+
+#[test]
+fn test_case_insensitive_lookup() {
+    init();
+
+    // Exact case wins over case-insensitive matches
+    assert_eq!(resolve("m", None).unwrap(), "m");
+    assert_eq!(resolve("Cal", None).unwrap(), "kcal");
+    assert_eq!(resolve("cal", None).unwrap(), "cal");
+
+    // Case-insensitive fallback, scoped to the category when one is given
+    assert_eq!(resolve("M", Some("length")).unwrap(), "m");
+    assert_eq!(resolve("Kilometers", None).unwrap(), "km");
+    assert_eq!(resolve("Feet", None).unwrap(), "ft");
+    assert_eq!(resolve("KN", Some("speed")).unwrap(), "kn");
+}
+
+#[test]
+fn test_ambiguous_alias() {
+    init();
+
+    // "M" is mach, but could also be the meter in capitals
+    match resolve("M", None) {
+        Err(ApiError::Ambiguous { candidates, .. }) => {
+            assert_eq!(candidates, ["M (mach, speed)", "m (meter, length)"]);
+        },
+        other => panic!("expected an ambiguous unit, got {:?}", other),
+    }
+    assert_eq!(resolve("M", Some("speed")).unwrap(), "M");
+
+    match resolve("KN", None) {
+        Err(ApiError::Ambiguous { candidates, .. }) => {
+            assert_eq!(candidates.len(), 2);
+            assert!(candidates.iter().any(|c| c.starts_with("kn (knot")));
+            assert!(candidates.iter().any(|c| c.starts_with("kN (kilonewton")));
+        },
+        other => panic!("expected an ambiguous unit error, got {:?}", other),
+    }
+
+    // Every unit a term matches ignoring case is a candidate, in a category too
+    match resolve("MJ", Some("energy")).and(resolve("mj", Some("energy"))) {
+        Err(ApiError::Ambiguous { candidates, .. }) => {
+            assert_eq!(candidates, ["MJ (megajoule, energy)", "mJ (millijoule, energy)"]);
+        },
+        other => panic!("expected an ambiguous unit error, got {:?}", other),
+    }
+
+    assert!(matches!(resolve("parsec", None), Err(ApiError::UnitNotFound { .. })));
+}
+
+#[test]
+fn test_conversion_with_aliases() {
+    init();
+
+    let response = convert(3.0, Some("length"), "feet", "Metres").unwrap();
+
+    assert_eq!(response.from_unit, "ft");
+    assert_eq!(response.to_unit, "m");
+    assert!((response.to_value - 0.9144).abs() < 1e-12);
+
+    let query = ConversionService::convert_query("72 deg F to degrees celsius").unwrap();
+    assert!((query.to_value - 22.2222).abs() < 1e-4);
+
+    let compound = convert(1.0, None, "kilograms/cu m", "lbs/ft3").unwrap();
+    assert!((compound.to_value - 0.062428).abs() < 1e-6);
+}
@@ -103,8 +103,8 @@ fn test_area_equality() {
     let ten_thousand_square_meters = Measurement::new(10_000.0, square_meter);
     assert_eq!(one_hectare, ten_thousand_square_meters);
     
-    // Approximately 0.092903 m² = 1 ft²
-    let zero_point_zero_nine_square_meters = Measurement::new(0.092903, square_meter);
+    // Exactly 0.09290304 m² = 1 ft²
+    let zero_point_zero_nine_square_meters = Measurement::new(0.09290304, square_meter);
     let one_square_foot = Measurement::new(1.0, square_foot);
     assert_eq!(zero_point_zero_nine_square_meters, one_square_foot);
 }
@@ -0,0 +1,89 @@
+mod common;
+
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::services::ConversionService;
+use common::request;
+
+#[test]
+fn test_batch_conversion() {
+    init();
+
+    let results = ConversionService::convert_batch(vec![
+        request(100.0, Some("length"), "cm", "m"),
+        request(0.0, Some("temperature"), "°C", "K"),
+        request(1.0, Some("mass"), "kg", "g"),
+    ]);
+
+    assert_eq!(results.len(), 3);
+
+    for (i, item) in results.iter().enumerate() {
+        assert_eq!(item.index, i);
+        assert!(item.error.is_none());
+    }
+
+    let m = results[0].result.as_ref().unwrap();
+    assert!((m.to_value - 1.0).abs() < f64::EPSILON);
+
+    let k = results[1].result.as_ref().unwrap();
+    assert!((k.to_value - 273.15).abs() < 1e-10);
+
+    let g = results[2].result.as_ref().unwrap();
+    assert!((g.to_value - 1000.0).abs() < 1e-10);
+}
+
+#[test]
+fn test_batch_conversion_partial_failure() {
+    init();
+
+    let results = ConversionService::convert_batch(vec![
+        request(1.0, Some("length"), "m", "cm"),
+        request(1.0, Some("length"), "parsec", "m"),
+        request(1.0, Some("length"), "m", "kg"),
+        request(1.0, Some("mass"), "lb", "oz"),
+    ]);
+
+    assert_eq!(results.len(), 4);
+
+    // Good items still succeed around the failures
+    assert!(results[0].result.is_some());
+    assert!(results[3].result.is_some());
+    assert!((results[3].result.as_ref().unwrap().to_value - 16.0).abs() < 1e-10);
+
+    // Each failure carries its index and a structured error
+    let missing = &results[1];
+    assert_eq!(missing.index, 1);
+    assert!(missing.result.is_none());
+    let error = missing.error.as_ref().unwrap();
+    assert_eq!(error.error, "unit_not_found");
+    assert!(error.message.contains("parsec"));
+
+    let wrong_category = &results[2];
+    assert_eq!(wrong_category.index, 2);
+    assert_eq!(wrong_category.error.as_ref().unwrap().error, "unit_not_found");
+}
+
+#[test]
+fn test_batch_non_finite_result() {
+    init();
+
+    let results = ConversionService::convert_batch(vec![
+        request(1e308, Some("length"), "km", "mm"),
+        request(1e308, None, "km/h", "mm/s"),
+        request(1e300, Some("length"), "km", "mm"),
+    ]);
+
+    // A result too large for an f64 is an error of its own item, not a null value
+    for item in &results[..2] {
+        let error = item.error.as_ref().unwrap();
+        assert_eq!(error.error, "invalid_conversion");
+        assert!(error.message.contains("isn't a finite number"), "{}", error.message);
+    }
+    assert!((results[2].result.as_ref().unwrap().to_value - 1e306).abs() < 1e292);
+}
+
+#[test]
+fn test_empty_batch() {
+    init();
+
+    assert!(ConversionService::convert_batch(Vec::new()).is_empty());
+}
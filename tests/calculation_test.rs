@@ -0,0 +1,197 @@
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{CalculationService, CalculationRequest, CalculationResponse};
+
+fn calculate(request: Value) -> Result<CalculationResponse, ApiError> {
+    let request: CalculationRequest = serde_json::from_value(request).unwrap();
+    CalculationService::calculate(&request)
+}
+
+#[test]
+fn test_sum_of_operands() {
+    init();
+
+    // A cup and two fluid ounces, in millilitres
+    let response = calculate(json!({
+        "operands": [
+            { "value": 1, "unit": "cup" },
+            { "value": 2, "unit": "fl oz" },
+        ],
+        "to_unit": "mL",
+    })).unwrap();
+    assert!((response.value - 295.735_295_625).abs() < 1e-6);
+    assert_eq!(response.unit.as_deref(), Some("mL"));
+    assert_eq!(response.category.as_deref(), Some("volume"));
+
+    // Without a target unit the sum is in the unit of the first operand
+    let response = calculate(json!({
+        "operands": [{ "value": 1, "unit": "m" }, { "value": 50, "unit": "cm" }],
+    })).unwrap();
+    assert!((response.value - 1.5).abs() < 1e-12);
+    assert_eq!(response.unit.as_deref(), Some("m"));
+}
+
+#[test]
+fn test_expression_tree() {
+    init();
+
+    // 12 miles in 45 minutes, in kilometres per hour
+    let response = calculate(json!({
+        "expression": {
+            "op": "divide",
+            "operands": [{ "value": 12, "unit": "mi" }, { "value": 45, "unit": "min" }],
+        },
+        "to_unit": "km/h",
+    })).unwrap();
+    assert!((response.value - 25.749504).abs() < 1e-9);
+    assert_eq!(response.category.as_deref(), Some("speed"));
+
+    // Products and quotients combine units, and units that cancel out are dropped
+    let response = calculate(json!({
+        "expression": {
+            "op": "multiply",
+            "operands": [{ "value": 3, "unit": "m" }, { "value": 4, "unit": "m" }],
+        },
+    })).unwrap();
+    assert_eq!((response.value, response.unit.as_deref()), (12.0, Some("m²")));
+    assert_eq!(response.category.as_deref(), Some("area"));
+
+    let response = calculate(json!({
+        "expression": {
+            "op": "divide",
+            "operands": [
+                { "op": "add", "operands": [{ "value": 1, "unit": "km" }, { "value": 500, "unit": "m" }] },
+                { "value": 500, "unit": "m" },
+            ],
+        },
+    })).unwrap();
+    assert!((response.value - 3.0).abs() < 1e-12);
+    assert_eq!((response.unit, response.category), (None, None));
+
+    // Subtracting a single operand negates it
+    let response = calculate(json!({
+        "expression": { "op": "subtract", "operands": [{ "value": 5, "unit": "m" }] },
+    })).unwrap();
+    assert_eq!((response.value, response.unit.as_deref()), (-5.0, Some("m")));
+}
+
+#[test]
+fn test_temperature_arithmetic() {
+    init();
+
+    // The difference of two temperatures is an interval
+    let response = calculate(json!({
+        "expression": {
+            "op": "subtract",
+            "operands": [{ "value": 20, "unit": "°C" }, { "value": 5, "unit": "°C" }],
+        },
+    })).unwrap();
+    assert_eq!((response.value, response.unit.as_deref()), (15.0, Some("Δ°C")));
+    assert_eq!(response.category.as_deref(), Some("temperature_interval"));
+
+    // An interval moves a temperature along its scale, without applying the offset twice
+    let response = calculate(json!({
+        "operands": [{ "value": 20, "unit": "°C" }, { "value": 18, "unit": "Δ°F" }],
+        "to_unit": "°F",
+    })).unwrap();
+    assert!((response.value - 86.0).abs() < 1e-9);
+    assert_eq!(response.category.as_deref(), Some("temperature"));
+
+    let err = calculate(json!({
+        "operands": [{ "value": 20, "unit": "°C" }, { "value": 5, "unit": "°C" }],
+    })).unwrap_err();
+    assert!(err.to_string().contains("Cannot add the temperatures"));
+
+    // Scaling a temperature scales its kelvins rather than its reading
+    let response = calculate(json!({
+        "expression": { "op": "multiply", "operands": [{ "value": 20, "unit": "°C" }, { "value": 2 }] },
+    })).unwrap();
+    assert!((response.value - 586.3).abs() < 1e-9);
+    assert_eq!(response.unit.as_deref(), Some("K"));
+
+    // Temperature intervals cancel against kelvins, as in a heat flow
+    let response = calculate(json!({
+        "expression": {
+            "op": "multiply",
+            "operands": [
+                { "value": 5, "unit": "W/(m²·K)" },
+                { "value": 2, "unit": "m²" },
+                { "value": 10, "unit": "Δ°C" },
+            ],
+        },
+    })).unwrap();
+    assert!((response.value - 100.0).abs() < 1e-9);
+    assert_eq!(response.unit.as_deref(), Some("W"));
+    assert_eq!(response.category.as_deref(), Some("power"));
+
+    let response = calculate(json!({
+        "expression": {
+            "op": "multiply",
+            "operands": [{ "value": 1, "unit": "J/K²" }, { "value": 18, "unit": "Δ°F" }],
+        },
+    })).unwrap();
+    assert!((response.value - 10.0).abs() < 1e-9);
+    assert_eq!(response.unit.as_deref(), Some("J/K"));
+}
+
+#[test]
+fn test_invalid_calculations() {
+    init();
+
+    assert!(matches!(calculate(json!({})), Err(ApiError::BadRequest(_))));
+    assert!(matches!(
+        calculate(json!({ "operands": [{ "value": 1, "unit": "kg" }, { "value": 1, "unit": "m" }] })),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        calculate(json!({ "expression": { "op": "multiply", "operands": [] } })),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        calculate(json!({ "operands": [{ "value": 1, "unit": "m" }], "to_unit": "kg" })),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        calculate(json!({
+            "expression": { "op": "divide", "operands": [{ "value": 1 }, { "value": 0 }] },
+        })),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        calculate(json!({ "operands": [{ "value": 1, "unit": "fot" }] })),
+        Err(ApiError::UnitNotFound { .. })
+    ));
+}
+
+#[actix_rt::test]
+async fn test_calculate_endpoint() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/calculate")
+        .set_json(json!({
+            "expression": {
+                "op": "multiply",
+                "operands": [{ "value": 2, "unit": "kg" }, { "value": 9.8, "unit": "m/s²" }],
+            },
+            "to_unit": "N",
+        }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = web_test::read_body_json(response).await;
+    assert!((body["value"].as_f64().unwrap() - 19.6).abs() < 1e-9);
+    assert_eq!(body["unit"], "N");
+    assert_eq!(body["category"], "force");
+
+    let request = web_test::TestRequest::post().uri("/api/calculate")
+        .set_json(json!({ "operands": [{ "value": 1, "unit": "kg" }, { "value": 1, "unit": "s" }] }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "invalid_calculation");
+}
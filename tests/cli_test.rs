@@ -0,0 +1,99 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn unitconv(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_unitconv"))
+        .args(args)
+        .env_remove("RATES_FILE")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = unitconv(args, "");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_cli_commands() {
+    assert_eq!(stdout(&["convert", "5", "ft", "m"]), "5 ft = 1.524 m\n");
+    assert_eq!(stdout(&["convert", "-40", "°C", "°F", "--category", "temperature"]), "-40 °C = -40 °F\n");
+    assert_eq!(
+        stdout(&["convert", "1", "mi", "km", "--exact", "--precision", "5", "--format", "tsv"]),
+        "1\tmi\t1.609344\tkm\tlength\t25146/15625\t1.6093\n"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(&stdout(&["-f", "json", "convert", "1", "kWh", "J"])).unwrap();
+    assert_eq!(json["to_value"], 3600000.0);
+
+    assert_eq!(stdout(&["compare", "1", "km", "3000", "ft", "-c", "length"]), "1 km is greater than 3000 ft\n");
+    assert_eq!(stdout(&["compare", "1", "km", "1000", "m", "-c", "length", "-f", "tsv"]), "0\tequal\n");
+
+    assert!(stdout(&["list", "categories"]).lines().any(|line| line == "length"));
+    let units: Vec<String> = serde_json::from_str(&stdout(&["list", "units", "length", "-f", "json"])).unwrap();
+    assert!(units.contains(&"ft".to_string()));
+
+    // Errors go to stderr with a failing exit status
+    let output = unitconv(&["convert", "1", "ft", "kg", "--category", "length"], "");
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+
+    assert!(!unitconv(&["list", "units", "nonsense"], "").status.success());
+}
+
+#[test]
+fn test_cli_pipe() {
+    let input = "leg\tmiles\tnote\nout\t1\tflat\nback\tx\thilly\nloop\t2.5\t\n";
+
+    let output = unitconv(&["pipe", "mi", "km", "--column", "2", "--delimiter", "\t", "--header"], input);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "leg\tmiles\tnote\nout\t1.609344\tflat\nback\tx\thilly\nloop\t4.02336\t\n"
+    );
+    // A bad row is reported without losing the others
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("line 3"));
+
+    // Text output keeps the line's own spacing around the converted value
+    let output = unitconv(&["pipe", "ft", "in"], "1 a\n\n  2\t b  \n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "12 a\n\n  24\t b  \n");
+    let output = unitconv(&["pipe", "ft", "in", "-f", "tsv"], "  2   b\n");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "24\tb\n");
+
+    let output = unitconv(&["pipe", "L", "mL", "-f", "json"], "1.5\n0.25\n");
+    let values: Vec<f64> = String::from_utf8(output.stdout).unwrap().lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["to_value"].as_f64().unwrap())
+        .collect();
+    assert_eq!(values, vec![1500.0, 250.0]);
+}
+
+
+#[test]
+fn test_server_arguments() {
+    let server = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_unit-conversion-api")).args(args).output().unwrap();
+
+    let output = server(&["--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("--import-format"));
+
+    let output = server(&["--import-format", "csv"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("csv"));
+
+    // A setting that doesn't parse stops the server rather than being ignored
+    let output = Command::new(env!("CARGO_BIN_EXE_unit-conversion-api"))
+        .env("LISTING_ORDER", "sideways")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("LISTING_ORDER") && stderr.contains("'sideways'"), "{}", stderr);
+}
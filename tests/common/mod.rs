@@ -0,0 +1,26 @@
+// Helpers shared by the integration tests, each of which uses only some of them
+#![allow(dead_code)]
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::services::{ConversionService, ConversionRequest, ConversionResponse};
+
+/// Builds a request to convert `value` between two units, in `category` if given
+pub fn request(value: f64, category: Option<&str>, from: &str, to: &str) -> ConversionRequest {
+    ConversionRequest {
+        value: value.into(),
+        from_category: category.map(str::to_string),
+        from_unit: from.to_string(),
+        to_unit: to.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Converts `value` between two units, in `category` if given
+pub fn convert(value: f64, category: Option<&str>, from: &str, to: &str) -> Result<ConversionResponse, ApiError> {
+    ConversionService::convert(request(value, category, from, to))
+}
+
+/// Converts `value` between two units, in `category` if given, returning just the result
+pub fn convert_value(value: f64, category: Option<&str>, from: &str, to: &str) -> Result<f64, ApiError> {
+    convert(value, category, from, to).map(|response| response.to_value)
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::{registry, CompoundUnit, Dimension};
+use common::convert_value;
+
+fn parse(expression: &str) -> Result<CompoundUnit, ApiError> {
+    let reg = registry().read().unwrap();
+    CompoundUnit::parse(expression, &reg)
+}
+
+#[test]
+fn test_dimension_parsing() {
+    init();
+
+    let force = Dimension::new([1, 1, -2, 0, 0, 0, 0]);
+    assert_eq!(parse("kg·m/s²").unwrap().dimension(), force);
+    assert_eq!(parse("kg*m/s^2").unwrap().dimension(), force);
+    assert_eq!(parse("kg m s^-2").unwrap().dimension(), force);
+    assert_eq!(parse("N").unwrap().dimension(), force);
+
+    // Heat transfer coefficient: M·T⁻³·Θ⁻¹
+    let htc = parse("W/(m²·K)").unwrap();
+    assert_eq!(htc.dimension(), Dimension::new([0, 1, -3, 0, -1, 0, 0]));
+    assert_eq!(htc.dimension().to_string(), "M·T⁻³·Θ⁻¹");
+
+    // ASCII exponents, and registered symbols containing operators
+    assert_eq!(parse("kg/m3").unwrap().dimension(), parse("kg/m³").unwrap().dimension());
+    assert_eq!(Some(parse("km/h").unwrap().dimension()), Dimension::LENGTH.checked_div(Dimension::TIME));
+    assert_eq!(parse("m/s²").unwrap().dimension(), Dimension::new([1, 0, -2, 0, 0, 0, 0]));
+    assert_eq!(parse("fl oz/s").unwrap().dimension(), Dimension::new([3, 0, -1, 0, 0, 0, 0]));
+    assert_eq!(Some(parse("1/s").unwrap().dimension()), Dimension::TIME.checked_powi(-1));
+}
+
+#[test]
+fn test_compound_conversions() {
+    init();
+
+    // Torque
+    let ft_lbf = convert_value(1.0, None, "N·m", "ft·lbf").unwrap();
+    assert!((ft_lbf - 0.737562).abs() < 1e-6);
+
+    // Density
+    let lb_ft3 = convert_value(1000.0, None, "kg/m³", "lb/ft³").unwrap();
+    assert!((lb_ft3 - 62.428).abs() < 0.01);
+
+    // Heat transfer coefficient
+    let btu = convert_value(1.0, None, "W/(m²·K)", "BTU/(h·ft²·°R)").unwrap();
+    assert!((btu - 0.17611).abs() < 1e-4);
+
+    // Derived SI units agree with their definitions
+    let pa = convert_value(1.0, None, "N/m²", "Pa").unwrap();
+    assert!((pa - 1.0).abs() < 1e-12);
+    let j = convert_value(1.0, None, "kg·m²/s²", "J").unwrap();
+    assert!((j - 1.0).abs() < 1e-12);
+    let w = convert_value(1.0, None, "J/s", "W").unwrap();
+    assert!((w - 1.0).abs() < 1e-12);
+
+    // Speed and acceleration
+    let kmh = convert_value(10.0, None, "m/s", "km/h").unwrap();
+    assert!((kmh - 36.0).abs() < 1e-3);
+    let g = convert_value(9.80665, None, "m/s²", "ft/s²").unwrap();
+    assert!((g - 32.174).abs() < 1e-3);
+}
+
+#[test]
+fn test_single_offset_units_without_category() {
+    init();
+
+    let f = convert_value(100.0, None, "°C", "°F").unwrap();
+    assert!((f - 212.0).abs() < 1e-9);
+
+    let k = convert_value(0.0, None, "°C", "K").unwrap();
+    assert!((k - 273.15).abs() < 1e-9);
+
+    // Within a product or quotient, offset units stand for their intervals
+    assert!(!parse("J/°C").unwrap().has_offset());
+    assert!((convert_value(1.0, None, "J/°C", "J/K").unwrap() - 1.0).abs() < 1e-12);
+    let u = convert_value(1.0, None, "W/(m²·K)", "BTU/(h·ft²·°F)").unwrap();
+    assert!((u - 0.176110).abs() < 1e-6, "{}", u);
+    let r = convert_value(1.0, None, "h·ft²·°F/BTU", "m²·K/W").unwrap();
+    assert!((r - 0.176110).abs() < 1e-6, "{}", r);
+}
+
+#[test]
+fn test_compound_errors() {
+    init();
+
+    // Mismatched dimensions
+    let err = convert_value(1.0, None, "N·m", "W").unwrap_err();
+    assert!(matches!(err, ApiError::BadRequest(_)));
+    assert!(err.to_string().contains("dimensions differ"));
+
+    // Unknown units report their position
+    let err = parse("kg/parsec").unwrap_err();
+    assert!(matches!(err, ApiError::UnitNotFound { .. }));
+    assert!(err.to_string().contains("'parsec' at position 3"));
+
+    // Malformed expressions
+    assert!(matches!(parse("(kg·m"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(parse("kg/"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(parse(""), Err(ApiError::BadRequest(_))));
+
+    // Exponents stay within the range a dimension holds, rather than wrapping
+    assert!(matches!(parse("m^128"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(
+        parse("((((m^127)^127)^127)^127)^127"),
+        Err(ApiError::ParseError { position: 10, .. })
+    ));
+    assert!(matches!(parse("m^127*m^127*m^2"), Err(ApiError::ParseError { position: 5, .. })));
+    assert!(matches!(parse("m^100*ft^100"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(parse("(m³)^50"), Err(ApiError::BadRequest(_))));
+    assert_eq!(parse("m^127/m^127").unwrap().dimension(), Dimension::DIMENSIONLESS);
+}
+
+#[test]
+fn test_scale_factor_bounds() {
+    init();
+
+    // Scale factors are exact, so their size is bounded before raising them to a power
+    assert!(matches!(parse("((km^127)^127)^127"), Err(ApiError::ParseError { .. })));
+    assert!(matches!(parse("(1000^127)^127·m"), Err(ApiError::ParseError { position: 10, .. })));
+    assert!((convert_value(1.0, None, "(10 m)^3", "L").unwrap() - 1e6).abs() < 1e-6);
+}
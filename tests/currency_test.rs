@@ -0,0 +1,156 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use actix_web::{test as web_test, web, App};
+use chrono::{DateTime, Utc};
+use unit_conversion_api::config::{AppConfig, ServerConfig};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::rates::{
+    set_rate_provider, FileRateProvider, MemoryRateProvider, RateProvider, RateTable, RateUpdate
+};
+use unit_conversion_api::models::registry;
+use unit_conversion_api::models::units::currency::register_currencies;
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{ConversionService, ConversionRequest, RateService};
+use common::{convert, request};
+
+fn timestamp(text: &str) -> DateTime<Utc> {
+    text.parse().unwrap()
+}
+
+fn install(provider: impl RateProvider + 'static) {
+    set_rate_provider(Arc::new(provider));
+    register_currencies(&mut registry().write().unwrap()).unwrap();
+}
+
+#[test]
+fn test_rate_files() {
+    let csv = FileRateProvider::load("tests/fixtures/rates.csv").unwrap();
+    assert_eq!(csv.base(), "USD");
+    assert_eq!(csv.currencies(), vec!["USD", "EUR", "GBP", "JPY", "SEK"]);
+    assert_eq!(csv.rate("JPY").unwrap().rate, 156.25);
+    assert_eq!(csv.rate("JPY").unwrap().timestamp, timestamp("2024-06-03T15:00:00Z"));
+    assert_eq!(csv.rate("USD").unwrap().rate, 1.0);
+    assert!(csv.rate("CHF").is_none());
+
+    let json = FileRateProvider::load("tests/fixtures/rates.json").unwrap();
+    assert_eq!(json.base(), "EUR");
+    assert_eq!(json.rate("PLN").unwrap().rate, 4.3);
+
+    // File providers are read-only
+    assert!(json.update(RateUpdate { base: None, timestamp: None, rates: HashMap::new() }).is_err());
+
+    assert!(FileRateProvider::load("tests/fixtures/missing.json").is_err());
+    assert!(RateTable::from_csv("base,currency,rate,timestamp\nUSD,EUR,0.92,2024-06-03T16:00:00Z\nEUR,GBP,0.85,2024-06-03T16:00:00Z\n").is_err());
+    assert!(RateTable::from_csv("base,currency,rate,timestamp\nUSD,EUR,-1,2024-06-03T16:00:00Z\n").is_err());
+    assert!(RateTable::from_json(r#"{"base": "US Dollar", "timestamp": "2024-06-03T16:00:00Z", "rates": {}}"#).is_err());
+}
+
+#[test]
+fn test_memory_provider() {
+    let provider = MemoryRateProvider::new("usd").unwrap();
+    assert_eq!(provider.base(), "USD");
+    assert_eq!(provider.currencies(), vec!["USD"]);
+
+    provider.update(RateUpdate {
+        base: Some("USD".to_string()),
+        timestamp: Some(timestamp("2024-06-04T09:00:00Z")),
+        rates: HashMap::from([("eur".to_string(), 0.93), ("CAD".to_string(), 1.37)]),
+    }).unwrap();
+    assert_eq!(provider.currencies(), vec!["USD", "CAD", "EUR"]);
+    assert_eq!(provider.rate("EUR").unwrap().timestamp, timestamp("2024-06-04T09:00:00Z"));
+
+    // An update with any invalid rate changes nothing
+    let invalid = provider.update(RateUpdate {
+        base: None,
+        timestamp: None,
+        rates: HashMap::from([("EUR".to_string(), 0.95), ("GBP".to_string(), 0.0)]),
+    });
+    assert!(matches!(invalid, Err(ApiError::BadRequest(_))));
+    assert_eq!(provider.rate("EUR").unwrap().rate, 0.93);
+
+    assert!(provider.update(RateUpdate {
+        base: Some("EUR".to_string()),
+        timestamp: None,
+        rates: HashMap::new(),
+    }).is_err());
+}
+
+#[actix_rt::test]
+async fn test_currency_conversions() {
+    init();
+
+    install(FileRateProvider::load("tests/fixtures/rates.csv").unwrap());
+
+    let response = convert(100.0, Some("currency"), "USD", "EUR").unwrap();
+    assert!((response.to_value - 92.0).abs() < 1e-9);
+    assert_eq!(response.category, "currency");
+    assert_eq!(response.rate_timestamp, Some(timestamp("2024-06-03T16:00:00Z")));
+
+    // The older of the two rates dates the conversion
+    let response = convert(1000.0, Some("currency"), "JPY", "GBP").unwrap();
+    assert!((response.to_value - 4.992).abs() < 1e-9);
+    assert_eq!(response.rate_timestamp, Some(timestamp("2024-06-03T15:00:00Z")));
+
+    // Currencies resolve by name and plural as well as by code
+    assert!((convert(10.5, Some("currency"), "Swedish kronor", "US dollars").unwrap().to_value - 1.0).abs() < 1e-9);
+    assert!((convert(1.0, Some("currency"), "eur", "euros").unwrap().to_value - 1.0).abs() < 1e-12);
+
+    // Other categories don't carry a timestamp
+    let response = convert(1.0, Some("length"), "m", "cm").unwrap();
+    assert!(response.rate_timestamp.is_none());
+    assert!(!serde_json::to_string(&response).unwrap().contains("rate_timestamp"));
+
+    // Rates can't be exact
+    assert!(ConversionService::convert(ConversionRequest {
+        exact: true,
+        ..request(1.0, Some("currency"), "USD", "EUR")
+    }).is_err());
+
+    // Switching to the in-memory provider, which the admin endpoint updates
+    install(MemoryRateProvider::new("USD").unwrap());
+    assert!(matches!(convert(1.0, Some("currency"), "USD", "EUR"), Err(ApiError::NotFound(_))));
+
+    let config = AppConfig {
+        server: ServerConfig { host: "127.0.0.1".to_string(), port: 8080 },
+        log_level: "info".to_string(),
+        admin_token: Some("secret".to_string()),
+        rates_file: None,
+        listing_order: Default::default(),
+        swagger_ui_dir: None,
+    };
+    let app = web_test::init_service(
+        App::new().app_data(web::Data::new(config)).configure(configure_routes)
+    ).await;
+    let body = serde_json::json!({
+        "timestamp": "2024-06-05T12:00:00Z",
+        "rates": { "EUR": 0.9, "CHF": 0.89 }
+    });
+
+    let request = web_test::TestRequest::put().uri("/api/admin/rates").set_json(&body).to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 401);
+
+    let request = web_test::TestRequest::put().uri("/api/admin/rates")
+        .insert_header(("Authorization", "Bearer wrong"))
+        .set_json(&body)
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 401);
+
+    let request = web_test::TestRequest::put().uri("/api/admin/rates")
+        .insert_header(("Authorization", "Bearer secret"))
+        .set_json(&body)
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 200);
+
+    // New currencies are registered as soon as they have a rate
+    let response = convert(9.0, Some("currency"), "EUR", "CHF").unwrap();
+    assert!((response.to_value - 8.9).abs() < 1e-9);
+    assert_eq!(response.rate_timestamp, Some(timestamp("2024-06-05T12:00:00Z")));
+
+    let rates = RateService::list_rates();
+    assert_eq!(rates.base, "USD");
+    assert_eq!(rates.source, "memory");
+    assert_eq!(rates.rates.len(), 3);
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use actix_web::{test as web_test, web, App};
+use unit_conversion_api::config::{AppConfig, ServerConfig};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{
+    ConversionService, UnitService, UnitDefinition, CategoryDefinition, BaseUnitDefinition
+};
+use common::convert_value;
+
+fn unit(name: &str, symbol: &str, factor: &str, offset: Option<&str>, of: &str) -> UnitDefinition {
+    UnitDefinition {
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        factor: factor.parse().unwrap(),
+        offset: offset.map(|o| o.parse().unwrap()),
+        unit: of.to_string(),
+        category: None,
+    }
+}
+
+#[test]
+fn test_define_units() {
+    init();
+
+    let smoot = UnitService::define_unit(unit("smoot", "smoot", "1.7018", None, "m")).unwrap();
+    assert_eq!(smoot.category, "length");
+    assert_eq!(smoot.factor, "8509/5000");
+    assert_eq!(smoot.offset, "0");
+
+    // The Harvard Bridge is 364.4 smoots long
+    assert!((convert_value(364.4, Some("length"), "smoots", "ft").unwrap() - 2034.5666666666666).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("length"), "smoot", "cm").unwrap() - 170.18).abs() < 1e-9);
+
+    // Custom units take part in unit expressions, by their category's dimension
+    assert!((convert_value(1.0, None, "smoot/s", "m/s").unwrap() - 1.7018).abs() < 1e-12);
+
+    // Units defined relative to units with an offset compose both conversions
+    UnitService::define_unit(unit("gas mark", "GM", "25", Some("250"), "°F")).unwrap();
+    assert!((convert_value(4.0, Some("temperature"), "GM", "°F").unwrap() - 350.0).abs() < 1e-9);
+    assert!((convert_value(4.0, Some("temperature"), "gas mark", "°C").unwrap() - 176.66666666666666).abs() < 1e-9);
+
+    // Units can be defined in terms of custom units
+    UnitService::define_unit(unit("kilosmoot", "ksmoot", "1000", None, "smoot")).unwrap();
+    assert!((convert_value(1.0, Some("length"), "ksmoot", "km").unwrap() - 1.7018).abs() < 1e-12);
+
+    assert!(matches!(
+        UnitService::define_unit(unit("other smoot", "smoot", "2", None, "m")),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        UnitService::define_unit(unit("nothing", "nil", "0", None, "m")),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        UnitService::define_unit(unit("backwards meter", "bm", "-1", None, "m")),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        UnitService::define_unit(unit("numeric", "2x", "2", None, "m")),
+        Err(ApiError::BadRequest(_))
+    ));
+    for symbol in ["fur lg", "smoot²/s", "sm·t", "(sm)"] {
+        assert!(matches!(
+            UnitService::define_unit(unit("odd smoot", symbol, "2", None, "m")),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    // Symbols and names already in use anywhere would make lookups ambiguous
+    for (name, symbol) in [("moment", "m"), ("minim", "M"), ("kilo-smoot", "KM"), ("meter", "mtr")] {
+        assert!(matches!(
+            UnitService::define_unit(unit(name, symbol, "2", None, "s")),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+    assert!(convert_value(1.0, None, "m", "ft").is_ok());
+
+    assert!(matches!(
+        UnitService::define_unit(unit("glorp", "glorp", "2", None, "flurb")),
+        Err(ApiError::UnitNotFound { .. })
+    ));
+
+    // Built-in units stay put
+    assert!(matches!(UnitService::remove_unit("length", "m"), Err(ApiError::BadRequest(_))));
+
+    assert!((convert_value(1.0, None, "ksmoot/h", "m/h").unwrap() - 1701.8).abs() < 1e-9);
+
+    UnitService::remove_unit("length", "ksmoot").unwrap();
+    assert!(matches!(convert_value(1.0, Some("length"), "kilosmoot", "m"), Err(ApiError::UnitNotFound { .. })));
+    assert!(matches!(convert_value(1.0, None, "ksmoot/h", "m/h"), Err(ApiError::UnitNotFound { .. })));
+    assert!(matches!(UnitService::remove_unit("length", "ksmoot"), Err(ApiError::NotFound(_))));
+}
+
+#[test]
+fn test_define_categories() {
+    init();
+
+    let category = UnitService::define_category(CategoryDefinition {
+        name: "packaging".to_string(),
+        description: Some("Shipping containers".to_string()),
+        base_unit: BaseUnitDefinition { name: "case".to_string(), symbol: "cse".to_string() },
+    }).unwrap();
+    assert_eq!(category.base_unit, "cse");
+
+    UnitService::define_unit(unit("pallet", "plt", "48", None, "case")).unwrap();
+    UnitService::define_unit(unit("truckload", "trk", "26", None, "plt")).unwrap();
+    assert_eq!(convert_value(2.0, Some("packaging"), "truckloads", "cases").unwrap(), 2496.0);
+    assert_eq!(ConversionService::list_units("packaging").unwrap().len(), 3);
+
+    for name in ["packaging", "Has Spaces", "9lives"] {
+        assert!(matches!(
+            UnitService::define_category(CategoryDefinition {
+                name: name.to_string(),
+                description: None,
+                base_unit: BaseUnitDefinition { name: "thing".to_string(), symbol: "thg".to_string() },
+            }),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    assert!(matches!(UnitService::remove_category("length"), Err(ApiError::BadRequest(_))));
+    assert_eq!(UnitService::remove_category("packaging").unwrap(), 3);
+    assert!(matches!(ConversionService::list_units("packaging"), Err(ApiError::NotFound(_))));
+    assert!(matches!(convert_value(1.0, Some("packaging"), "plt", "cse"), Err(ApiError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn test_unit_routes() {
+    init();
+
+    let config = AppConfig {
+        server: ServerConfig { host: "127.0.0.1".to_string(), port: 8080 },
+        log_level: "info".to_string(),
+        admin_token: Some("secret".to_string()),
+        rates_file: None,
+        listing_order: Default::default(),
+        swagger_ui_dir: None,
+    };
+    let app = web_test::init_service(
+        App::new().app_data(web::Data::new(config)).configure(configure_routes)
+    ).await;
+
+    // Defining units needs the admin token too
+    let barrel = serde_json::json!({ "name": "barrel of crude", "symbol": "bbl", "factor": 42, "unit": "gal" });
+    let request = web_test::TestRequest::post().uri("/api/units").set_json(&barrel).to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 401);
+    let request = web_test::TestRequest::post().uri("/api/categories")
+        .set_json(serde_json::json!({ "name": "crates", "base_unit": { "name": "crate", "symbol": "crt" } }))
+        .insert_header(("Authorization", "Bearer wrong"))
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 401);
+
+    let request = web_test::TestRequest::post().uri("/api/units")
+        .set_json(&barrel)
+        .insert_header(("Authorization", "Bearer secret"))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 201);
+    let body: serde_json::Value = web_test::read_body_json(response).await;
+    assert_eq!(body["category"], "volume");
+    assert_eq!(body["factor"], "9936705933/62500000000");
+
+    // Removing units needs the admin token
+    let request = web_test::TestRequest::delete().uri("/api/units/volume/bbl").to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 401);
+    for token in ["secre", "secrets", "Secret", ""] {
+        let request = web_test::TestRequest::delete().uri("/api/units/volume/bbl")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        assert_eq!(web_test::call_service(&app, request).await.status(), 401, "{}", token);
+    }
+
+    let request = web_test::TestRequest::delete().uri("/api/units/volume/bbl")
+        .insert_header(("Authorization", "Bearer secret"))
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 204);
+
+    let request = web_test::TestRequest::delete().uri("/api/units/volume/gal")
+        .insert_header(("Authorization", "Bearer secret"))
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 400);
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::units::data::{Bit, Nibble, Byte};
+use unit_conversion_api::models::units::data_rate::{BitPerSecond, BytePerSecond};
+use unit_conversion_api::models::unit::Unit;
+use unit_conversion_api::services::ConversionService;
+use common::convert_value;
+
+#[test]
+fn test_data_conversions() {
+    // Initialize the registry
+    init();
+
+    // Test conversions to base unit (bit)
+    assert_eq!(Bit::new().to_base(1.0), 1.0);
+    assert_eq!(Nibble::new().to_base(1.0), 4.0);
+    assert_eq!(Byte::new().to_base(1.0), 8.0);
+    assert_eq!(Byte::new().from_base(64.0), 8.0);
+
+    // Decimal and binary multiples of the byte
+    assert_eq!(convert_value(1.0, Some("data"), "kB", "B").unwrap(), 1000.0);
+    assert_eq!(convert_value(1.0, Some("data"), "KiB", "B").unwrap(), 1024.0);
+    assert_eq!(convert_value(1.0, Some("data"), "MiB", "KiB").unwrap(), 1024.0);
+    assert_eq!(convert_value(1.0, Some("data"), "GB", "MB").unwrap(), 1000.0);
+    assert_eq!(convert_value(1.0, Some("data"), "TiB", "GiB").unwrap(), 1024.0);
+    assert_eq!(convert_value(1.0, Some("data"), "PiB", "B").unwrap(), 1125899906842624.0);
+    assert_eq!(convert_value(1.0, Some("data"), "PB", "TB").unwrap(), 1000.0);
+    assert_eq!(convert_value(1.0, Some("data"), "B", "nibble").unwrap(), 2.0);
+    assert_eq!(convert_value(1.0, Some("data"), "Mbit", "kB").unwrap(), 125.0);
+
+    // A "500 GB" drive as an operating system reports it
+    assert!((convert_value(500.0, Some("data"), "GB", "GiB").unwrap() - 465.66128730773926).abs() < 1e-9);
+
+    // Names and plurals resolve like symbols
+    assert_eq!(convert_value(2.0, Some("data"), "gibibytes", "megabytes").unwrap(), 2147.483648);
+    assert_eq!(convert_value(1.0, Some("data"), "octet", "bits").unwrap(), 8.0);
+}
+
+#[test]
+fn test_data_rate_conversions() {
+    init();
+
+    assert_eq!(BitPerSecond::new().to_base(1.0), 1.0);
+    assert_eq!(BytePerSecond::new().to_base(1.0), 8.0);
+
+    assert_eq!(convert_value(1.0, Some("data_rate"), "Gbit/s", "MB/s").unwrap(), 125.0);
+    assert_eq!(convert_value(100.0, Some("data_rate"), "Mbps", "MBps").unwrap(), 12.5);
+    assert_eq!(convert_value(1.0, Some("data_rate"), "MiB/s", "kbit/s").unwrap(), 8388.608);
+    assert_eq!(convert_value(10.0, Some("data_rate"), "gigabits per second", "bps").unwrap(), 1e10);
+}
+
+#[test]
+fn test_data_queries() {
+    init();
+
+    // Data sizes and rates take part in free-text queries and unit expressions
+    let response = ConversionService::convert_query("1 TB to TiB").unwrap();
+    assert!((response.to_value - 0.9094947017729282).abs() < 1e-12);
+    assert_eq!(response.category, "data");
+
+    let response = ConversionService::convert_query("1 GB/min in Mbit/s").unwrap();
+    assert!((response.to_value - 133.33333333333334).abs() < 1e-9);
+    assert_eq!(response.category, "data_rate");
+
+    assert!(ConversionService::convert_query("1 GB to m").is_err());
+}
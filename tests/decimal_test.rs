@@ -0,0 +1,72 @@
+use num_rational::BigRational;
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::decimal::round_rational;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::services::{ConversionService, ConversionRequest, ConversionResponse};
+
+fn convert(json: &str) -> Result<ConversionResponse, ApiError> {
+    let request: ConversionRequest = serde_json::from_str(json).unwrap();
+    ConversionService::convert(request)
+}
+
+#[test]
+fn test_decimal_string_values() {
+    init();
+
+    let response = convert(r#"{"value": "0.1", "from_category": "length", "from_unit": "ft", "to_unit": "in"}"#).unwrap();
+    assert_eq!(response.from_value, 0.1);
+    assert_eq!(response.to_value, 1.2);
+    assert!(response.decimal_value.is_none());
+
+    // Numbers and strings are interchangeable
+    let response = convert(r#"{"value": 0.3, "from_category": "energy", "from_unit": "kWh", "to_unit": "J", "precision": 10}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("1080000"));
+
+    assert!(serde_json::from_str::<ConversionRequest>(
+        r#"{"value": "abc", "from_unit": "m", "to_unit": "ft"}"#
+    ).is_err());
+}
+
+#[test]
+fn test_precision_mode() {
+    init();
+
+    // Terminating results are exact, with no binary noise
+    let response = convert(r#"{"value": "0.1", "from_category": "energy", "from_unit": "kWh", "to_unit": "MJ", "precision": 20}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("0.36"));
+
+    let response = convert(r#"{"value": "98.6", "from_category": "temperature", "from_unit": "°F", "to_unit": "°C", "precision": 10}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("37"));
+
+    // Digits beyond f64 precision are kept
+    let response = convert(r#"{"value": "1234567.891234567891234", "from_category": "length", "from_unit": "m", "to_unit": "km", "precision": 30}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("1234.567891234567891234"));
+
+    // Repeating results are rounded to the requested significant digits
+    let response = convert(r#"{"value": "1", "from_category": "length", "from_unit": "m", "to_unit": "ft", "precision": 10}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("3.280839895"));
+
+    // Compound expressions work the same way
+    let response = convert(r#"{"value": "2.5", "from_unit": "therm/h", "to_unit": "kW", "precision": 12}"#).unwrap();
+    assert_eq!(response.decimal_value.as_deref(), Some("73.2677675398"));
+
+    assert!(matches!(
+        convert(r#"{"value": 1, "from_category": "length", "from_unit": "m", "to_unit": "ft", "precision": 0}"#),
+        Err(ApiError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_round_half_even() {
+    let round = |numer: i64, denom: i64, precision: u64| {
+        round_rational(&BigRational::new(numer.into(), denom.into()), precision)
+            .normalized()
+            .to_plain_string()
+    };
+
+    assert_eq!(round(1, 8, 2), "0.12");
+    assert_eq!(round(3, 8, 2), "0.38");
+    assert_eq!(round(-2, 3, 3), "-0.667");
+    assert_eq!(round(9995, 1, 3), "10000");
+    assert_eq!(round(1, 3000, 1), "0.0003");
+}
@@ -0,0 +1,143 @@
+mod common;
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::{registry, DefinedUnit, Definitions, DefinitionSet, UnitRegistry};
+use common::convert_value;
+
+fn register(toml: &str) -> Result<usize, ApiError> {
+    let mut set = DefinitionSet::new();
+    set.add("inline.toml", Definitions::from_toml(toml)?);
+    set.register(&mut registry().write().unwrap())
+}
+
+fn message(err: ApiError) -> String {
+    match err {
+        ApiError::BadRequest(message) => message,
+        err => panic!("expected a bad request, got {:?}", err),
+    }
+}
+
+#[test]
+fn test_load_definition_files() {
+    init();
+
+    let set = DefinitionSet::load_dir("tests/fixtures/units").unwrap();
+    assert_eq!(set.register(&mut registry().write().unwrap()).unwrap(), 6);
+
+    // Units resolve across files, whatever order they're defined in
+    assert_eq!(convert_value(1.0, Some("shipping"), "truckload", "cases").unwrap(), 1920.0);
+    assert_eq!(convert_value(3.0, Some("shipping"), "skids", "boxes").unwrap(), 144.0);
+    assert!((convert_value(1.0, Some("length"), "furlongs", "m").unwrap() - 201.168).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("length"), "NM", "km").unwrap() - 1.852).abs() < 1e-12);
+
+    let registry = registry().read().unwrap();
+    let description = |category, symbol| registry.get_unit(category, symbol)
+        .and_then(|unit| unit.as_any().downcast_ref::<DefinedUnit>())
+        .and_then(|unit| unit.description());
+    assert_eq!(description("shipping", "plt"), Some("A standard pallet of 48 cases"));
+    assert_eq!(description("length", "nmi"), Some("International nautical mile"));
+    assert_eq!(registry.get_category("shipping").unwrap().description(), "Shipping containers");
+
+    // A missing directory has nothing to load
+    let empty = DefinitionSet::load_dir("tests/fixtures/missing").unwrap();
+    assert_eq!(empty.register(&mut UnitRegistry::new()).unwrap(), 0);
+}
+
+#[test]
+fn test_invalid_definitions() {
+    init();
+
+    // Units that are already registered are only redefined on purpose
+    let err = message(register(r#"
+        [[units]]
+        name = "foot"
+        symbol = "ft"
+        factor = 12
+        unit = "in"
+    "#).unwrap_err());
+    assert!(err.contains("inline.toml") && err.contains("replace = true"), "{}", err);
+
+    assert!(message(register(r#"
+        [[units]]
+        name = "league"
+        symbol = "lea"
+        factor = 3
+        unit = "mi"
+        replace = true
+    "#).unwrap_err()).contains("has no unit 'lea'"));
+
+    assert!(message(register(r#"
+        [[units]]
+        name = "rod"
+        symbol = "rd"
+        factor = 16.5
+        unit = "ft"
+
+        [[units]]
+        name = "pole"
+        symbol = "rd"
+        factor = 5.5
+        unit = "yd"
+    "#).unwrap_err()).contains("also defined in 'inline.toml'"));
+
+    // Symbols and aliases that stand for units of other categories are only shared on purpose
+    let err = message(register(r#"
+        [[categories]]
+        name = "markers"
+        description = "Mile markers"
+        base_unit = { name = "marker", symbol = "m" }
+    "#).unwrap_err());
+    assert!(err.contains("'m' in category 'length'") && err.contains("allow_ambiguous"), "{}", err);
+
+    let err = message(register(r#"
+        [[categories]]
+        name = "crates"
+        description = "Crates"
+        base_unit = { name = "crate", symbol = "crt", aliases = ["SEC"] }
+    "#).unwrap_err());
+    assert!(err.contains("'s' in category 'time'"), "{}", err);
+
+    assert_eq!(register(r#"
+        [[categories]]
+        name = "crates"
+        description = "Crates"
+        base_unit = { name = "crate", symbol = "crt", aliases = ["sec"], allow_ambiguous = true }
+    "#).unwrap(), 1);
+
+    assert!(message(register(r#"
+        [[units]]
+        name = "glorp"
+        symbol = "glp"
+        factor = 2
+        unit = "flurb"
+    "#).unwrap_err()).contains("Unit 'glp' refers to unknown unit 'flurb'"));
+
+    let err = message(register(r#"
+        [[units]]
+        name = "alpha"
+        symbol = "al"
+        factor = 2
+        unit = "ga"
+
+        [[units]]
+        name = "beta"
+        symbol = "be"
+        factor = 3
+        unit = "al"
+
+        [[units]]
+        name = "gamma"
+        symbol = "ga"
+        factor = 4
+        unit = "be"
+    "#).unwrap_err());
+    assert!(err.contains("Cyclic definition: al -> ga -> be -> al"), "{}", err);
+
+    assert!(Definitions::from_toml("[[units]]\nname = \"chain\"\nsymbol = \"ch\"\nfactor = 22\nunit = \"yd\"\nfactr = 1\n").is_err());
+    assert!(Definitions::load("tests/fixtures/rates.csv").is_err());
+
+    // Nothing from a failed set is registered
+    assert!(matches!(convert_value(1.0, Some("length"), "rd", "m"), Err(ApiError::UnitNotFound { .. })));
+    assert!(matches!(convert_value(1.0, Some("length"), "al", "m"), Err(ApiError::UnitNotFound { .. })));
+}
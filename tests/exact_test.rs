@@ -0,0 +1,67 @@
+mod common;
+
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::units::{Foot, Inch, Meter, Celsius, Fahrenheit, USGallon, Liter};
+use unit_conversion_api::models::Measurement;
+use unit_conversion_api::services::{ConversionService, ConversionRequest, ConversionResponse};
+
+fn convert(value: f64, category: Option<&str>, from: &str, to: &str) -> ConversionResponse {
+    ConversionService::convert(ConversionRequest { exact: true, ..common::request(value, category, from, to) }).unwrap()
+}
+
+#[test]
+fn test_round_trips_are_bit_identical() {
+    let values = [1.0, 0.1, 3.7, 12.345, 1e-9, 123_456.789, 5280.0, 0.3048];
+
+    for value in values {
+        let feet = Measurement::new(value, Foot::new());
+        let inches = feet.convert_to(Inch::new()).unwrap();
+        let back = inches.convert_to(Foot::new()).unwrap();
+        assert_eq!(back.value().to_bits(), value.to_bits(), "ft → in → ft drifted for {}", value);
+
+        let gallons = Measurement::new(value, USGallon::new());
+        let liters = gallons.convert_to(Liter::new()).unwrap();
+        let back = liters.convert_to(USGallon::new()).unwrap();
+        assert_eq!(back.value().to_bits(), value.to_bits(), "gal → L → gal drifted for {}", value);
+    }
+
+    let fahrenheit = Measurement::new(98.6, Fahrenheit::new());
+    let celsius = fahrenheit.convert_to(Celsius::new()).unwrap();
+    assert_eq!(celsius.value(), 37.0);
+}
+
+#[test]
+fn test_exact_equality() {
+    // 0.1 ft is exactly 1.2 in, and 1 in is exactly 0.0254 m
+    assert_eq!(Measurement::new(0.1, Foot::new()), Measurement::new(1.2, Inch::new()));
+    assert_eq!(Measurement::new(1.0, Inch::new()), Measurement::new(0.0254, Meter::new()));
+
+    // -40 is the same temperature in both scales
+    assert_eq!(Measurement::new(-40.0, Fahrenheit::new()), Measurement::new(-40.0, Celsius::new()));
+}
+
+#[test]
+fn test_exact_fraction_in_response() {
+    init();
+
+    let response = convert(1.0, Some("length"), "m", "ft");
+    assert_eq!(response.exact_value.as_deref(), Some("1250/381"));
+    assert!((response.to_value - 3.280839895).abs() < 1e-9);
+
+    let response = convert(212.0, Some("temperature"), "°F", "°C");
+    assert_eq!(response.exact_value.as_deref(), Some("100"));
+
+    let response = convert(1.0, Some("speed"), "knot", "km/h");
+    assert_eq!(response.exact_value.as_deref(), Some("463/250"));
+
+    // Compound expressions compose exactly too
+    let response = convert(1.0, None, "lbf/in²", "psi");
+    assert_eq!(response.exact_value.as_deref(), Some("1"));
+    let response = convert(1.0, None, "ft·lbf", "J");
+    assert_eq!(response.exact_value.as_deref(), Some("3389544870828501/2500000000000000"));
+
+    // Without the flag the fraction is left out
+    let response = common::convert(1.0, Some("length"), "m", "ft").unwrap();
+    assert!(response.exact_value.is_none());
+    assert!(!serde_json::to_string(&response).unwrap().contains("exact_value"));
+}
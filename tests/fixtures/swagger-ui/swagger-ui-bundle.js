@@ -0,0 +1 @@
+// Stand-in for Swagger UI's bundle
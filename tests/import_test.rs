@@ -0,0 +1,88 @@
+mod common;
+
+use unit_conversion_api::models::import::{import_file, ImportFormat, ImportReport};
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::registry;
+use common::convert_value;
+
+fn import(path: &str, format: Option<ImportFormat>) -> ImportReport {
+    import_file(&mut registry().write().unwrap(), path, format).unwrap()
+}
+
+fn warning<'a>(report: &'a ImportReport, name: &str) -> &'a str {
+    report.warnings.iter()
+        .find(|warning| warning.name.starts_with(name))
+        .map(|warning| warning.message.as_str())
+        .unwrap_or_else(|| panic!("no warning for '{}' in {:?}", name, report.warnings))
+}
+
+#[test]
+fn test_import_gnu_units() {
+    init();
+
+    let text = std::fs::read_to_string("tests/fixtures/import/sample.units").unwrap();
+    assert_eq!(ImportFormat::detect(&text), ImportFormat::Gnu);
+
+    let report = import("tests/fixtures/import/sample.units", None);
+    assert_eq!(report.units, 7);
+    assert_eq!(report.aliases, 1);
+    // m, kg, s, K, gram and poundal are already registered
+    assert_eq!(report.skipped, 6, "{:?}", report);
+
+    assert!((convert_value(1.0, Some("length"), "furlong", "m").unwrap() - 201.168).abs() < 1e-9);
+    assert!((convert_value(2.0, Some("length"), "fur", "ft").unwrap() - 1320.0).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("length"), "halfmile", "m").unwrap() - 804.672).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("length"), "sprint", "km").unwrap() - 402.336).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("time"), "fortnight", "d").unwrap() - 14.0).abs() < 1e-12);
+    assert!((convert_value(1.0, Some("volume"), "firkin", "gal").unwrap() - 9.0).abs() < 1e-12);
+    assert!((convert_value(1.0, Some("mass"), "slug", "kg").unwrap() - 14.593902937206364).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("speed"), "fpf", "m/s").unwrap() - 201.168 / 1_209_600.0).abs() < 1e-15);
+
+    // What can't be expressed is reported rather than aborting the import
+    assert_eq!(report.warnings.len(), 11, "{:?}", report.warnings);
+    assert!(warning(&report, "!include").contains("aren't followed"));
+    assert!(warning(&report, "tempF").contains("nonlinear"));
+    assert!(warning(&report, "US$").contains("no counterpart"));
+    assert!(warning(&report, "euro").contains("depends on 'US$'"));
+    assert!(warning(&report, "dozen").contains("plain number"));
+    assert!(warning(&report, "radian").contains("plain number"));
+    assert!(warning(&report, "cubit").contains("cyclic definition: cubit -> span -> cubit"));
+    assert!(warning(&report, "span").contains("cyclic"));
+    assert!(warning(&report, "galaxy").contains("unknown unit 'kiloparsec'"));
+    assert!(warning(&report, "big").contains("too large"));
+    assert!(warning(&report, "huge").contains("too large"));
+}
+
+#[test]
+fn test_import_pint() {
+    init();
+
+    let report = import("tests/fixtures/import/sample_pint.txt", Some(ImportFormat::Pint));
+    assert_eq!(report.units, 8, "{:?}", report);
+
+    assert!((convert_value(1.0, Some("length"), "thou", "in").unwrap() - 0.001).abs() < 1e-12);
+    assert!((convert_value(1.0, Some("length"), "hands", "in").unwrap() - 4.0).abs() < 1e-12);
+    assert!((convert_value(1.0, Some("length"), "light_year", "m").unwrap() - 9460730472580800.0).abs() < 1.0);
+    assert!((convert_value(1.0, Some("length"), "ly", "au").unwrap() - 63241.07708426628).abs() < 1e-6);
+    assert!((convert_value(1.0, Some("area"), "barn", "m²").unwrap() - 1e-28).abs() < 1e-40);
+    assert!((convert_value(2.0, Some("volume"), "stere", "L").unwrap() - 2000.0).abs() < 1e-9);
+    assert!((convert_value(1.0, Some("length"), "metro", "cm").unwrap() - 100.0).abs() < 1e-12);
+
+    // Units with an offset keep it
+    assert!((convert_value(0.0, Some("temperature"), "°De", "°C").unwrap() - 100.0).abs() < 1e-9);
+    assert!((convert_value(150.0, Some("temperature"), "delisle", "K").unwrap() - 273.15).abs() < 1e-9);
+
+    // and temperatures without one are differences between temperatures
+    assert!((convert_value(33.0, None, "Δ°N", "Δ°C").unwrap() - 100.0).abs() < 1e-9);
+    assert!((convert_value(33.0, Some("temperature_interval"), "delta degree Newton", "ΔK").unwrap() - 100.0).abs() < 1e-9);
+    assert!(convert_value(1.0, None, "Δ°N", "°C").is_err());
+
+    assert!(warning(&report, "dollar").contains("no counterpart"));
+    assert!(warning(&report, "pi").contains("plain number"));
+    assert!(warning(&report, "circle").contains("unknown unit 'radian'"));
+    assert!(warning(&report, "decibel").contains("logbase"));
+    assert!(warning(&report, "@context").contains("contexts"));
+
+    assert!(import_file(&mut registry().write().unwrap(), "tests/fixtures/import/missing.txt", None).is_err());
+    assert!("fortran".parse::<ImportFormat>().is_err());
+}
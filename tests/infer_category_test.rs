@@ -0,0 +1,99 @@
+mod common;
+
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::{registry, DefinedUnit, LinearConversion};
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::ConversionService;
+use common::convert;
+
+// Units defined over HTTP can't reuse a symbol, so the clashing ones go
+// straight into the registry
+fn define_bolt(category: &'static str, factor: &str) {
+    let mut registry = registry().write().unwrap();
+    let base = registry.get_category(category).and_then(|c| c.base_unit()).unwrap();
+    let dimension = registry.resolve_unit(base, Some(category)).unwrap().dimension();
+    let bolt = DefinedUnit::new("bolt", "bolt", category, dimension, LinearConversion::parse(factor, "0"));
+    registry.register_defined_unit(bolt).unwrap();
+}
+
+#[test]
+fn test_infer_category() {
+    init();
+
+    let response = convert(1.0, None, "ft", "m").unwrap();
+    assert_eq!(response.category, "length");
+    assert!((response.to_value - 0.3048).abs() < 1e-12);
+    assert_eq!(convert(1.0, None, "GB", "MB").unwrap().category, "data");
+
+    // A symbol that is ambiguous on its own is settled by the other unit
+    let response = convert(1.0, None, "KN", "m/s").unwrap();
+    assert_eq!((response.from_unit.as_str(), response.category.as_str()), ("kn", "speed"));
+    let response = convert(1.0, None, "KN", "lbf").unwrap();
+    assert_eq!((response.from_unit.as_str(), response.category.as_str()), ("kN", "force"));
+
+    // Anything else is still converted as a unit expression
+    assert!((convert(1.0, None, "kg/m³", "g/L").unwrap().to_value - 1.0).abs() < 1e-12);
+    assert!(matches!(convert(1.0, None, "kg", "m"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(convert(1.0, None, "fot", "m"), Err(ApiError::UnitNotFound { .. })));
+}
+
+#[test]
+fn test_ambiguous_category() {
+    init();
+
+    // A bolt is both 40 yards of cloth and a made-up weight
+    define_bolt("length", "36.576");
+    define_bolt("mass", "25");
+
+    match convert(1.0, None, "bolt", "bolt") {
+        Err(ApiError::Ambiguous { message, candidates }) => {
+            assert_eq!(candidates, ["length", "mass"]);
+            assert_eq!(message, "Unit 'bolt' is in categories 'length' and 'mass'; give a category to choose");
+        },
+        other => panic!("expected an ambiguous category, got {:?}", other),
+    }
+
+    assert!((convert(1.0, None, "bolt", "m").unwrap().to_value - 36.576).abs() < 1e-9);
+    assert!((convert(1.0, None, "bolt", "kg").unwrap().to_value - 25.0).abs() < 1e-9);
+    assert_eq!(ConversionService::compare(1.0, "bolt", 30.0, "m", None).unwrap(), 1);
+    assert_eq!(ConversionService::compare(1.0, "bolt", 30.0, "kg", None).unwrap(), -1);
+    assert!(matches!(
+        ConversionService::compare(1.0, "bolt", 1.0, "bolt", None),
+        Err(ApiError::Ambiguous { .. })
+    ));
+}
+
+#[actix_rt::test]
+async fn test_inferred_category_in_responses() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1, "unit1": "mi", "value2": 1, "unit2": "km" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["relation"], "greater_than");
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1, "unit1": "kg", "value2": 1, "unit2": "m" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "invalid_comparison");
+
+    let request = web_test::TestRequest::post().uri("/api/convert/batch")
+        .set_json(json!([
+            { "value": 2, "from_unit": "KN", "to_unit": "km/h" },
+            { "value": 1, "from_unit": "KN", "to_unit": "KN" },
+        ]))
+        .to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert_eq!(body[0]["result"]["category"], "speed");
+    assert_eq!(body[1]["error"]["error"], "ambiguous_unit");
+}
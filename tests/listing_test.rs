@@ -0,0 +1,135 @@
+use actix_web::{test as web_test, App};
+use serde_json::Value;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::{ListingOrder, UnitSystem};
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::ConversionService;
+
+async fn fetch(uri: &str) -> (u16, Value) {
+    let (status, _, body) = fetch_page(uri).await;
+    (status, body)
+}
+
+async fn fetch_page(uri: &str) -> (u16, Option<usize>, Value) {
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+    let request = web_test::TestRequest::get().uri(uri).to_request();
+    let response = web_test::call_service(&app, request).await;
+    let status = response.status().as_u16();
+    let total = response.headers().get("x-total-count")
+        .map(|value| value.to_str().unwrap().parse().unwrap());
+    (status, total, web_test::read_body_json(response).await)
+}
+
+fn symbols(units: &Value) -> Vec<&str> {
+    units.as_array().unwrap().iter().map(|u| u["symbol"].as_str().unwrap()).collect()
+}
+
+#[actix_rt::test]
+async fn test_list_categories() {
+    init();
+    let (status, categories) = fetch("/api/categories").await;
+    assert_eq!(status, 200);
+
+    // Built-in categories come first, in the order they're registered
+    let categories = categories.as_array().unwrap();
+    let names: Vec<&str> = categories.iter().map(|c| c["name"].as_str().unwrap()).collect();
+    assert_eq!(names[..3], ["length", "mass", "volume"]);
+
+    let length = categories.iter().find(|c| c["name"] == "length").unwrap();
+    assert_eq!(length["description"], "Units of length measurement");
+    assert_eq!(length["base_unit"], "m");
+    assert_eq!(length["dimension"], "L");
+    assert!(length["units"].as_u64().unwrap() > 10);
+
+    let currency = categories.iter().find(|c| c["name"] == "currency").unwrap();
+    assert!(currency["base_unit"].is_null());
+    assert!(currency["dimension"].is_null());
+}
+
+#[actix_rt::test]
+async fn test_list_units() {
+    init();
+    let (status, units) = fetch("/api/categories/length/units").await;
+    assert_eq!(status, 200);
+
+    let units = units.as_array().unwrap();
+    let foot = units.iter().find(|u| u["symbol"] == "ft").unwrap();
+    assert_eq!(foot["name"], "foot");
+    assert_eq!(foot["plural"], "feet");
+    assert_eq!(foot["category"], "length");
+    assert_eq!(foot["is_base"], false);
+    assert_eq!(foot["factor"], "381/1250");
+    assert_eq!(foot["offset"], "0");
+    assert_eq!(foot["systems"], serde_json::json!(["imperial", "us_customary"]));
+    assert!(foot["aliases"].as_array().unwrap().contains(&Value::from("feet")));
+    assert!(foot["description"].is_null());
+
+    let meter = units.iter().find(|u| u["symbol"] == "m").unwrap();
+    assert_eq!(meter["is_base"], true);
+    assert_eq!(meter["systems"], serde_json::json!(["si"]));
+
+    // Prefixed units belong to the systems of the unit they were generated from
+    let gigameter = units.iter().find(|u| u["symbol"] == "Gm").unwrap();
+    assert_eq!(gigameter["systems"], serde_json::json!(["si"]));
+
+    let (status, error) = fetch("/api/categories/nonsense/units").await;
+    assert_eq!(status, 404);
+    assert_eq!(error["error"], "category_not_found");
+}
+
+#[test]
+fn test_describe_units_with_offsets() {
+    init();
+    let units = ConversionService::describe_units("temperature", ListingOrder::Name).unwrap();
+    let fahrenheit = units.iter().find(|u| u.symbol == "°F").unwrap();
+    assert_eq!(fahrenheit.factor.as_deref(), Some("5/9"));
+    assert_eq!(fahrenheit.offset.as_deref(), Some("45967/180"));
+    assert_eq!(fahrenheit.systems, [UnitSystem::Imperial, UnitSystem::UsCustomary]);
+
+    let currencies = ConversionService::describe_units("currency", ListingOrder::Registration).unwrap();
+    assert!(currencies.iter().all(|u| u.factor.is_none() && !u.is_base));
+}
+
+#[actix_rt::test]
+async fn test_listing_order_and_pages() {
+    init();
+
+    // Registration order is the order the units are defined in, with prefixed units after
+    let (_, total, units) = fetch_page("/api/categories/length/units").await;
+    let registered = symbols(&units);
+    assert_eq!(registered[..3], ["m", "km", "cm"]);
+    assert_eq!(total, Some(registered.len()));
+    let (_, _, again) = fetch_page("/api/categories/length/units?sort=registration").await;
+    assert_eq!(symbols(&again), registered);
+
+    let (_, _, units) = fetch_page("/api/categories/length/units?sort=name").await;
+    let names = symbols(&units);
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted);
+
+    // Ordered by factor, smallest first, with exact ties kept in registration order
+    let (_, _, units) = fetch_page("/api/categories/power/units?sort=factor").await;
+    let by_factor = symbols(&units);
+    let position = |symbol: &str| by_factor.iter().position(|&s| s == symbol).unwrap();
+    assert!(position("mW") < position("W") && position("W") < position("hp") && position("hp") < position("kW"));
+    assert_eq!(position("J/s"), position("W") + 1);
+
+    let (status, total, page) = fetch_page("/api/categories/length/units?offset=1&limit=2").await;
+    assert_eq!(status, 200);
+    assert_eq!(total, Some(registered.len()));
+    assert_eq!(symbols(&page), registered[1..3]);
+    let (_, _, page) = fetch_page("/api/categories/length/units?offset=1000").await;
+    assert!(page.as_array().unwrap().is_empty());
+
+    let (_, total, page) = fetch_page("/api/categories?sort=name&limit=2").await;
+    assert_eq!(page.as_array().unwrap().len(), 2);
+    assert_eq!(page[0]["name"], "area");
+    assert!(total.unwrap() >= 14);
+
+    let (status, error) = fetch("/api/categories?sort=factor").await;
+    assert_eq!(status, 400);
+    assert_eq!(error["error"], "invalid_listing");
+    let (status, _) = fetch("/api/categories/length/units?sort=size").await;
+    assert_eq!(status, 400);
+}
@@ -0,0 +1,128 @@
+use actix_web::{test as web_test, web, App};
+use serde_json::Value;
+use unit_conversion_api::config::{AppConfig, ServerConfig};
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{UnitService, UnitDefinition};
+
+async fn fetch_document() -> Value {
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+    let request = web_test::TestRequest::get().uri("/api/openapi.json").to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert!(response.status().is_success());
+    web_test::read_body_json(response).await
+}
+
+fn names(values: &Value) -> Vec<&str> {
+    values.as_array().unwrap().iter().map(|value| value.as_str().unwrap()).collect()
+}
+
+#[actix_rt::test]
+async fn test_openapi_document() {
+    init();
+    let document = fetch_document().await;
+
+    assert!(document["openapi"].as_str().unwrap().starts_with("3."));
+    for path in [
+        "/api/categories", "/api/categories/{category}/units", "/api/convert",
+        "/api/convert/batch", "/api/compare", "/api/units", "/api/rates", "/api/admin/rates",
+    ] {
+        assert!(document["paths"][path].is_object(), "missing {}", path);
+    }
+    assert!(document["paths"]["/api/convert"]["get"].is_object());
+    assert!(document["paths"]["/api/convert"]["post"].is_object());
+
+    let schemas = &document["components"]["schemas"];
+    for schema in ["ConversionRequest", "ConversionResponse", "CompareRequest", "CompareResponse", "ErrorResponse"] {
+        assert!(schemas[schema].is_object(), "missing {}", schema);
+    }
+    let request = &schemas["ConversionRequest"];
+    let required = names(&request["required"]);
+    assert!(required.contains(&"value") && required.contains(&"from_unit") && !required.contains(&"from_category"));
+    assert_eq!(request["properties"]["value"]["$ref"], "#/components/schemas/DecimalValue");
+    assert_eq!(schemas["DecimalValue"]["oneOf"].as_array().unwrap().len(), 2);
+
+    // Admin endpoints take the bearer token
+    assert_eq!(document["components"]["securitySchemes"]["admin_token"]["scheme"], "bearer");
+    assert!(document["paths"]["/api/admin/rates"]["put"]["security"].is_array());
+    assert!(document["paths"]["/api/units"]["post"]["security"].is_array());
+    assert!(document["paths"]["/api/convert"]["post"]["security"].is_null());
+}
+
+#[actix_rt::test]
+async fn test_openapi_enums_follow_registry() {
+    init();
+
+    let document = fetch_document().await;
+    let schemas = &document["components"]["schemas"];
+    assert!(names(&schemas["Category"]["enum"]).contains(&"length"));
+    assert!(names(&schemas["Unit"]["enum"]).contains(&"ft"));
+    assert!(!names(&schemas["Unit"]["enum"]).contains(&"cubit"));
+
+    let category = "#/components/schemas/Category";
+    assert_eq!(schemas["CompareRequest"]["properties"]["category"]["allOf"][0]["$ref"], category);
+    assert_eq!(schemas["ConversionRequest"]["properties"]["from_category"]["allOf"][0]["$ref"], category);
+    assert_eq!(schemas["CompareRequest"]["properties"]["unit1"]["allOf"][0]["$ref"], "#/components/schemas/Unit");
+    assert_eq!(schemas["ConversionRequest"]["properties"]["to_unit"]["anyOf"][0]["$ref"], "#/components/schemas/Unit");
+    let parameter = &document["paths"]["/api/categories/{category}/units"]["get"]["parameters"][0];
+    assert_eq!(parameter["schema"]["$ref"], category);
+
+    // Units defined at runtime show up in the next document
+    UnitService::define_unit(UnitDefinition {
+        name: "cubit".to_string(),
+        symbol: "cubit".to_string(),
+        factor: "0.4572".parse().unwrap(),
+        offset: None,
+        unit: "m".to_string(),
+        category: None,
+    }).unwrap();
+    let document = fetch_document().await;
+    assert!(names(&document["components"]["schemas"]["Unit"]["enum"]).contains(&"cubit"));
+}
+
+#[actix_rt::test]
+async fn test_docs_page() {
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+    let request = web_test::TestRequest::get().uri("/api/docs").to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert!(response.status().is_success());
+    assert!(response.headers().get("content-type").unwrap().to_str().unwrap().starts_with("text/html"));
+
+    let body = web_test::read_body(response).await;
+    let page = std::str::from_utf8(&body).unwrap();
+    assert!(page.contains("/api/openapi.json"));
+    assert!(page.contains("https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui-bundle.js\" crossorigin=\"anonymous\""));
+
+    // Without a directory of its own there's nothing to serve
+    let request = web_test::TestRequest::get().uri("/api/docs/assets/swagger-ui.css").to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 404);
+}
+
+#[actix_rt::test]
+async fn test_local_docs_assets() {
+    let config = AppConfig {
+        server: ServerConfig { host: "127.0.0.1".to_string(), port: 8080 },
+        log_level: "info".to_string(),
+        admin_token: None,
+        rates_file: None,
+        listing_order: Default::default(),
+        swagger_ui_dir: Some("tests/fixtures/swagger-ui".to_string()),
+    };
+    let app = web_test::init_service(
+        App::new().app_data(web::Data::new(config)).configure(configure_routes)
+    ).await;
+
+    let request = web_test::TestRequest::get().uri("/api/docs").to_request();
+    let body = web_test::read_body(web_test::call_service(&app, request).await).await;
+    let page = std::str::from_utf8(&body).unwrap();
+    assert!(page.contains("src=\"/api/docs/assets/swagger-ui-bundle.js\"") && !page.contains("unpkg"));
+
+    let request = web_test::TestRequest::get().uri("/api/docs/assets/swagger-ui-bundle.js").to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert!(response.headers().get("content-type").unwrap().to_str().unwrap().starts_with("text/javascript"));
+    assert!(web_test::read_body(response).await.starts_with(b"// Stand-in"));
+
+    // Only Swagger UI's own files are served from the directory
+    let request = web_test::TestRequest::get().uri("/api/docs/assets/..%2FCargo.toml").to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 404);
+}
@@ -0,0 +1,106 @@
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::services::ConversionService;
+
+fn convert(query: &str) -> f64 {
+    ConversionService::convert_query(query).unwrap().to_value
+}
+
+fn parse_error(query: &str) -> (usize, String) {
+    match ConversionService::convert_query(query) {
+        Err(ApiError::ParseError { position, token, .. }) => (position, token),
+        other => panic!("expected a parse error for '{}', got {:?}", query, other),
+    }
+}
+
+#[test]
+fn test_single_quantity_queries() {
+    init();
+
+    assert!((convert("72.5 °F in K") - 295.65).abs() < 1e-9);
+    assert!((convert("100 cm to m") - 1.0).abs() < 1e-12);
+    assert!((convert("-40 °F to °C") + 40.0).abs() < 1e-9);
+    assert!((convert("1.5e3 m to km") - 1.5).abs() < 1e-12);
+    assert!((convert("60mph to km/h") - 96.56).abs() < 0.01);
+
+    // "in" is both a separator and the inch
+    assert!((convert("3 in in cm") - 7.62).abs() < 1e-9);
+    assert!((convert("12 in to ft") - 1.0).abs() < 1e-9);
+
+    // Unit names resolve as well as symbols
+    assert!((convert("2 mile as kilometer") - 3.218688).abs() < 1e-9);
+}
+
+#[test]
+fn test_summed_quantities() {
+    init();
+
+    assert!((convert("5 ft 3 in to m") - 1.6002).abs() < 1e-9);
+    assert!((convert("3 mi + 200 yd to km") - 5.010912).abs() < 1e-9);
+    assert!((convert("1 h - 15 min to min") - 45.0).abs() < 1e-9);
+    assert!((convert("1 kg 500 g in lb") - 3.30693).abs() < 1e-5);
+
+    let response = ConversionService::convert_query("5 ft 3 in to m").unwrap();
+    assert_eq!(response.quantities.len(), 2);
+    assert_eq!(response.quantities[1].unit, "in");
+    assert_eq!(response.to_unit, "m");
+    assert_eq!(response.category, "length");
+}
+
+#[test]
+fn test_parse_errors() {
+    init();
+
+    // Unknown units point at the unit
+    assert_eq!(parse_error("5 parsec to m"), (2, "parsec".to_string()));
+    assert_eq!(parse_error("5 ft to furlongs"), (8, "furlongs".to_string()));
+
+    // Structural problems point at the offending token
+    assert_eq!(parse_error("5 to m"), (0, "5".to_string()));
+    assert_eq!(parse_error("ft to m"), (0, "ft".to_string()));
+    assert_eq!(parse_error("5 ft + to m"), (5, "+".to_string()));
+    assert_eq!(parse_error("5 ft"), (4, String::new()));
+
+    // Mixing dimensions
+    assert_eq!(parse_error("5 ft 3 kg to m"), (7, "kg".to_string()));
+    assert_eq!(parse_error("5 ft to kg"), (8, "kg".to_string()));
+
+    // Positions count characters, not bytes
+    assert_eq!(parse_error("20 °C to °Q"), (9, "°Q".to_string()));
+
+    // Positions within a unit expression are positions in the query, in the message too
+    for (query, position) in [("5 foo to m", 2), ("5 m/foo to m", 4), ("5 °C·bar·qux to K", 9)] {
+        match ConversionService::convert_query(query) {
+            Err(ApiError::ParseError { message, position: at, .. }) => {
+                assert_eq!(at, position, "{}", query);
+                assert!(message.ends_with(&format!("at position {}", position)), "{}", message);
+            },
+            other => panic!("expected a parse error for '{}', got {:?}", query, other),
+        }
+    }
+}
+
+#[test]
+fn test_non_finite_queries() {
+    init();
+
+    assert_eq!(parse_error("5e1000 m to ft"), (0, "5e1000".to_string()));
+    assert_eq!(parse_error("1 ft + 1e999 in to m"), (7, "1e999".to_string()));
+    assert!(matches!(ConversionService::convert_query("1e308 km to mm"), Err(ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_temperature_queries() {
+    init();
+
+    // Temperatures and intervals don't convert to each other, in either direction
+    assert_eq!(parse_error("20 °C to Δ°F"), (9, "Δ°F".to_string()));
+    assert_eq!(parse_error("10 Δ°C to °F"), (10, "°F".to_string()));
+    assert_eq!(parse_error("300 K + 5 ΔK to ΔK"), (16, "ΔK".to_string()));
+
+    // Intervals convert between themselves, and move a temperature along its scale
+    let response = ConversionService::convert_query("10 Δ°C to Δ°F").unwrap();
+    assert!((response.to_value - 18.0).abs() < 1e-9);
+    assert_eq!(response.category, "temperature_interval");
+    assert!((convert("300 K + 5 ΔK to K") - 305.0).abs() < 1e-9);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::prefix::Prefixes;
+use unit_conversion_api::models::registry;
+use unit_conversion_api::services::{ConversionService, ConversionRequest};
+use common::request;
+
+fn resolve(term: &str) -> Result<(String, String), ApiError> {
+    let reg = registry().read().unwrap();
+    reg.resolve_unit(term, None).map(|u| (u.symbol().to_string(), u.category_name().to_string()))
+}
+
+fn convert(value: f64, from: &str, to: &str) -> (f64, Option<String>) {
+    let response = ConversionService::convert(ConversionRequest { exact: true, ..request(value, None, from, to) }).unwrap();
+    (response.to_value, response.exact_value)
+}
+
+#[test]
+fn test_generated_units() {
+    init();
+
+    for (symbol, category) in [
+        ("Gm", "length"), ("qm", "length"), ("Qm", "length"),
+        ("pg", "mass"), ("Mg", "mass"),
+        ("hL", "volume"), ("μL", "volume"),
+        ("ks", "time"), ("mK", "temperature"),
+        ("hPa", "pressure"), ("GPa", "pressure"), ("kbar", "pressure"),
+        ("GJ", "energy"), ("MWh", "energy"), ("keV", "energy"),
+        ("mW", "power"), ("TW", "power"), ("MN", "force"),
+    ] {
+        assert_eq!(resolve(symbol).unwrap(), (symbol.to_string(), category.to_string()));
+    }
+
+    // Names, plurals and ASCII stand-ins are generated like those of built-in units
+    assert_eq!(resolve("gigajoules").unwrap().0, "GJ");
+    assert_eq!(resolve("hectopascal").unwrap().0, "hPa");
+    assert_eq!(resolve("picograms").unwrap().0, "pg");
+    assert_eq!(resolve("kiloelectron volts").unwrap().0, "keV");
+    assert_eq!(resolve("megawatt hours").unwrap().0, "MWh");
+    assert_eq!(resolve("uW").unwrap().0, "μW");
+    assert_eq!(resolve("decametres").unwrap().0, "dam");
+}
+
+#[test]
+fn test_generated_units_keep_hand_written_ones() {
+    init();
+
+    let reg = registry().read().unwrap();
+    let length: Vec<&str> = reg.get_units_for_category("length").iter().map(|u| u.symbol()).collect();
+    assert_eq!(length.iter().filter(|&&s| s == "km").count(), 1);
+    assert!(reg.get_unit("length", "km").unwrap().as_any().is::<unit_conversion_api::models::units::Kilometer>());
+
+    drop(reg);
+    assert_eq!(resolve("mL").unwrap().0, "mL");
+    assert_eq!(resolve("MPa").unwrap().0, "MPa");
+}
+
+#[test]
+fn test_prefixes_keep_their_case() {
+    init();
+
+    // Symbols differing only in the case of their prefix are different units
+    for (term, candidates) in [
+        ("mw", ["MW (megawatt, power)", "mW (milliwatt, power)"]),
+        ("mpa", ["MPa (megapascal, pressure)", "mPa (millipascal, pressure)"]),
+        ("mj", ["MJ (megajoule, energy)", "mJ (millijoule, energy)"]),
+        ("ml", ["ML (megaliter, volume)", "mL (milliliter, volume)"]),
+    ] {
+        match resolve(term) {
+            Err(ApiError::Ambiguous { candidates: found, .. }) => assert_eq!(found, candidates, "{}", term),
+            other => panic!("expected '{}' to be ambiguous, got {:?}", term, other),
+        }
+    }
+
+    // and a prefixed symbol isn't matched ignoring case even when it's the only one
+    for term in ["gw", "KM", "kpa"] {
+        assert!(matches!(resolve(term), Err(ApiError::UnitNotFound { .. })), "{}", term);
+    }
+    assert_eq!(resolve("megawatts").unwrap().0, "MW");
+}
+
+#[test]
+fn test_prefixed_conversions() {
+    init();
+
+    assert_eq!(convert(1.0, "GJ", "kWh"), (2500.0 / 9.0, Some("2500/9".to_string())));
+    assert_eq!(convert(1013.25, "hPa", "atm").1.as_deref(), Some("1"));
+    assert_eq!(convert(1.0, "mW/cm²", "W/m²").1.as_deref(), Some("10"));
+    assert_eq!(convert(1.0, "Qm", "qm").1.as_deref(), Some("1000000000000000000000000000000000000000000000000000000000000"));
+
+    let binary: Vec<&str> = Prefixes::Binary.list().iter().map(|p| p.symbol).collect();
+    assert!(binary.contains(&"k") && binary.contains(&"Ki") && binary.contains(&"Yi"));
+    assert!(!binary.contains(&"m"));
+}
@@ -0,0 +1,106 @@
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::parser::{parse_range, Range};
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{ConversionService, RangeConversionRequest, RangeConversionResponse};
+
+fn range(min: Option<f64>, max: Option<f64>, unit: &str) -> Range {
+    Range { min, max, unit: unit.to_string() }
+}
+
+fn convert(text: &str, to: &str) -> Result<RangeConversionResponse, ApiError> {
+    ConversionService::convert_range(&RangeConversionRequest {
+        range: Some(text.to_string()),
+        to_unit: to.to_string(),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_parse_range() {
+    assert_eq!(parse_range("68–77 °F").unwrap(), range(Some(68.0), Some(77.0), "°F"));
+    assert_eq!(parse_range("68 - 77 °F").unwrap(), range(Some(68.0), Some(77.0), "°F"));
+    assert_eq!(parse_range("-40 to 85 °C").unwrap(), range(Some(-40.0), Some(85.0), "°C"));
+    assert_eq!(parse_range("-40-85 °C").unwrap(), range(Some(-40.0), Some(85.0), "°C"));
+    assert_eq!(parse_range("-40 °C – -10 °C").unwrap(), range(Some(-40.0), Some(-10.0), "°C"));
+    assert_eq!(parse_range("10 kg/m³ ~ 12 kg/m³").unwrap(), range(Some(10.0), Some(12.0), "kg/m³"));
+
+    // Open-ended ranges are bounded on one side
+    assert_eq!(parse_range("≥5 bar").unwrap(), range(Some(5.0), None, "bar"));
+    assert_eq!(parse_range("<= 30 m/s").unwrap(), range(None, Some(30.0), "m/s"));
+
+    for (text, token) in [("68 °F – 77 °C", "68 °F – 77 °C"), ("68–77", ""), ("68 °F", ""), ("a–b °F", "a")] {
+        match parse_range(text) {
+            Err(ApiError::ParseError { token: found, .. }) => assert_eq!(found, token, "for '{}'", text),
+            other => panic!("expected a parse error for '{}', got {:?}", text, other),
+        }
+    }
+}
+
+#[test]
+fn test_convert_range() {
+    init();
+
+    // Both ends carry the offset, so the range isn't shifted or stretched
+    let response = convert("68–77 °F", "°C").unwrap();
+    assert!((response.to_min.unwrap() - 20.0).abs() < 1e-9);
+    assert!((response.to_max.unwrap() - 25.0).abs() < 1e-9);
+    assert_eq!((response.from_unit.as_str(), response.category.as_str()), ("°F", "temperature"));
+
+    let response = convert("≥ -40 degF", "K").unwrap();
+    assert!((response.to_min.unwrap() - 233.15).abs() < 1e-9);
+    assert_eq!(response.to_max, None);
+
+    // Unit expressions convert without a category, and named units by inference
+    let response = convert("0–60 mi/h", "m/s").unwrap();
+    assert!((response.to_max.unwrap() - 26.8224).abs() < 1e-9);
+    let response = ConversionService::convert_range(&RangeConversionRequest {
+        max: Some(2.0),
+        unit: Some("tonne".to_string()),
+        to_unit: "kg".to_string(),
+        ..Default::default()
+    }).unwrap();
+    assert_eq!((response.from_min, response.to_max), (None, Some(2000.0)));
+
+    assert!(matches!(convert("77–68 °F", "°C"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(convert("1–2 kg", "m"), Err(ApiError::BadRequest(_))));
+    assert!(matches!(convert("1–2 fot", "m"), Err(ApiError::UnitNotFound { .. })));
+
+    // Ends too large for the target unit are rejected rather than returned as null
+    let err = convert("1–1e307 km", "m").unwrap_err();
+    assert!(err.to_string().contains("isn't a finite number"), "{}", err);
+    assert!(matches!(
+        ConversionService::convert_range(&RangeConversionRequest {
+            unit: Some("kg".to_string()),
+            to_unit: "g".to_string(),
+            ..Default::default()
+        }),
+        Err(ApiError::BadRequest(_))
+    ));
+}
+
+#[actix_rt::test]
+async fn test_range_endpoint() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/convert/range")
+        .set_json(json!({ "min": -40, "max": 85, "unit": "°C", "to_unit": "°F" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["to_min"].as_f64().unwrap().round(), -40.0);
+    assert_eq!(body["to_max"].as_f64().unwrap().round(), 185.0);
+    assert_eq!(body["to_unit"], "°F");
+
+    let request = web_test::TestRequest::post().uri("/api/convert/range")
+        .set_json(json!({ "range": "up to 5 bar", "to_unit": "psi" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "parse_error");
+}
@@ -0,0 +1,94 @@
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::services::{ReplOutput, ReplSession};
+
+fn eval(session: &mut ReplSession, line: &str) -> String {
+    match session.eval(line) {
+        Ok(ReplOutput::Text(text)) => text,
+        other => panic!("expected text for '{}', got {:?}", line, other),
+    }
+}
+
+fn error(session: &mut ReplSession, line: &str) -> String {
+    match session.eval(line) {
+        Err(ApiError::BadRequest(message)) | Err(ApiError::NotFound(message))
+        | Err(ApiError::UnitNotFound { message, .. }) => message,
+        other => panic!("expected an error for '{}', got {:?}", line, other),
+    }
+}
+
+#[test]
+fn test_repl_expressions() {
+    init();
+    let mut session = ReplSession::new();
+
+    // Variables shadow units of the same name, such as t for the tonne
+    assert_eq!(eval(&mut session, "d = 12 mi"), "d = 12 mi");
+    assert_eq!(eval(&mut session, "t = 45 min"), "t = 45 min");
+    assert_eq!(eval(&mut session, "d / t to km/h"), "25.749504 km/h");
+    assert_eq!(eval(&mut session, "d / t"), "0.266666666667 mi/min");
+    assert_eq!(eval(&mut session, "v = d / t -> mi/h"), "v = 16 mi/h");
+    assert_eq!(eval(&mut session, "v * 2 h"), "32 mi");
+
+    assert_eq!(eval(&mut session, "1 km + 300 m"), "1.3 km");
+    assert_eq!(eval(&mut session, "(3 m)^2 to ft²"), "96.8751937504 ft²");
+    assert_eq!(eval(&mut session, "2 kWh in MJ"), "7.2 MJ");
+    assert_eq!(eval(&mut session, "5 in to cm"), "12.7 cm");
+    assert_eq!(eval(&mut session, "-40 °C to °F"), "-40 °F");
+    assert_eq!(eval(&mut session, "20 °C - 5 °C"), "15 Δ°C");
+    assert_eq!(eval(&mut session, "20 °C + 18 Δ°F to °F"), "86 °F");
+    assert_eq!(eval(&mut session, "20 °C * 2"), "586.3 K");
+    assert_eq!(eval(&mut session, "300 K / 2"), "150 K");
+    assert_eq!(eval(&mut session, "mi / km"), "1.609344");
+    assert_eq!(eval(&mut session, "2 * (3 + 4) ** 2"), "98");
+
+    assert!(error(&mut session, "1 km + 3 s").contains("dimensions differ"));
+    assert!(error(&mut session, "20 °C + 5 °C").contains("Cannot add the temperatures"));
+    assert!(error(&mut session, "5 + 3 m").contains("plain number"));
+    assert!(error(&mut session, "12 to km").contains("plain number"));
+    assert!(error(&mut session, "(1 m").contains("Missing ')'"));
+    assert!(error(&mut session, "1 / 0 m").contains("isn't a finite number"));
+    assert!(error(&mut session, "flurb").contains("flurb"));
+
+    // A failed assignment leaves the variable alone
+    assert!(session.eval("d = 12 flurbs").is_err());
+    assert!(session.eval("d = 1 / 0 mi").is_err());
+    assert_eq!(eval(&mut session, "d"), "12 mi");
+}
+
+#[test]
+fn test_repl_commands_and_completion() {
+    init();
+    let mut session = ReplSession::new();
+
+    assert_eq!(eval(&mut session, ":vars"), "No variables defined");
+    eval(&mut session, "distance = 5 km");
+    assert_eq!(eval(&mut session, ":vars"), "distance = 5 km");
+    assert_eq!(session.eval(":clear").unwrap(), ReplOutput::Empty);
+    assert_eq!(eval(&mut session, ":vars"), "No variables defined");
+
+    assert!(eval(&mut session, ":units").lines().any(|line| line == "length"));
+    assert!(eval(&mut session, ":units length").split(", ").any(|unit| unit == "ft"));
+    assert!(eval(&mut session, ":help").contains(":units"));
+    assert!(error(&mut session, ":frobnicate").contains("Unknown command"));
+    assert!(session.eval(":units nonsense").is_err());
+    assert_eq!(session.eval("   ").unwrap(), ReplOutput::Empty);
+    assert_eq!(session.eval(":quit").unwrap(), ReplOutput::Quit);
+
+    eval(&mut session, "distance = 5 km");
+    let (start, candidates) = session.complete("dist", 4);
+    assert_eq!((start, candidates), (0, vec!["distance".to_string()]));
+
+    let (start, candidates) = session.complete("3 k", 3);
+    assert_eq!(start, 2);
+    assert!(candidates.iter().any(|candidate| candidate == "km"));
+    assert!(candidates.iter().all(|candidate| candidate.starts_with('k')));
+
+    let (start, candidates) = session.complete("12 mi/k", 7);
+    assert_eq!(start, 6);
+    assert!(candidates.contains(&"kWh".to_string()));
+
+    assert_eq!(session.complete(":un", 3), (0, vec![":units".to_string()]));
+    let (start, candidates) = session.complete(":units len", 10);
+    assert_eq!((start, candidates), (7, vec!["length".to_string()]));
+}
@@ -0,0 +1,80 @@
+use actix_web::{test as web_test, App};
+use serde_json::Value;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::{registry, MatchKind};
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::UnitService;
+
+fn symbols(query: &str, category: Option<&str>) -> Vec<String> {
+    UnitService::search(query, category, 10).unwrap().into_iter().map(|r| r.symbol).collect()
+}
+
+#[test]
+fn test_search_ranking() {
+    init();
+
+    // Exact matches first, in the query's case before others
+    let results = UnitService::search("m", None, 10).unwrap();
+    assert_eq!((results[0].symbol.as_str(), results[0].match_kind), ("m", MatchKind::Exact));
+    assert_eq!((results[1].symbol.as_str(), results[1].category.as_str()), ("M", "speed"));
+    assert_eq!(results[2].match_kind, MatchKind::Prefix);
+    assert!(results[2].matched.starts_with('m'));
+
+    // Prefix matches, shortest term first
+    let results = UnitService::search("kilom", None, 10).unwrap();
+    assert_eq!(results[0].symbol, "km");
+    assert_eq!(results[0].matched, "kilometer");
+    assert_eq!(results[0].match_kind, MatchKind::Prefix);
+    assert!(symbols("kilom", None).contains(&"km/h".to_string()));
+
+    // Fuzzy matches catch misspellings, closest first
+    let results = UnitService::search("kilomter", None, 10).unwrap();
+    assert_eq!((results[0].symbol.as_str(), results[0].match_kind, results[0].distance), ("km", MatchKind::Fuzzy, 1));
+    assert_eq!(symbols("fahrenhiet", None), ["°F"]);
+    assert_eq!(symbols("poud", None), ["lb"]);
+
+    // Each unit appears once, under its best term
+    let feet = symbols("feet", None);
+    assert_eq!(feet.iter().filter(|s| *s == "ft").count(), 1);
+
+    assert_eq!(symbols("kilo", Some("mass")), ["kg"]);
+    // Queries too short to misspell only match exactly or by prefix
+    assert!(UnitService::search("mx", None, 50).unwrap().iter().all(|r| r.match_kind != MatchKind::Fuzzy));
+    assert!(symbols("zzzzzz", None).is_empty());
+    assert!(UnitService::search("  ", None, 10).is_err());
+    assert!(UnitService::search("m", Some("nonsense"), 10).is_err());
+
+    let reg = registry().read().unwrap();
+    let matches = reg.search_units("kilometre", None);
+    assert_eq!(matches[0].unit.symbol(), "km");
+}
+
+#[actix_rt::test]
+async fn test_search_route() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::get().uri("/api/units/search?q=fo&limit=3").to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = web_test::read_body_json(response).await;
+    let results = body.as_array().unwrap();
+    assert!(results.len() <= 3);
+    assert_eq!(results[0]["symbol"], "ft");
+    assert_eq!(results[0]["category"], "length");
+    assert_eq!(results[0]["matched"], "foot");
+    assert_eq!(results[0]["match_kind"], "prefix");
+
+    let request = web_test::TestRequest::get().uri("/api/units/search?q=degre%20celsius&category=temperature").to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert_eq!(body[0]["symbol"], "°C");
+
+    let request = web_test::TestRequest::get().uri("/api/units/search?q=").to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "invalid_search");
+
+    let request = web_test::TestRequest::get().uri("/api/units/search?q=m&category=nonsense").to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 404);
+}
@@ -1,4 +1,3 @@
-use unit_conversion_api::models::registry;
 use unit_conversion_api::models::init::init;
 use unit_conversion_api::models::units::speed::{
     MeterPerSecond, KilometerPerHour, MilePerHour, 
@@ -0,0 +1,77 @@
+mod common;
+
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::routes::configure_routes;
+use common::convert_value;
+
+fn not_found(result: Result<f64, ApiError>) -> (String, Vec<String>) {
+    match result {
+        Err(ApiError::UnitNotFound { message, suggestions }) => (message, suggestions),
+        other => panic!("expected a unit that isn't registered, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_unit_suggestions() {
+    init();
+
+    let (message, suggestions) = not_found(convert_value(1.0, Some("length"), "kilomter", "m"));
+    assert_eq!(suggestions, ["kilometer"]);
+    assert_eq!(message, "Unit 'kilomter' not found in category 'length'; did you mean 'kilometer'?");
+
+    let (_, suggestions) = not_found(convert_value(1.0, Some("speed"), "m/s", "kmph"));
+    assert_eq!(suggestions, ["kmh", "mph"]);
+
+    // Suggestions come from the category being converted in
+    let (_, suggestions) = not_found(convert_value(1.0, Some("temperature"), "K", "farenheit"));
+    assert_eq!(suggestions, ["fahrenheit"]);
+
+    let (message, suggestions) = not_found(convert_value(1.0, Some("length"), "m", "xyzzy"));
+    assert_eq!(message, "Unit 'xyzzy' not found in category 'length'");
+    assert!(suggestions.is_empty());
+
+    // Unit expressions suggest from every category
+    let err = convert_value(1.0, None, "kg/metr", "kg/m").unwrap_err();
+    assert!(matches!(&err, ApiError::UnitNotFound { suggestions, .. } if suggestions[0] == "metre"));
+}
+
+#[test]
+fn test_unit_in_another_category() {
+    init();
+
+    let (message, _) = not_found(convert_value(1.0, Some("length"), "kg", "m"));
+    assert_eq!(message, "Unit 'kg' is in category 'mass', not 'length'");
+    let (message, _) = not_found(convert_value(1.0, Some("length"), "m", "mph"));
+    assert_eq!(message, "Unit 'mph' is in category 'speed', not 'length'");
+
+    // A category that doesn't exist is reported as such
+    assert!(matches!(convert_value(1.0, Some("nonsense"), "m", "ft"), Err(ApiError::NotFound(_))));
+}
+
+#[actix_rt::test]
+async fn test_suggestions_in_responses() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/convert")
+        .set_json(json!({ "value": 1, "from_category": "length", "from_unit": "fot", "to_unit": "m" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 404);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "unit_not_found");
+    assert_eq!(body["suggestions"], json!(["ft"]));
+    assert_eq!(body["message"], "Unit 'fot' not found in category 'length'; did you mean 'ft'?");
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1, "unit1": "kg", "value2": 1, "unit2": "m", "category": "length" }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 404);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["message"], "Unit 'kg' is in category 'mass', not 'length'");
+    assert_eq!(body["suggestions"], json!([]));
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use common::convert;
+
+#[test]
+fn test_interval_conversions() {
+    init();
+
+    // A 10 °F rise is a rise of 50/9 °C, with no offset involved
+    let response = convert(10.0, Some("temperature_interval"), "Δ°F", "Δ°C").unwrap();
+    assert!((response.to_value - 50.0 / 9.0).abs() < 1e-12);
+    assert_eq!(response.category, "temperature_interval");
+
+    // The category is inferred, and intervals have ASCII and spelled-out names
+    assert!((convert(10.0, None, "delta degF", "ΔK").unwrap().to_value - 50.0 / 9.0).abs() < 1e-12);
+    assert!((convert(9.0, None, "delta degrees fahrenheit", "delta celsius").unwrap().to_value - 5.0).abs() < 1e-12);
+    assert!((convert(1.0, None, "∆°C", "Δ°R").unwrap().to_value - 1.8).abs() < 1e-12);
+
+    // Intervals take part in unit expressions where absolute temperatures can't
+    let u_value = convert(1.0, None, "BTU/(h·ft²·Δ°F)", "W/(m²·K)").unwrap();
+    assert!((u_value.to_value - 5.678263341).abs() < 1e-9);
+}
+
+#[test]
+fn test_intervals_are_not_temperatures() {
+    init();
+
+    let err = convert(10.0, None, "Δ°F", "°C").unwrap_err();
+    assert_eq!(err.to_string(), "Bad request: Cannot convert between different categories: 'temperature_interval' and 'temperature'");
+    assert!(matches!(convert(1.0, None, "ΔK", "K"), Err(ApiError::BadRequest(_))));
+
+    match convert(10.0, Some("temperature"), "°C", "Δ°C") {
+        Err(ApiError::UnitNotFound { message, .. }) => {
+            assert_eq!(message, "Unit 'Δ°C' is in category 'temperature_interval', not 'temperature'; did you mean '°C'?");
+        },
+        other => panic!("expected an interval outside the temperature category, got {:?}", other),
+    }
+}
@@ -1,6 +1,5 @@
 use unit_conversion_api::models::units::temperature::*;
 use unit_conversion_api::models::Measurement;
-use unit_conversion_api::models::unit::Unit;
 
 #[test]
 fn test_temperature_conversions() {
@@ -137,41 +136,51 @@ fn test_temperature_arithmetic() {
     // Create units
     let kelvin = Kelvin::new();
     let celsius = Celsius::new();
+    let fahrenheit = Fahrenheit::new();
 
-    // For temperature arithmetic, we need to handle both relative temperature 
-    // changes and absolute temperature values differently
+    // Temperatures form an affine space: adding an interval to a temperature
+    // gives a temperature, and the difference of two is an interval. Adding two
+    // temperatures doesn't compile.
 
-    // ----- Test with Celsius -----
-    // Start with 0°C = 273.15K
+    // ----- Temperature plus interval -----
+    // 0°C + 10 Δ°C = 10°C
     let freezing_celsius = Measurement::new(0.0, celsius);
-    
-    // 0°C + 10°C = 10°C (283.15K)
-    let plus_ten_c = freezing_celsius.clone() + Measurement::new(10.0, celsius);
-    println!("0°C + 10°C = {}K (Expected: 283.15K)", plus_ten_c.value());
-    assert!((plus_ten_c.value() - 283.15).abs() < 0.01, 
-            "Expected 283.15, got {}", plus_ten_c.value());
-    
-    // ----- Test with Kelvin -----
-    // Start with 273.15K (0°C)
+    let plus_ten_c = freezing_celsius.clone() + Measurement::new(10.0, CelsiusInterval::new());
+    assert_eq!(plus_ten_c.to_string(), "10 °C");
+
+    // 0°C + 18 Δ°F = 10°C, since a Fahrenheit degree is 5/9 of a Celsius one
+    let plus_f = freezing_celsius.clone() + Measurement::new(18.0, FahrenheitInterval::new());
+    assert!((plus_f.value() - 10.0).abs() < 1e-12, "Expected 10, got {}", plus_f.value());
+
+    // 273.15K + 10 ΔK = 283.15K
     let freezing_kelvin = Measurement::new(273.15, kelvin);
-    
-    // 273.15K + 10K = 283.15K
-    let k_plus_ten_k = freezing_kelvin.clone() + Measurement::new(10.0, kelvin);
-    println!("273.15K + 10K = {}K (Expected: 283.15K)", k_plus_ten_k.value());
-    assert!((k_plus_ten_k.value() - 283.15).abs() < 0.01, 
+    let k_plus_ten_k = freezing_kelvin.clone() + Measurement::new(10.0, KelvinInterval::new());
+    assert!((k_plus_ten_k.value() - 283.15).abs() < 1e-9,
             "Expected 283.15, got {}", k_plus_ten_k.value());
-            
-    // Special case: Adding Kelvin to Celsius
-    // For this test we're expecting:
-    // 0°C (as 273.15K) + 10K = 283.15K
-    // This is because we're adding 10 absolute Kelvin to the absolute 
-    // temperature value of 0°C (273.15K)
-    // This is an indirect test since our model converts everything to Kelvin for operations
-    let c_plus_k = Measurement::new(0.0, celsius).convert_to(kelvin).unwrap().value() + 10.0; 
-    println!("0°C + 10K = {}K (Expected: 283.15K)", c_plus_k);
-    assert!((c_plus_k - 283.15).abs() < 0.01, 
-            "Expected 283.15, got {}", c_plus_k);
-            
+
+    // 50°F - 10 Δ°C = 32°F
+    let minus = Measurement::new(50.0, fahrenheit) - Measurement::new(10.0, CelsiusInterval::new());
+    assert!((minus.value() - 32.0).abs() < 1e-12, "Expected 32, got {}", minus.value());
+
+    // ----- Temperature minus temperature -----
+    // 20°C - 5°C = 15 Δ°C, not a temperature near absolute zero
+    let rise = Measurement::new(20.0, celsius) - Measurement::new(5.0, celsius);
+    assert_eq!(rise.to_string(), "15 Δ°C");
+
+    // The difference is on the scale of the first temperature: 68°F - 10°C = 18 Δ°F
+    let rise_f = Measurement::new(68.0, fahrenheit) - Measurement::new(10.0, celsius);
+    assert_eq!(rise_f.to_string(), "18 Δ°F");
+    assert!((rise_f.convert_to(KelvinInterval::new()).unwrap().value() - 10.0).abs() < 1e-12);
+
+    // ----- Interval arithmetic -----
+    // Intervals add like any other quantity: 10 Δ°F + 5 Δ°C = 19 Δ°F
+    let intervals = Measurement::new(10.0, FahrenheitInterval::new()) + Measurement::new(5.0, CelsiusInterval::new());
+    assert!((intervals.value() - 19.0).abs() < 1e-12, "Expected 19, got {}", intervals.value());
+
+    // A 10°F rise is a rise of 50/9 °C, with no offset involved
+    let rise = Measurement::new(10.0, FahrenheitInterval::new()).convert_to(CelsiusInterval::new()).unwrap();
+    assert!((rise.value() - 50.0 / 9.0).abs() < 1e-12, "Expected 5.556, got {}", rise.value());
+
     // Test multiplication and division using Kelvin (absolute scale)
     let doubled = freezing_kelvin.clone() * 2.0;
     println!("273.15K * 2 = {}K (Expected: 546.3K)", doubled.value());
@@ -182,4 +191,4 @@ fn test_temperature_arithmetic() {
     println!("273.15K / 2 = {}K (Expected: 136.575K)", halved.value());
     assert!((halved.value() - 136.575).abs() < 0.01, 
             "Expected 136.575, got {}", halved.value());
-} 
\ No newline at end of file
+}
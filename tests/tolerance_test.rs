@@ -0,0 +1,121 @@
+use std::cmp::Ordering;
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::tolerance::{ulps_between, Tolerance};
+use unit_conversion_api::models::units::length::{Centimeter, Meter};
+use unit_conversion_api::models::units::power::{Gigawatt, Watt};
+use unit_conversion_api::models::Measurement;
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::ConversionService;
+
+#[test]
+fn test_tolerance() {
+    assert_eq!(ulps_between(1.0, 1.0 + f64::EPSILON), 1);
+    assert_eq!(ulps_between(-0.0, 0.0), 0);
+    assert_eq!(ulps_between(-f64::MIN_POSITIVE, f64::MIN_POSITIVE), 2 << 52);
+
+    // The default allows a few ULPs whatever the magnitude
+    let tolerance = Tolerance::default();
+    assert!(tolerance.approx_eq(0.1 + 0.2, 0.3));
+    assert!(tolerance.approx_eq(1e9 + 2e-7, 1e9));
+    assert!(!tolerance.approx_eq(1e9 + 1e-5, 1e9));
+    assert!(!tolerance.approx_eq(1e-300, 0.0));
+
+    // Values are equal within any of the bounds given
+    let tolerance = Tolerance::new(Some(0.5), Some(0.01), None).unwrap();
+    assert!(tolerance.approx_eq(10.4, 10.0));
+    assert!(tolerance.approx_eq(1000.0, 1009.0));
+    assert!(!tolerance.approx_eq(10.6, 10.0));
+    assert!(!Tolerance::new(None, None, Some(0)).unwrap().approx_eq(0.1 + 0.2, 0.3));
+
+    assert!(matches!(Tolerance::new(Some(-1.0), None, None), Err(ApiError::BadRequest(_))));
+    assert!(matches!(Tolerance::new(None, Some(f64::NAN), None), Err(ApiError::BadRequest(_))));
+}
+
+#[test]
+fn test_measurement_equality() {
+    // Rounding left by a conversion doesn't make measurements unequal
+    assert_eq!(Measurement::new(1.0000000000000002, Gigawatt::new()), Measurement::new(1e9, Watt::new()));
+    assert_eq!(Measurement::new(0.1 + 0.2, Meter::new()), Measurement::new(30.0, Centimeter::new()));
+    assert_ne!(Measurement::new(1.001, Gigawatt::new()), Measurement::new(1e9, Watt::new()));
+
+    // Ordering agrees with equality
+    let sum = Measurement::new(0.1 + 0.2, Meter::new());
+    assert_eq!(sum.partial_cmp(&Measurement::new(30.0, Centimeter::new())), Some(Ordering::Equal));
+    assert!(sum <= Measurement::new(30.0, Centimeter::new()) && sum >= Measurement::new(30.0, Centimeter::new()));
+    assert!(Measurement::new(1.001, Gigawatt::new()) > Measurement::new(1e9, Watt::new()));
+    assert_eq!(Measurement::new(f64::NAN, Meter::new()).partial_cmp(&Measurement::new(1.0, Meter::new())), None);
+
+    let tolerance = Tolerance::new(Some(0.5), None, None).unwrap();
+    assert!(Measurement::new(1.004, Meter::new()).approx_eq(&Measurement::new(100.0, Centimeter::new()), &tolerance));
+    assert!(!Measurement::new(1.006, Meter::new()).approx_eq(&Measurement::new(100.0, Centimeter::new()), &tolerance));
+}
+
+#[test]
+fn test_compare_within_tolerance() {
+    init();
+
+    assert_eq!(ConversionService::compare(1.0, "in", 2.54, "cm", None).unwrap(), 0);
+    assert_eq!(ConversionService::compare(1.0, "GW", 1e9, "W", None).unwrap(), 0);
+    assert_eq!(ConversionService::compare(1.0000000000000002, "GW", 1e9, "W", None).unwrap(), 0);
+    assert_eq!(ConversionService::compare(0.1 + 0.2, "m", 30.0, "cm", None).unwrap(), 0);
+
+    // A measured 100.04 mm is within 0.05 mm of a 10 cm spec, but not 0.03 mm
+    let tolerance = Tolerance::new(Some(0.005), None, None).unwrap();
+    let comparison = ConversionService::compare_within(100.04, "mm", 10.0, "cm", None, &tolerance).unwrap();
+    assert_eq!(comparison.result, 0);
+    assert!((comparison.difference - 0.004).abs() < 1e-12);
+    assert!((comparison.ratio.unwrap() - 1.0004).abs() < 1e-12);
+
+    let tolerance = Tolerance::new(Some(0.003), None, None).unwrap();
+    assert_eq!(ConversionService::compare_within(100.04, "mm", 10.0, "cm", None, &tolerance).unwrap().result, 1);
+
+    // Tolerances apply to the values in the second unit, offsets included
+    let tolerance = Tolerance::new(None, Some(0.01), None).unwrap();
+    let comparison = ConversionService::compare_within(68.5, "°F", 20.0, "°C", None, &tolerance).unwrap();
+    assert_eq!(comparison.result, 1);
+    assert!((comparison.difference - 5.0 / 18.0).abs() < 1e-9);
+    let comparison = ConversionService::compare_within(32.0, "°F", 0.0, "°C", None, &Tolerance::default()).unwrap();
+    assert_eq!((comparison.result, comparison.ratio), (0, None));
+
+    // Values that aren't numbers have no order
+    assert!(matches!(ConversionService::compare(f64::NAN, "m", 1.0, "cm", None), Err(ApiError::BadRequest(_))));
+}
+
+#[actix_rt::test]
+async fn test_compare_endpoint_tolerances() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1, "unit1": "in", "value2": 2.54, "unit2": "cm" }))
+        .to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert_eq!(body, json!({ "result": 0, "relation": "equal", "difference": 0.0, "ratio": 1.0 }));
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({
+            "value1": 1005, "unit1": "W", "value2": 1, "unit2": "kW",
+            "rel_tolerance": 0.01, "ulp_tolerance": 0,
+        }))
+        .to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert_eq!(body["relation"], "equal");
+    assert!((body["difference"].as_f64().unwrap() - 0.005).abs() < 1e-12);
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1005, "unit1": "W", "value2": 1, "unit2": "kW", "abs_tolerance": 0.001 }))
+        .to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert_eq!(body["relation"], "greater_than");
+
+    let request = web_test::TestRequest::post().uri("/api/compare")
+        .set_json(json!({ "value1": 1, "unit1": "m", "value2": 1, "unit2": "m", "abs_tolerance": -1 }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = web_test::read_body_json(response).await;
+    assert_eq!(body["error"], "invalid_comparison");
+}
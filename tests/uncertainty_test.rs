@@ -0,0 +1,149 @@
+mod common;
+
+use actix_web::{test as web_test, App};
+use serde_json::{json, Value};
+use unit_conversion_api::errors::ApiError;
+use unit_conversion_api::models::init::init;
+use unit_conversion_api::models::units::length::{Centimeter, Inch, Meter};
+use unit_conversion_api::models::units::temperature::{Celsius, Fahrenheit, FahrenheitInterval};
+use unit_conversion_api::models::{Measurement, Uncertainty};
+use unit_conversion_api::routes::configure_routes;
+use unit_conversion_api::services::{ConversionService, ConversionRequest};
+use common::request;
+
+fn sigma(uncertainty: Option<&Uncertainty>) -> f64 {
+    match uncertainty {
+        Some(Uncertainty::StandardDeviation(sigma)) => *sigma,
+        other => panic!("expected a standard deviation, got {:?}", other),
+    }
+}
+
+fn bounds(uncertainty: Option<&Uncertainty>) -> (f64, f64) {
+    match uncertainty {
+        Some(Uncertainty::Interval { lower, upper }) => (*lower, *upper),
+        other => panic!("expected an interval, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_standard_deviation_propagation() {
+    let length = Measurement::new(10.0, Inch::new()).with_uncertainty(Uncertainty::StandardDeviation(0.1));
+    assert_eq!(length.to_string(), "10 ± 0.1 in");
+
+    // Converting scales the standard deviation by the factor, ignoring offsets
+    let converted = length.convert_to(Centimeter::new()).unwrap();
+    assert!((converted.value() - 25.4).abs() < 1e-12);
+    assert!((sigma(converted.uncertainty()) - 0.254).abs() < 1e-12);
+
+    let temperature = Measurement::new(20.0, Celsius::new()).with_uncertainty(Uncertainty::StandardDeviation(0.5));
+    let converted = temperature.convert_to(Fahrenheit::new()).unwrap();
+    assert!((converted.value() - 68.0).abs() < 1e-12);
+    assert!((sigma(converted.uncertainty()) - 0.9).abs() < 1e-12);
+
+    // Uncorrelated standard deviations add in quadrature, in the unit of the left
+    let width = Measurement::new(1.0, Meter::new()).with_uncertainty(Uncertainty::StandardDeviation(0.003));
+    let margin = Measurement::new(40.0, Centimeter::new()).with_uncertainty(Uncertainty::StandardDeviation(0.4));
+    let total = width.clone() + margin.clone();
+    assert!((total.value() - 1.4).abs() < 1e-12);
+    assert!((sigma(total.uncertainty()) - 0.005).abs() < 1e-12);
+    assert!((sigma((width.clone() - margin).uncertainty()) - 0.005).abs() < 1e-12);
+
+    // An exact value leaves the other's uncertainty as it is, and scaling scales it
+    assert!((sigma((width.clone() + Measurement::new(1.0, Meter::new())).uncertainty()) - 0.003).abs() < 1e-12);
+    assert!((sigma((width.clone() * 3.0).uncertainty()) - 0.009).abs() < 1e-12);
+    assert!((sigma((width / -2.0).uncertainty()) - 0.0015).abs() < 1e-12);
+}
+
+#[test]
+fn test_interval_propagation() {
+    let length = Measurement::new(10.0, Centimeter::new())
+        .with_uncertainty(Uncertainty::Interval { lower: 9.5, upper: 11.0 });
+    assert_eq!(length.to_string(), "10 cm [9.5, 11]");
+
+    let converted = length.convert_to(Meter::new()).unwrap();
+    let (lower, upper) = bounds(converted.uncertainty());
+    assert!((lower - 0.095).abs() < 1e-12 && (upper - 0.11).abs() < 1e-12);
+
+    // Subtracting pairs each bound with the opposite one of the other
+    let other = Measurement::new(4.0, Centimeter::new())
+        .with_uncertainty(Uncertainty::Interval { lower: 3.0, upper: 4.5 });
+    assert_eq!(bounds((length.clone() + other.clone()).uncertainty()), (12.5, 15.5));
+    assert_eq!(bounds((length.clone() - other).uncertainty()), (5.0, 8.0));
+    assert_eq!(bounds((length.clone() * -2.0).uncertainty()), (-22.0, -19.0));
+
+    // A standard deviation combined with an interval counts as ±σ bounds
+    let other = Measurement::new(4.0, Centimeter::new()).with_uncertainty(Uncertainty::StandardDeviation(0.5));
+    assert_eq!(bounds((length + other).uncertainty()), (13.0, 15.5));
+}
+
+#[test]
+fn test_temperature_uncertainty() {
+    // The difference of two temperatures is an interval with both uncertainties
+    let inside = Measurement::new(20.0, Celsius::new()).with_uncertainty(Uncertainty::StandardDeviation(0.3));
+    let outside = Measurement::new(41.0, Fahrenheit::new()).with_uncertainty(Uncertainty::StandardDeviation(0.72));
+    let difference = inside.clone() - outside;
+    assert!((difference.value() - 15.0).abs() < 1e-12);
+    assert!((sigma(difference.uncertainty()) - 0.5).abs() < 1e-12);
+
+    let rise = Measurement::new(9.0, FahrenheitInterval::new())
+        .with_uncertainty(Uncertainty::Interval { lower: 8.1, upper: 9.9 });
+    let (lower, upper) = bounds((inside + rise).uncertainty());
+    assert!((lower - 24.2).abs() < 1e-9 && (upper - 25.8).abs() < 1e-9);
+}
+
+#[test]
+fn test_convert_with_uncertainty() {
+    init();
+
+    let convert = |value: f64, from: &str, to: &str, uncertainty| ConversionService::convert(ConversionRequest {
+        uncertainty: Some(uncertainty),
+        ..request(value, None, from, to)
+    });
+
+    let response = convert(20.0, "°C", "°F", Uncertainty::StandardDeviation(0.5)).unwrap();
+    assert!((sigma(response.uncertainty.as_ref()) - 0.9).abs() < 1e-12);
+    let response = convert(1.0, "mi/h", "m/s", Uncertainty::Interval { lower: 0.5, upper: 1.0 }).unwrap();
+    let (lower, upper) = bounds(response.uncertainty.as_ref());
+    assert!((lower - 0.22352).abs() < 1e-12 && (upper - 0.44704).abs() < 1e-12);
+
+    assert!(matches!(
+        convert(1.0, "m", "ft", Uncertainty::StandardDeviation(-0.1)),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        convert(1.0, "m", "ft", Uncertainty::Interval { lower: 1.5, upper: 2.0 }),
+        Err(ApiError::BadRequest(_))
+    ));
+}
+
+#[actix_rt::test]
+async fn test_convert_endpoint_uncertainty() {
+    init();
+    let app = web_test::init_service(App::new().configure(configure_routes)).await;
+
+    let request = web_test::TestRequest::post().uri("/api/convert")
+        .set_json(json!({
+            "value": 12.5, "from_unit": "mg", "to_unit": "g",
+            "uncertainty": { "standard_deviation": 0.2 },
+        }))
+        .to_request();
+    let response = web_test::call_service(&app, request).await;
+    assert_eq!(response.status(), 200);
+    let body: Value = web_test::read_body_json(response).await;
+    assert!((body["uncertainty"]["standard_deviation"].as_f64().unwrap() - 0.0002).abs() < 1e-15);
+
+    // Without an uncertainty in the request there's none in the response
+    let request = web_test::TestRequest::post().uri("/api/convert")
+        .set_json(json!({ "value": 12.5, "from_unit": "mg", "to_unit": "g" }))
+        .to_request();
+    let body: Value = web_test::read_body_json(web_test::call_service(&app, request).await).await;
+    assert!(body.get("uncertainty").is_none());
+
+    let request = web_test::TestRequest::post().uri("/api/convert")
+        .set_json(json!({
+            "value": 1, "from_unit": "m", "to_unit": "ft",
+            "uncertainty": { "interval": { "lower": 2, "upper": 1 } },
+        }))
+        .to_request();
+    assert_eq!(web_test::call_service(&app, request).await.status(), 400);
+}
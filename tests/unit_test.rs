@@ -1,5 +1,5 @@
 use std::fmt::{Display, Formatter, Result as FmtResult};
-use unit_conversion_api::models::{Unit, UnitCategory, Measurement};
+use unit_conversion_api::models::{Unit, UnitCategory, LinearCategory, Measurement};
 
 // Create a simple test unit category
 #[derive(Debug, Clone, Copy)]
@@ -21,6 +21,9 @@ impl UnitCategory for LengthCategory {
     }
 }
 
+// Lengths can be added and subtracted
+impl LinearCategory for LengthCategory {}
+
 // Create a test meter unit (base unit)
 #[derive(Debug, Clone, Copy)]
 struct Meter(LengthCategory);
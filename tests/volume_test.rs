@@ -20,7 +20,7 @@ fn test_volume_conversions() {
     
     // Create measurements
     let one_cubic_meter = Measurement::new(1.0, cubic_meter);
-    let thousand_liter = Measurement::new(1000.0, liter);
+    let _thousand_liter = Measurement::new(1000.0, liter);
     let one_liter = Measurement::new(1.0, liter);
     let one_us_gallon = Measurement::new(1.0, us_gallon);
     